@@ -0,0 +1,453 @@
+//! Step-by-step animation of the days that are simulations, drawn as text in
+//! the terminal by `aoc run <day> --animate`.
+//!
+//! A [`Simulation`] advances one step at a time and draws its state as a
+//! [`Frame`]. The [`Player`] steps it at the chosen rate and shows the part
+//! of the frame around its focus, the falling grain or the head of the rope,
+//! through a [`Viewport`] that scrolls along.
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::grid::Grid;
+
+/// Frames drawn per second at most. Faster simulations take several steps
+/// per frame.
+const MAX_FRAME_RATE: f64 = 60.0;
+const MIN_STEPS_PER_SECOND: f64 = 0.25;
+const HELP: &str = "space: pause  n: step  +/-: faster/slower  q: quit";
+
+pub trait Simulation {
+    /// Advances the simulation by one step. Returns false, without changing
+    /// anything, once the simulation is over.
+    fn step(&mut self) -> bool;
+
+    /// The current state
+    fn frame(&self) -> Frame;
+}
+
+/// A drawing of the state of a simulation.
+///
+/// Positions are `(x, y)` with y growing downwards, in coordinates of the
+/// simulation rather than of the cells, so that frames that grow or move
+/// keep their place on the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub cells: Grid<char>,
+    /// Position of the top left cell
+    pub origin: (i64, i64),
+    /// Position of the active region, which is kept in view
+    pub focus: (i64, i64),
+    /// Shown below the frame, e.g. the round number
+    pub status: String,
+}
+
+impl Frame {
+    /// A frame of `cells` at (0, 0), with the focus on the top left cell
+    pub fn new(cells: Grid<char>) -> Frame {
+        Frame {
+            cells,
+            origin: (0, 0),
+            focus: (0, 0),
+            status: String::new(),
+        }
+    }
+
+    /// A frame of `lines` of text, padded with spaces
+    pub fn text(lines: &[String]) -> Frame {
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let rows = lines
+            .iter()
+            .map(|l| {
+                let mut row: Vec<char> = l.chars().collect();
+                row.resize(width, ' ');
+                row
+            })
+            .collect();
+        Frame::new(Grid::from_rows(rows))
+    }
+}
+
+/// The part of a frame shown on the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// Position of the top left cell shown
+    pub x: i64,
+    pub y: i64,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn new(width: usize, height: usize) -> Viewport {
+        Viewport {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    /// Scrolls as little as needed to keep the focus of `frame` a quarter of
+    /// the viewport away from its edges, without showing more than needed
+    /// beyond the edges of the frame.
+    pub fn follow(&mut self, frame: &Frame) {
+        self.x = follow(
+            self.x,
+            self.width,
+            frame.focus.0,
+            frame.origin.0,
+            frame.cells.width(),
+        );
+        self.y = follow(
+            self.y,
+            self.height,
+            frame.focus.1,
+            frame.origin.1,
+            frame.cells.height(),
+        );
+    }
+
+    /// The rows of `frame` in view, padded with spaces outside of it
+    pub fn draw(&self, frame: &Frame) -> Vec<String> {
+        (self.y..self.y + self.height as i64)
+            .map(|y| {
+                (self.x..self.x + self.width as i64)
+                    .map(|x| {
+                        frame
+                            .cells
+                            .pos(x - frame.origin.0, y - frame.origin.1)
+                            .map_or(' ', |pos| frame.cells[pos])
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Start of the view along one axis
+fn follow(start: i64, size: usize, focus: i64, frame_start: i64, frame_size: usize) -> i64 {
+    let (size, frame_size) = (size as i64, frame_size as i64);
+    let margin = size / 4;
+    let mut start = start;
+    if focus < start + margin {
+        start = focus - margin;
+    } else if focus >= start + size - margin {
+        start = focus - size + margin + 1;
+    }
+    if frame_size <= size {
+        frame_start
+    } else {
+        start.clamp(frame_start, frame_start + frame_size - size)
+    }
+}
+
+/// Keys of the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    TogglePause,
+    /// A single step, while paused
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    pub fn from_key(key: u8) -> Option<Control> {
+        match key {
+            b' ' | b'p' => Some(Control::TogglePause),
+            b'n' | b'.' | b'\n' => Some(Control::Step),
+            b'+' | b'=' => Some(Control::Faster),
+            b'-' | b'_' => Some(Control::Slower),
+            b'q' => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// What to do after waiting for the next frame
+enum Wait {
+    Steps(u64),
+    Redraw,
+    Quit,
+}
+
+/// Plays a simulation in the terminal.
+pub struct Player {
+    /// Simulation steps per second
+    pub steps_per_second: f64,
+    pub paused: bool,
+    pub viewport: Viewport,
+}
+
+impl Player {
+    /// A player for a terminal of `rows` and `columns`, leaving two rows for
+    /// the status and the keys
+    pub fn new(steps_per_second: f64, rows: usize, columns: usize) -> Player {
+        Player {
+            steps_per_second,
+            paused: false,
+            viewport: Viewport::new(columns, rows.saturating_sub(2).max(1)),
+        }
+    }
+
+    /// Plays `simulation` until it is over or `q` is pressed, drawing to `out`.
+    /// Returns the number of steps taken.
+    pub fn play(
+        &mut self,
+        simulation: &mut dyn Simulation,
+        out: &mut impl Write,
+        controls: &Receiver<Control>,
+    ) -> io::Result<u64> {
+        // Clear the screen and hide the cursor
+        write!(out, "\x1b[2J\x1b[?25l")?;
+        let mut steps = 0;
+        let mut over = false;
+        loop {
+            let frame = simulation.frame();
+            self.viewport.follow(&frame);
+            self.draw(&frame, steps, over, out)?;
+            if over {
+                break;
+            }
+            match self.wait(controls) {
+                Wait::Steps(n) => {
+                    for _ in 0..n {
+                        if !simulation.step() {
+                            over = true;
+                            break;
+                        }
+                        steps += 1;
+                    }
+                }
+                Wait::Redraw => {}
+                Wait::Quit => break,
+            }
+        }
+        write!(out, "\x1b[?25h")?;
+        out.flush()?;
+        Ok(steps)
+    }
+
+    fn draw(&self, frame: &Frame, steps: u64, over: bool, out: &mut impl Write) -> io::Result<()> {
+        let mut screen = String::from("\x1b[H");
+        for line in self.viewport.draw(frame) {
+            screen += &line;
+            screen += "\x1b[K\n";
+        }
+        let state = if over {
+            "over"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        screen += &format!(
+            "step {}  {} steps/s  {}  {}\x1b[K\n{}\x1b[K",
+            steps, self.steps_per_second, state, frame.status, HELP
+        );
+        out.write_all(screen.as_bytes())?;
+        out.flush()
+    }
+
+    /// Waits for the next frame, handling the controls in the meantime
+    fn wait(&mut self, controls: &Receiver<Control>) -> Wait {
+        let steps = (self.steps_per_second / MAX_FRAME_RATE).ceil().max(1.0);
+        let period = Duration::from_secs_f64(steps / self.steps_per_second);
+        let start = Instant::now();
+        loop {
+            let control = if self.paused {
+                match controls.recv() {
+                    Ok(control) => control,
+                    // Nothing can resume it anymore
+                    Err(_) => {
+                        self.paused = false;
+                        return Wait::Redraw;
+                    }
+                }
+            } else {
+                let remaining = period.saturating_sub(start.elapsed());
+                match controls.recv_timeout(remaining) {
+                    Ok(control) => control,
+                    Err(RecvTimeoutError::Timeout) => return Wait::Steps(steps as u64),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        thread::sleep(remaining);
+                        return Wait::Steps(steps as u64);
+                    }
+                }
+            };
+            match control {
+                Control::TogglePause => {
+                    self.paused = !self.paused;
+                    return Wait::Redraw;
+                }
+                Control::Step if self.paused => return Wait::Steps(1),
+                Control::Step => {}
+                Control::Faster => {
+                    self.steps_per_second *= 2.0;
+                    return Wait::Redraw;
+                }
+                Control::Slower => {
+                    self.steps_per_second = (self.steps_per_second / 2.0).max(MIN_STEPS_PER_SECOND);
+                    return Wait::Redraw;
+                }
+                Control::Quit => return Wait::Quit,
+            }
+        }
+    }
+}
+
+/// The terminal of an animation. Where `stty` can switch it over, keys are
+/// read as they are pressed rather than after Enter; dropping the terminal
+/// switches it back.
+pub struct Terminal {
+    raw: bool,
+}
+
+impl Terminal {
+    /// Opens the terminal and reads the controls from stdin in the background.
+    pub fn open() -> (Terminal, Receiver<Control>) {
+        let raw = stty(&["-icanon", "-echo"]);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for key in io::stdin().lock().bytes() {
+                let Ok(key) = key else { break };
+                let Some(control) = Control::from_key(key) else {
+                    continue;
+                };
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        });
+        (Terminal { raw }, receiver)
+    }
+
+    /// Rows and columns, from `stty size` or else the `LINES` and `COLUMNS`
+    /// environment variables, 24x80 by default
+    pub fn size() -> (usize, usize) {
+        let size = Command::new("stty")
+            .arg("size")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()
+            .and_then(|output| {
+                let size = String::from_utf8(output.stdout).ok()?;
+                let (rows, columns) = size.trim().split_once(' ')?;
+                Some((rows.parse().ok()?, columns.parse().ok()?))
+            });
+        size.unwrap_or_else(|| {
+            let variable = |name, default| {
+                std::env::var(name)
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(default)
+            };
+            (variable("LINES", 24), variable("COLUMNS", 80))
+        })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        if self.raw {
+            stty(&["icanon", "echo"]);
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> bool {
+    Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A dot moving right along a line of 100 cells
+    struct Dot {
+        x: i64,
+    }
+
+    impl Simulation for Dot {
+        fn step(&mut self) -> bool {
+            if self.x == 99 {
+                return false;
+            }
+            self.x += 1;
+            true
+        }
+
+        fn frame(&self) -> Frame {
+            let mut cells = Grid::new(100, 1, '.');
+            cells[(self.x as usize, 0)] = '*';
+            Frame {
+                focus: (self.x, 0),
+                status: format!("x = {}", self.x),
+                ..Frame::new(cells)
+            }
+        }
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut viewport = Viewport::new(10, 1);
+        let mut dot = Dot { x: 0 };
+        viewport.follow(&dot.frame());
+        assert_eq!(viewport.draw(&dot.frame()), vec!["*........."]);
+
+        // Scrolls once the dot gets within a quarter of the right edge
+        dot.x = 7;
+        viewport.follow(&dot.frame());
+        assert_eq!(viewport.x, 0);
+        dot.x = 8;
+        viewport.follow(&dot.frame());
+        assert_eq!((viewport.x, viewport.y), (1, 0));
+
+        // No further than the end of the frame
+        dot.x = 99;
+        viewport.follow(&dot.frame());
+        assert_eq!(viewport.draw(&dot.frame()), vec![".........*"]);
+
+        // Frames smaller than the viewport are shown whole
+        let mut viewport = Viewport::new(4, 3);
+        let frame = Frame {
+            origin: (-1, 5),
+            focus: (0, 6),
+            ..Frame::text(&["ab".to_owned(), "c".to_owned()])
+        };
+        viewport.follow(&frame);
+        assert_eq!(viewport.draw(&frame), vec!["ab  ", "c   ", "    "]);
+    }
+
+    #[test]
+    fn test_play() {
+        let (sender, controls) = mpsc::channel();
+        let mut player = Player::new(1e6, 3, 10);
+        let mut out = vec![];
+
+        // Single steps while paused
+        for control in [Control::TogglePause, Control::Step, Control::Step] {
+            sender.send(control).unwrap();
+        }
+        sender.send(Control::Quit).unwrap();
+        let steps = player.play(&mut Dot { x: 0 }, &mut out, &controls).unwrap();
+        assert_eq!(steps, 2);
+
+        // Plays on until the end once the controls are gone
+        drop(sender);
+        let mut dot = Dot { x: 0 };
+        let steps = player.play(&mut dot, &mut out, &controls).unwrap();
+        assert_eq!((steps, dot.x), (99, 99));
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(".........*\x1b[K\nstep 99  1000000 steps/s  over  x = 99"));
+    }
+}
@@ -0,0 +1,309 @@
+//! Timing of the parse, part 1 and part 2 phases over repeated runs, and a
+//! local history of the results to spot regressions.
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{parse_number, Answer, AocError, ParseError, Part, Result, Solver};
+
+pub const HISTORY_PATH: &str = "bench_history.tsv";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse".to_owned(),
+            Phase::Solve(part) => format!("part{}", part),
+        };
+        f.pad(&name)
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Solve(Part::One)),
+            "part2" => Ok(Phase::Solve(Part::Two)),
+            _ => Err(format!("Invalid phase: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub median: Duration,
+    pub min: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Panics if `times` is empty.
+    pub fn new(times: &[Duration]) -> Stats {
+        let mut sorted = times.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|t| (t.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Stats {
+            runs: n,
+            median,
+            min: sorted[0],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub struct BenchResult {
+    pub phases: Vec<(Phase, Stats)>,
+    /// Answers of the last run
+    pub answers: Vec<(Part, Answer)>,
+}
+
+/// Parses `input` and solves `parts` of it `runs` times, timing each phase.
+pub fn bench(solver: &dyn Solver, input: &str, parts: &[Part], runs: usize) -> Result<BenchResult> {
+    let mut parse_times = vec![];
+    let mut part_times = vec![vec![]; parts.len()];
+    let mut answers = vec![];
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let parsed = solver.parse_input(input)?;
+        parse_times.push(start.elapsed());
+
+        answers.clear();
+        for (&part, times) in parts.iter().zip(part_times.iter_mut()) {
+            let start = Instant::now();
+            let answer = solver.solve_parsed(parsed.as_ref(), part)?;
+            times.push(start.elapsed());
+            answers.push((part, answer));
+        }
+    }
+
+    let mut phases = vec![(Phase::Parse, Stats::new(&parse_times))];
+    for (&part, times) in parts.iter().zip(&part_times) {
+        phases.push((Phase::Solve(part), Stats::new(times)));
+    }
+    Ok(BenchResult { phases, answers })
+}
+
+/// One line of the history file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub day: u8,
+    pub phase: Phase,
+    pub input: String,
+    pub stats: Stats,
+    /// The puzzle constants and strategy of the solver, as in
+    /// `rocks_part_one=2022,strategy=cycles`. Empty for the days without any
+    /// and in the records written before they were kept.
+    pub params: String,
+}
+
+/// The `params` of a [`Record`]
+pub fn params_field(params: &[(&str, String)]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    params.join(",")
+}
+
+impl Record {
+    pub fn new(day: u8, phase: Phase, input: &str, params: &str, stats: Stats) -> Record {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Record {
+            timestamp,
+            day,
+            phase,
+            input: input.to_owned(),
+            stats,
+            params: params.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.day,
+            self.phase,
+            self.input,
+            self.stats.runs,
+            self.stats.median.as_nanos(),
+            self.stats.min.as_nanos(),
+            self.stats.stddev.as_nanos(),
+            self.params
+        )
+    }
+}
+
+fn parse_record(input: &str, line: &str) -> std::result::Result<Record, ParseError> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 9 {
+        return Err(ParseError::at(
+            input,
+            line,
+            "timestamp, day, phase, input, runs, median, min, stddev and params separated by tabs",
+        ));
+    }
+    let nanos = |field: &str| parse_number(input, field).map(Duration::from_nanos);
+    Ok(Record {
+        timestamp: parse_number(input, fields[0])?,
+        day: parse_number(input, fields[1])?,
+        phase: fields[2]
+            .parse()
+            .map_err(|_| ParseError::at(input, fields[2], "parse, part1 or part2"))?,
+        input: fields[3].to_owned(),
+        stats: Stats {
+            runs: parse_number(input, fields[4])?,
+            median: nanos(fields[5])?,
+            min: nanos(fields[6])?,
+            stddev: nanos(fields[7])?,
+        },
+        params: fields[8].to_owned(),
+    })
+}
+
+/// Benchmark results of earlier runs, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub records: Vec<Record>,
+}
+
+impl History {
+    pub fn parse(input: &str) -> Result<History> {
+        let mut records = vec![];
+        for line in input.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            records.push(parse_record(input, line)?);
+        }
+        Ok(History { records })
+    }
+
+    /// A missing file is an empty history.
+    pub fn load(path: &str) -> Result<History> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(source) => {
+                return Err(AocError::Io {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        };
+        History::parse(&contents).map_err(|e| e.in_file(path))
+    }
+
+    /// The most recent record for the same day, phase, input and params.
+    pub fn last(&self, day: u8, phase: Phase, input: &str, params: &str) -> Option<&Record> {
+        self.records
+            .iter()
+            .rev()
+            .find(|r| r.day == day && r.phase == phase && r.input == input && r.params == params)
+    }
+
+    pub fn append(path: &str, records: &[Record]) -> Result<()> {
+        let io_error = |source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(io_error)?;
+        for record in records {
+            writeln!(file, "{}", record).map_err(io_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// How much slower `current` is than `previous`, in percent. Negative when
+/// it got faster.
+pub fn slowdown_percent(previous: Duration, current: Duration) -> f64 {
+    if previous.is_zero() {
+        return 0.0;
+    }
+    (current.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let times = [5, 1, 3, 7].map(Duration::from_millis);
+        let stats = Stats::new(&times);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        // sqrt(((4-1)^2 + (4-3)^2 + (4-5)^2 + (4-7)^2) / 4) = sqrt(5)
+        assert_eq!(stats.stddev.as_micros(), 2236);
+    }
+
+    #[test]
+    fn test_history() {
+        let input = "inputs/day_17_pyroclastic_flow.txt";
+        let params = params_field(&[
+            ("rocks_part_one", "2022".to_owned()),
+            ("strategy", "cycles".to_owned()),
+        ]);
+        assert_eq!(params, "rocks_part_one=2022,strategy=cycles");
+        let record = Record {
+            timestamp: 1671000000,
+            day: 17,
+            phase: Phase::Solve(Part::One),
+            input: input.to_owned(),
+            stats: Stats::new(&[Duration::from_micros(120)]),
+            params: params.clone(),
+        };
+        let history = History::parse(&format!("# comment\n{}\n", record)).unwrap();
+        assert_eq!(history.records, vec![record]);
+        assert!(history.last(17, Phase::Parse, input, &params).is_none());
+        assert!(history
+            .last(17, Phase::Solve(Part::One), input, &params)
+            .is_some());
+        let brute_force = "rocks_part_one=2022,strategy=brute_force";
+        assert!(history
+            .last(17, Phase::Solve(Part::One), input, brute_force)
+            .is_none());
+
+        // Without params
+        assert!(History::parse("1671000000\t1\tparse\tx\t3\t10\t9\t1\n").is_err());
+        assert!(History::parse("1671000000\t1\tparse\tx\t3\t10\t9\t1\t\n").is_ok());
+    }
+}
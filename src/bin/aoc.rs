@@ -0,0 +1,1445 @@
+/// Runner for the Advent of Code 2022 solutions
+///
+/// Usage:
+///   aoc run <day> [--part 1|2] [--input <path>] [--example]
+///           [--format text|json] [--animate [--fps <n>]] [--strict]
+///           [--stream] [--time-limit <seconds>] [--memory-limit <MiB>]
+///           [--param <name>=<value>]... [--params <path>]
+///   aoc run all [--part 1|2] [--example] [--format text|json]
+///           [--time-limit <seconds>] [--memory-limit <MiB>] [--workers <n>]
+///           [--params <path>]
+///   aoc bench <day|all> [--runs <n>] [--threshold <percent>]
+///           [--input <path>] [--example] [--param <name>=<value>]...
+///           [--params <path>]
+///   aoc compare <day> [--part 1|2] [--runs <n>] [--time-limit <seconds>]
+///           [--input <path>] [--example] [--param <name>=<value>]...
+///           [--params <path>]
+///   aoc render <day> --out <path> [--picture <name>] [--palette <palette>]
+///           [--scale <n>] [--input <path>] [--example]
+///           [--param <name>=<value>]... [--params <path>]
+///   aoc generate <day> [--seed <n>] [--size <n>] [--out <path>]
+///   aoc new <day> <title>
+///   aoc journal <day> [<1|2> <correct|too_high|too_low|wrong> <answer>]
+///   aoc serve [--addr <host:port>] [--time-limit <seconds>] [--workers <n>]
+///   aoc watch <day>
+///   aoc download <day> [--force]           (built with --features client)
+///   aoc submit <day> <1|2> [<answer>]      (built with --features client)
+///
+/// Every command takes -v (-vv, -vvv) and -q (-qq) to log more or less.
+use std::any::Any;
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::animate::{Player, Terminal};
+use advent_of_code_2022::bench::{self, History, Record, HISTORY_PATH};
+use advent_of_code_2022::budget::{self, Budget, CountingAllocator, Token};
+use advent_of_code_2022::calendar::{self, Job, Row, Status};
+#[cfg(feature = "client")]
+use advent_of_code_2022::client::{self, Client, ClientError, Throttle, Verdict};
+use advent_of_code_2022::compare;
+use advent_of_code_2022::generate;
+use advent_of_code_2022::input;
+use advent_of_code_2022::journal::{Entry, Journal, Outcome};
+use advent_of_code_2022::manifest::{Manifest, ANSWERS_PATH};
+use advent_of_code_2022::params::{self, Overrides, ParamsConfig, PARAMS_PATH};
+use advent_of_code_2022::render::{Image, ImageFormat, Palette};
+use advent_of_code_2022::scaffold;
+use advent_of_code_2022::serve::{ServeOptions, Server};
+use advent_of_code_2022::stream;
+use advent_of_code_2022::watch;
+use advent_of_code_2022::{days, info, json, log, warn, Day, DAYS};
+use advent_of_code_2022::{Answer, AocError, Part, Solver};
+
+/// Counts the memory of each thread, for the memory limits
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "Usage:
+  aoc run <day> [--part 1|2] [--input <path>] [--example]
+          [--format text|json] [--animate [--fps <n>]] [--strict]
+          [--stream] [--time-limit <seconds>] [--memory-limit <MiB>]
+          [--param <name>=<value>]... [--params <path>]
+  aoc run all [--part 1|2] [--example] [--format text|json]
+          [--time-limit <seconds>] [--memory-limit <MiB>] [--workers <n>]
+          [--params <path>]
+  aoc bench <day|all> [--runs <n>] [--threshold <percent>]
+          [--input <path>] [--example] [--param <name>=<value>]...
+          [--params <path>]
+  aoc compare <day> [--part 1|2] [--runs <n>] [--time-limit <seconds>]
+          [--input <path>] [--example] [--param <name>=<value>]...
+          [--params <path>]
+  aoc render <day> --out <path> [--picture <name>] [--palette <palette>]
+          [--scale <n>] [--input <path>] [--example]
+          [--param <name>=<value>]... [--params <path>]
+  aoc generate <day> [--seed <n>] [--size <n>] [--out <path>]
+  aoc new <day> <title>
+  aoc journal <day> [<1|2> <correct|too_high|too_low|wrong> <answer>]
+  aoc serve [--addr <host:port>] [--time-limit <seconds>] [--workers <n>]
+  aoc watch <day>
+  aoc download <day> [--force]           (built with --features client)
+  aoc submit <day> <1|2> [<answer>]      (built with --features client)
+
+Options:
+  -v, -vv, -vvv           Log info, debug or trace messages to stderr
+                          (default: warnings and errors)
+  -q, -qq                 Log only errors, or nothing
+  --part <1|2>            Run only one part of the puzzle
+  --input <path>          Read the puzzle input from <path>, or stdin for -
+                          (default: a piped stdin, or inputs/day_NN_title.txt)
+  --example               Read the example input from test_inputs/
+  --format <text|json>    Print the answers as text (default) or as one JSON
+                          object per part
+  --animate               Show the simulation of the part (default: 1) step
+                          by step. Keys: space pauses, n steps while paused,
+                          + and - change the speed, q quits
+  --fps <n>               Simulation steps per second to start with
+                          (default: 10)
+  --strict                Fail instead of warning when the journal rules out
+                          an answer for the real input
+  --stream                Solve the input while reading it (days 1, 2, 3, 4,
+                          6, 9 and 10; always done for stdin when the day can)
+  --param <name>=<value>  Override a puzzle constant or pick a strategy, e.g.
+                          --param interesting_row=10
+  --params <path>         Read the overrides for each input file from <path>
+                          (default: params.toml)
+  --runs <n>              Number of timed runs (default: 10, 3 for compare)
+  --threshold <percent>   Flag phases slower than the last benchmark by more
+                          than <percent> (default: 10)
+  --out <path>            Write the picture to <path>, a .png, .ppm or .pbm
+                          file, or the generated input instead of printing it
+  --picture <name>        Which picture of the day to render (default: the
+                          first)
+  --palette <palette>     grey (default), heat, terrain, paper or colours
+                          like #000000,#ff8000 to blend between
+  --scale <n>             Size of a cell in pixels (default: 4)
+  --seed <n>              Seed of the generated input (default: 1)
+  --size <n>              Size of the generated input, about its number of
+                          lines (default: 100)
+  --addr <host:port>      Address to serve the HTTP API on
+                          (default: 127.0.0.1:2022)
+  --time-limit <seconds>  Longest time a request, a day of run or a run of
+                          compare waits for its answers (default: none for
+                          one day, otherwise 10)
+  --memory-limit <MiB>    Most memory a day of run may allocate (default:
+                          none)
+  --workers <n>           Most puzzles solved at once (default: one per CPU)
+
+AOC_LOG sets the log levels, also per day, e.g. AOC_LOG=info,day_15=debug.
+Downloads and submissions use the session cookie from AOC_SESSION or
+.aoc_session. README.md describes each command in detail.";
+
+/// Errors reported by the runner. Usage errors exit with code 2, errors
+/// reading the input or solving the puzzle and rejected answers exit with
+/// code 1.
+enum CliError {
+    Usage(String),
+    Aoc(AocError),
+    /// An answer the journal rules out
+    Rejected(String),
+    /// The server could not listen on its address, or the watcher failed
+    System(String),
+    /// Parts of `aoc run all` that were wrong, panicked or could not be solved
+    Failed(String),
+    #[cfg(feature = "client")]
+    Client(ClientError),
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        CliError::Usage(message)
+    }
+}
+
+impl From<AocError> for CliError {
+    fn from(e: AocError) -> Self {
+        CliError::Aoc(e)
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<ClientError> for CliError {
+    fn from(e: ClientError) -> Self {
+        CliError::Client(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format: {} (expected text or json)", s)),
+        }
+    }
+}
+
+struct RunOptions {
+    day: u8,
+    part: Option<Part>,
+    input: Option<String>,
+    example: bool,
+    format: Format,
+    /// Steps per second of the animation, if animated
+    animate: Option<f64>,
+    /// Fail when the journal rules out an answer
+    strict: bool,
+    /// Solve the input as it is read
+    stream: bool,
+    budget: Budget,
+    params: Overrides,
+    params_path: Option<String>,
+}
+
+fn parse_time_limit(value: &str) -> Result<Duration, String> {
+    match value.parse() {
+        Ok(seconds) if seconds > 0.0 => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("Invalid time limit: {}", value)),
+    }
+}
+
+/// A limit in MiB, in bytes
+fn parse_memory_limit(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(mib) if mib > 0 => mib
+            .checked_mul(1 << 20)
+            .ok_or_else(|| format!("Invalid memory limit: {}", value)),
+        _ => Err(format!("Invalid memory limit: {}", value)),
+    }
+}
+
+fn parse_day(arg: Option<&String>) -> Result<u8, String> {
+    Ok(arg
+        .ok_or("Day number is required.")?
+        .parse::<u8>()
+        .map_err(|_| "Day must be a number (example: 11).")?)
+}
+
+fn find_day(number: u8) -> Result<&'static Day, String> {
+    days::find(number).ok_or(format!("Cannot find solution for day {}", number))
+}
+
+fn input_path(day: &Day, input: &Option<String>, example: bool) -> String {
+    match input {
+        Some(path) => path.to_owned(),
+        None if example => day.test_input_path(),
+        None => day.input_path(),
+    }
+}
+
+/// The solver of `day` with the constants for the input `filename` from the
+/// params file, followed by the command line overrides.
+fn configure(
+    day: &Day,
+    filename: &str,
+    params_path: &Option<String>,
+    overrides: &Overrides,
+) -> Result<Box<dyn Solver>, AocError> {
+    let config = match params_path {
+        Some(path) => ParamsConfig::load(path)?,
+        // The default params file is optional
+        None => match ParamsConfig::load(PARAMS_PATH) {
+            Err(AocError::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+                ParamsConfig::default()
+            }
+            config => config?,
+        },
+    };
+    let mut all_overrides = config.for_input(filename);
+    all_overrides.extend(overrides.iter().cloned());
+    day.solver.with_params(&all_overrides)
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next())?;
+
+    let mut options = RunOptions {
+        day,
+        part: None,
+        input: None,
+        example: false,
+        format: Format::Text,
+        animate: None,
+        strict: false,
+        stream: false,
+        budget: Budget::default(),
+        params: vec![],
+        params_path: None,
+    };
+    let mut fps = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().ok_or("--part requires a value")?;
+                options.part = Some(part.parse()?);
+            }
+            "--input" => {
+                let input = args.next().ok_or("--input requires a value")?;
+                options.input = Some(input.to_owned());
+            }
+            "--example" => options.example = true,
+            "--format" => {
+                let format = args.next().ok_or("--format requires a value")?;
+                options.format = format.parse()?;
+            }
+            "--animate" => options.animate = Some(10.0),
+            "--fps" => {
+                let value = args.next().ok_or("--fps requires a value")?;
+                fps = match value.parse() {
+                    Ok(fps) if fps > 0.0 => Some(fps),
+                    _ => return Err(format!("Invalid frame rate: {}", value)),
+                };
+            }
+            "--strict" => options.strict = true,
+            "--stream" => options.stream = true,
+            "--time-limit" => {
+                let value = args.next().ok_or("--time-limit requires a value")?;
+                options.budget.time = Some(parse_time_limit(value)?);
+            }
+            "--memory-limit" => {
+                let value = args.next().ok_or("--memory-limit requires a value")?;
+                options.budget.memory = Some(parse_memory_limit(value)?);
+            }
+            "--param" => {
+                let param = args.next().ok_or("--param requires a value")?;
+                options.params.push(params::parse_override(param)?);
+            }
+            "--params" => {
+                let path = args.next().ok_or("--params requires a value")?;
+                options.params_path = Some(path.to_owned());
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    if options.animate.is_some() {
+        // The keys that control the animation are read from stdin
+        if options.input.as_deref() == Some(input::STDIN) {
+            return Err("--animate cannot be used with input from stdin".to_owned());
+        }
+        if options.stream {
+            return Err("--animate cannot be used with --stream".to_owned());
+        }
+    }
+    match (options.animate, fps) {
+        (Some(_), _) if options.format == Format::Json => {
+            return Err("--animate cannot be used with --format json".to_owned())
+        }
+        (None, Some(_)) => return Err("--fps requires --animate".to_owned()),
+        (Some(_), Some(fps)) => options.animate = Some(fps),
+        _ => {}
+    }
+    Ok(options)
+}
+
+fn print_answer(day: u8, part: Part, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Day {:02}, part {}:\n{}", day, part, answer);
+    } else {
+        println!("Day {:02}, part {}: {}", day, part, answer);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), CliError> {
+    if args.first().is_some_and(|arg| arg == "all") {
+        return run_all(&args[1..]);
+    }
+    let options = parse_run_options(args)?;
+    let day = find_day(options.day)?;
+
+    // A piped stdin is the input, unless another one is given
+    let filename = match &options.input {
+        None if !options.example && options.animate.is_none() && input::stdin_is_piped() => {
+            info!("Reading the input from the pipe on stdin");
+            input::STDIN.to_owned()
+        }
+        input => input_path(day, input, options.example),
+    };
+    let solver = configure(day, &filename, &options.params_path, &options.params)?;
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    // Parsing and the parts share the budget
+    let _budget = budget::enter(&options.budget, &Token::default());
+
+    if options.stream || (filename == input::STDIN && options.animate.is_none()) {
+        let start = Instant::now();
+        let streamed = if filename == input::STDIN {
+            stream::solve(solver.as_ref(), &parts, io::stdin().lock(), &filename)
+        } else {
+            let file = File::open(&filename).map_err(|source| AocError::Io {
+                path: filename.clone(),
+                source,
+            })?;
+            stream::solve(solver.as_ref(), &parts, file, &filename)
+        };
+        match streamed {
+            Some(answers) => {
+                // The parts are solved together while reading the input
+                let elapsed = start.elapsed();
+                let answers = answers?;
+                budget::check()?;
+                for (part, answer) in answers {
+                    report(day, &filename, part, &answer, elapsed, &options)?;
+                }
+                return Ok(());
+            }
+            None if options.stream => {
+                return Err(CliError::Usage(format!(
+                    "Day {:02} cannot be streamed",
+                    day.number
+                )))
+            }
+            // Read all of stdin instead
+            None => {}
+        }
+    }
+
+    let contents = input::load(&filename)?;
+    let parsed = solver
+        .parse_input(&contents)
+        .map_err(|e| e.in_file(&filename))?;
+    budget::check()?;
+    if let Some(fps) = options.animate {
+        let part = options.part.unwrap_or(Part::One);
+        return animate(day, solver.as_ref(), parsed.as_ref(), part, fps);
+    }
+    for part in parts {
+        budget::reset_peak();
+        let start = Instant::now();
+        let answer = solver.solve_parsed(parsed.as_ref(), part)?;
+        budget::check()?;
+        report(day, &filename, part, &answer, start.elapsed(), &options)?;
+    }
+    Ok(())
+}
+
+/// Checks the answer to `part` against the journal and prints it.
+fn report(
+    day: &Day,
+    filename: &str,
+    part: Part,
+    answer: &Answer,
+    elapsed: Duration,
+    options: &RunOptions,
+) -> Result<(), CliError> {
+    check_journal(day, filename, part, answer, options.strict)?;
+    match options.format {
+        Format::Text => print_answer(day.number, part, answer),
+        Format::Json => println!(
+            "{}",
+            json::Object::new()
+                .number("day", day.number)
+                .number("part", part)
+                .raw("answer", json::answer(answer))
+                .number(
+                    "elapsed_ms",
+                    format!("{:.3}", elapsed.as_secs_f64() * 1000.0)
+                )
+                .string("input", filename)
+        ),
+    }
+    Ok(())
+}
+
+struct RunAllOptions {
+    part: Option<Part>,
+    example: bool,
+    format: Format,
+    budget: Budget,
+    workers: usize,
+    params_path: Option<String>,
+}
+
+fn parse_run_all_options(args: &[String]) -> Result<RunAllOptions, String> {
+    let mut args = args.iter();
+    let serve_options = ServeOptions::default();
+    let mut options = RunAllOptions {
+        part: None,
+        example: false,
+        format: Format::Text,
+        budget: Budget {
+            time: Some(serve_options.time_limit),
+            memory: None,
+        },
+        workers: serve_options.workers,
+        params_path: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().ok_or("--part requires a value")?;
+                options.part = Some(part.parse()?);
+            }
+            "--example" => options.example = true,
+            "--format" => {
+                let format = args.next().ok_or("--format requires a value")?;
+                options.format = format.parse()?;
+            }
+            "--time-limit" => {
+                let value = args.next().ok_or("--time-limit requires a value")?;
+                options.budget.time = Some(parse_time_limit(value)?);
+            }
+            "--memory-limit" => {
+                let value = args.next().ok_or("--memory-limit requires a value")?;
+                options.budget.memory = Some(parse_memory_limit(value)?);
+            }
+            "--workers" => {
+                let value = args.next().ok_or("--workers requires a value")?;
+                options.workers = match value.parse() {
+                    Ok(workers) if workers > 0 => workers,
+                    _ => return Err(format!("Invalid number of workers: {}", value)),
+                };
+            }
+            "--params" => {
+                let path = args.next().ok_or("--params requires a value")?;
+                options.params_path = Some(path.to_owned());
+            }
+            "--input" | "--param" | "--animate" | "--fps" | "--strict" => {
+                return Err(format!("{} cannot be used with all days", arg))
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Solves every day on a pool of workers and prints a table of the answers.
+/// Fails if an answer is wrong, a solver panicked or an input is broken.
+fn run_all(args: &[String]) -> Result<(), CliError> {
+    let options = parse_run_all_options(args)?;
+    let manifest = load_manifest()?;
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let mut jobs = vec![];
+    // Days whose input cannot be read or constants cannot be applied
+    let mut rows = vec![];
+    for day in DAYS {
+        let filename = input_path(day, &None, options.example);
+        let job = input::load(&filename).and_then(|contents| {
+            Ok(Job {
+                day,
+                solver: configure(day, &filename, &options.params_path, &vec![])?,
+                input: filename.clone(),
+                contents,
+                parts: parts.clone(),
+                expected: manifest.find(&filename).cloned(),
+            })
+        });
+        match job {
+            Ok(job) => jobs.push(job),
+            Err(e) => rows.extend(
+                parts
+                    .iter()
+                    .map(|&part| Row::failed(day.number, part, &filename, Status::error(&e))),
+            ),
+        }
+    }
+    rows.extend(calendar::run_all(jobs, options.workers, options.budget));
+    rows.sort_by_key(|row| (row.day, row.part == Part::Two));
+
+    match options.format {
+        Format::Text => print_table(&rows),
+        Format::Json => {
+            for row in &rows {
+                let elapsed = row.elapsed.map_or("null".to_owned(), |elapsed| {
+                    format!("{:.3}", elapsed.as_secs_f64() * 1000.0)
+                });
+                println!(
+                    "{}",
+                    json::Object::new()
+                        .number("day", row.day)
+                        .number("part", row.part)
+                        .raw(
+                            "answer",
+                            row.answer.as_ref().map_or("null".to_owned(), json::answer)
+                        )
+                        .raw("elapsed_ms", elapsed)
+                        .string("input", &row.input)
+                        .string("status", row.status.name())
+                        .string("message", &row.status.to_string())
+                );
+            }
+        }
+    }
+
+    let failures = rows.iter().filter(|row| row.status.is_failure()).count();
+    if failures > 0 {
+        return Err(CliError::Failed(format!(
+            "{} of {} parts failed",
+            failures,
+            rows.len()
+        )));
+    }
+    Ok(())
+}
+
+/// The results of `aoc run all` and how many parts ended with each status
+fn print_table(rows: &[Row]) {
+    let answers: Vec<String> = rows
+        .iter()
+        .map(|row| row.answer.as_ref().map_or("-".to_owned(), Answer::short))
+        .collect();
+    let width = answers.iter().map(String::len).max().unwrap_or(0).max(6);
+    println!("Day  Part  {:<width$}  {:>9}  Status", "Answer", "Time");
+    for (row, answer) in rows.iter().zip(&answers) {
+        let elapsed = row
+            .elapsed
+            .map_or("-".to_owned(), |elapsed| format!("{:.1?}", elapsed));
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>9}  {}",
+            row.day, row.part, answer, elapsed, row.status
+        );
+    }
+
+    let mut counts: Vec<(&str, usize)> = vec![];
+    for row in rows {
+        match counts
+            .iter_mut()
+            .find(|(name, _)| *name == row.status.name())
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((row.status.name(), 1)),
+        }
+    }
+    let counts: Vec<String> = counts
+        .iter()
+        .map(|(name, count)| format!("{} {}", count, name))
+        .collect();
+    println!("{} parts: {}", rows.len(), counts.join(", "));
+}
+
+/// Warns, or fails if `strict`, when the journal rules out `answer`. Only
+/// answers for the real input are checked.
+fn check_journal(
+    day: &Day,
+    filename: &str,
+    part: Part,
+    answer: &Answer,
+    strict: bool,
+) -> Result<(), CliError> {
+    if filename.trim_start_matches("./") != day.input_path() {
+        return Ok(());
+    }
+    let path = day.journal_path(part);
+    let Some(conflict) = Journal::load(&path)?.check(answer) else {
+        return Ok(());
+    };
+    let message = format!(
+        "Day {:02}, part {}: {} cannot be right, {} ({})",
+        day.number, part, answer, conflict, path
+    );
+    if strict {
+        return Err(CliError::Rejected(message));
+    }
+    warn!("{}", message);
+    Ok(())
+}
+
+fn animate(
+    day: &Day,
+    solver: &dyn Solver,
+    parsed: &dyn Any,
+    part: Part,
+    fps: f64,
+) -> Result<(), CliError> {
+    let mut simulation = solver.simulate_parsed(parsed, part).ok_or(format!(
+        "Day {:02} has no simulation to animate",
+        day.number
+    ))?;
+    let (rows, columns) = Terminal::size();
+    let (terminal, controls) = Terminal::open();
+    let mut player = Player::new(fps, rows, columns);
+    let steps = player
+        .play(simulation.as_mut(), &mut io::stdout(), &controls)
+        .map_err(|source| AocError::Io {
+            path: "stdout".to_owned(),
+            source,
+        })?;
+    drop(terminal);
+    println!("\nDay {:02}, part {}: {} steps", day.number, part, steps);
+    Ok(())
+}
+
+struct BenchOptions {
+    /// None for all days
+    day: Option<u8>,
+    runs: usize,
+    threshold: f64,
+    input: Option<String>,
+    example: bool,
+    params: Overrides,
+    params_path: Option<String>,
+}
+
+fn parse_bench_options(args: &[String]) -> Result<BenchOptions, String> {
+    let mut args = args.iter();
+    let day = match args.next() {
+        Some(all) if all == "all" => None,
+        day => Some(parse_day(day)?),
+    };
+
+    let mut options = BenchOptions {
+        day,
+        runs: 10,
+        threshold: 10.0,
+        input: None,
+        example: false,
+        params: vec![],
+        params_path: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let runs = args.next().ok_or("--runs requires a value")?;
+                options.runs = match runs.parse() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("Invalid number of runs: {}", runs)),
+                };
+            }
+            "--threshold" => {
+                let threshold = args.next().ok_or("--threshold requires a value")?;
+                options.threshold = threshold
+                    .parse()
+                    .map_err(|_| format!("Invalid threshold: {}", threshold))?;
+            }
+            "--input" if options.day.is_none() => {
+                return Err("--input cannot be used with all days".to_owned())
+            }
+            "--input" => {
+                let input = args.next().ok_or("--input requires a value")?;
+                options.input = Some(input.to_owned());
+            }
+            "--example" => options.example = true,
+            "--param" if options.day.is_none() => {
+                return Err("--param cannot be used with all days".to_owned())
+            }
+            "--param" => {
+                let param = args.next().ok_or("--param requires a value")?;
+                options.params.push(params::parse_override(param)?);
+            }
+            "--params" => {
+                let path = args.next().ok_or("--params requires a value")?;
+                options.params_path = Some(path.to_owned());
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// The answers file is optional for benchmarks.
+fn load_manifest() -> Result<Manifest, AocError> {
+    match Manifest::load(ANSWERS_PATH) {
+        Err(AocError::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+            Ok(Manifest::default())
+        }
+        manifest => manifest,
+    }
+}
+
+fn bench(args: &[String]) -> Result<(), CliError> {
+    let options = parse_bench_options(args)?;
+    let days: Vec<&Day> = match options.day {
+        Some(number) => vec![find_day(number)?],
+        None => DAYS.iter().collect(),
+    };
+    let manifest = load_manifest()?;
+    let history = History::load(HISTORY_PATH)?;
+
+    let mut records = vec![];
+    let mut regressions = 0;
+    for day in days {
+        let filename = input_path(day, &options.input, options.example);
+        let contents = input::load(&filename)?;
+        let expected = manifest.find(&filename);
+
+        let solver = configure(day, &filename, &options.params_path, &options.params)?;
+        // The parts with an expected answer, if the input has any, that
+        // finish at all (day 17 part 2 doesn't with brute_force)
+        let parts: Vec<Part> = Part::BOTH
+            .into_iter()
+            .filter(|&part| expected.is_none_or(|expected| expected.answer(part).is_some()))
+            .filter(|&part| solver.finishes(part))
+            .collect();
+
+        let result = bench::bench(solver.as_ref(), &contents, &parts, options.runs)
+            .map_err(|e| e.in_file(&filename))?;
+        // Only runs with the same constants and strategy are comparable
+        let params = bench::params_field(&solver.params());
+
+        println!("Day {:02} {} ({} runs)", day.number, filename, options.runs);
+        for (phase, stats) in result.phases {
+            let mut line = format!(
+                "  {:<6} median {:>9}  min {:>9}  stddev {:>9}",
+                phase,
+                format!("{:.1?}", stats.median),
+                format!("{:.1?}", stats.min),
+                format!("{:.1?}", stats.stddev)
+            );
+            if let Some(last) = history.last(day.number, phase, &filename, &params) {
+                let slowdown = bench::slowdown_percent(last.stats.median, stats.median);
+                if slowdown > options.threshold {
+                    line += &format!(
+                        "  SLOWER by {:.0}% than the last run ({:.1?})",
+                        slowdown, last.stats.median
+                    );
+                    regressions += 1;
+                }
+            }
+            println!("{}", line);
+            records.push(Record::new(day.number, phase, &filename, &params, stats));
+        }
+
+        for (part, answer) in &result.answers {
+            match expected.and_then(|e| e.answer(*part)) {
+                Some(expected) if expected != answer => println!(
+                    "  part {} answer {} does not match the expected {}",
+                    part, answer, expected
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    History::append(HISTORY_PATH, &records)?;
+    if regressions > 0 {
+        println!(
+            "{} phase(s) more than {}% slower than the last run",
+            regressions, options.threshold
+        );
+    }
+    Ok(())
+}
+
+struct CompareOptions {
+    day: u8,
+    part: Option<Part>,
+    runs: usize,
+    budget: Budget,
+    input: Option<String>,
+    example: bool,
+    params: Overrides,
+    params_path: Option<String>,
+}
+
+fn parse_compare_options(args: &[String]) -> Result<CompareOptions, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next())?;
+
+    let mut options = CompareOptions {
+        day,
+        part: None,
+        runs: 3,
+        budget: Budget {
+            time: Some(ServeOptions::default().time_limit),
+            memory: None,
+        },
+        input: None,
+        example: false,
+        params: vec![],
+        params_path: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().ok_or("--part requires a value")?;
+                options.part = Some(part.parse()?);
+            }
+            "--runs" => {
+                let runs = args.next().ok_or("--runs requires a value")?;
+                options.runs = match runs.parse() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("Invalid number of runs: {}", runs)),
+                };
+            }
+            "--time-limit" => {
+                let value = args.next().ok_or("--time-limit requires a value")?;
+                options.budget.time = Some(parse_time_limit(value)?);
+            }
+            "--input" => {
+                let input = args.next().ok_or("--input requires a value")?;
+                options.input = Some(input.to_owned());
+            }
+            "--example" => options.example = true,
+            "--param" => {
+                let param = args.next().ok_or("--param requires a value")?;
+                options.params.push(params::parse_override(param)?);
+            }
+            "--params" => {
+                let path = args.next().ok_or("--params requires a value")?;
+                options.params_path = Some(path.to_owned());
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Solves the input with every strategy of the day and prints their answers
+/// and times. Fails if they disagree.
+fn compare(args: &[String]) -> Result<(), CliError> {
+    let options = parse_compare_options(args)?;
+    let day = find_day(options.day)?;
+    let filename = input_path(day, &options.input, options.example);
+    let solver = configure(day, &filename, &options.params_path, &options.params)?;
+    if solver.strategies().is_empty() {
+        return Err(CliError::Usage(format!(
+            "Day {:02} has a single strategy",
+            day.number
+        )));
+    }
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let contents = input::load(&filename)?;
+    let comparison = compare::compare(
+        solver.as_ref(),
+        &contents,
+        &parts,
+        options.runs,
+        &options.budget,
+    )
+    .map_err(|e| e.in_file(&filename))?;
+
+    println!(
+        "Day {:02} {} ({} runs, parse median {:.1?})",
+        day.number, filename, options.runs, comparison.parse.median
+    );
+    let answers: Vec<String> = comparison
+        .runs
+        .iter()
+        .map(|run| run.answer.as_ref().map_or("-".to_owned(), Answer::short))
+        .collect();
+    let width = answers.iter().map(String::len).max().unwrap_or(0).max(6);
+    let strategy_width = comparison
+        .runs
+        .iter()
+        .map(|run| run.strategy.len())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    println!(
+        "Part  {:<strategy_width$}  {:<width$}  {:>9}  {:>9}",
+        "Strategy", "Answer", "Median", "Min"
+    );
+    for (run, answer) in comparison.runs.iter().zip(&answers) {
+        let (median, min) = run.stats.map_or(("-".to_owned(), "-".to_owned()), |stats| {
+            (
+                format!("{:.1?}", stats.median),
+                format!("{:.1?}", stats.min),
+            )
+        });
+        let mut line = format!(
+            "{:>4}  {:<strategy_width$}  {:<width$}  {:>9}  {:>9}",
+            run.part, run.strategy, answer, median, min
+        );
+        if let Err(e) = &run.answer {
+            // The first line of a parse error says what was expected
+            line += &format!("  {}", e.to_string().lines().next().unwrap_or(""));
+        }
+        println!("{}", line);
+    }
+
+    let disagreements = comparison.disagreements();
+    if !disagreements.is_empty() {
+        let parts: Vec<String> = disagreements.iter().map(Part::to_string).collect();
+        return Err(CliError::Failed(format!(
+            "The strategies of day {:02} disagree on part {}",
+            day.number,
+            parts.join(" and ")
+        )));
+    }
+    Ok(())
+}
+
+struct RenderOptions {
+    day: u8,
+    out: String,
+    format: ImageFormat,
+    picture: Option<String>,
+    palette: Palette,
+    scale: usize,
+    input: Option<String>,
+    example: bool,
+    params: Overrides,
+    params_path: Option<String>,
+}
+
+fn parse_render_options(args: &[String]) -> Result<RenderOptions, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next())?;
+
+    let mut out = None;
+    let mut options = RenderOptions {
+        day,
+        out: String::new(),
+        format: ImageFormat::Png,
+        picture: None,
+        palette: Palette::default(),
+        scale: 4,
+        input: None,
+        example: false,
+        params: vec![],
+        params_path: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(args.next().ok_or("--out requires a value")?),
+            "--picture" => {
+                let picture = args.next().ok_or("--picture requires a value")?;
+                options.picture = Some(picture.to_owned());
+            }
+            "--palette" => {
+                let palette = args.next().ok_or("--palette requires a value")?;
+                options.palette = palette.parse()?;
+            }
+            "--scale" => {
+                let scale = args.next().ok_or("--scale requires a value")?;
+                options.scale = match scale.parse() {
+                    Ok(scale) if scale > 0 => scale,
+                    _ => return Err(format!("Invalid scale: {}", scale)),
+                };
+            }
+            "--input" => {
+                let input = args.next().ok_or("--input requires a value")?;
+                options.input = Some(input.to_owned());
+            }
+            "--example" => options.example = true,
+            "--param" => {
+                let param = args.next().ok_or("--param requires a value")?;
+                options.params.push(params::parse_override(param)?);
+            }
+            "--params" => {
+                let path = args.next().ok_or("--params requires a value")?;
+                options.params_path = Some(path.to_owned());
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    let out = out.ok_or("--out is required")?;
+    options.format = ImageFormat::from_path(out).ok_or(format!(
+        "Unknown image format: {} (expected a .png, .ppm or .pbm file)",
+        out
+    ))?;
+    options.out = out.to_owned();
+    Ok(options)
+}
+
+fn render(args: &[String]) -> Result<(), CliError> {
+    let options = parse_render_options(args)?;
+    let day = find_day(options.day)?;
+
+    let filename = input_path(day, &options.input, options.example);
+    let contents = input::load(&filename)?;
+    let solver = configure(day, &filename, &options.params_path, &options.params)?;
+    let parsed = solver
+        .parse_input(&contents)
+        .map_err(|e| e.in_file(&filename))?;
+
+    let mut pictures = solver.render_parsed(parsed.as_ref())?;
+    if pictures.is_empty() {
+        return Err(CliError::Usage(format!(
+            "Day {:02} has no pictures to render",
+            day.number
+        )));
+    }
+    let index = match &options.picture {
+        None => 0,
+        Some(name) => pictures
+            .iter()
+            .position(|(picture, _)| picture == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = pictures.iter().map(|(picture, _)| *picture).collect();
+                format!(
+                    "Unknown picture: {} (expected one of {})",
+                    name,
+                    names.join(", ")
+                )
+            })?,
+    };
+    let (name, picture) = pictures.swap_remove(index);
+
+    let image = Image::new(&picture, &options.palette, options.scale);
+    image.save(&options.out, options.format)?;
+    println!(
+        "Day {:02} {} written to {} ({}x{} {})",
+        day.number, name, options.out, image.width, image.height, options.format
+    );
+    Ok(())
+}
+
+struct GenerateOptions {
+    day: u8,
+    seed: u64,
+    size: usize,
+    out: Option<String>,
+}
+
+fn parse_generate_options(args: &[String]) -> Result<GenerateOptions, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next())?;
+
+    let mut options = GenerateOptions {
+        day,
+        seed: 1,
+        size: 100,
+        out: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let seed = args.next().ok_or("--seed requires a value")?;
+                options.seed = seed
+                    .parse()
+                    .map_err(|_| format!("Invalid seed: {}", seed))?;
+            }
+            "--size" => {
+                let size = args.next().ok_or("--size requires a value")?;
+                options.size = size
+                    .parse()
+                    .map_err(|_| format!("Invalid size: {}", size))?;
+            }
+            "--out" => {
+                let out = args.next().ok_or("--out requires a value")?;
+                options.out = Some(out.to_owned());
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn generate(args: &[String]) -> Result<(), CliError> {
+    let options = parse_generate_options(args)?;
+    let day = find_day(options.day)?;
+    let generated = generate::generate(day.number, options.seed, options.size)
+        .ok_or_else(|| format!("Day {:02} has no input generator", day.number))?;
+
+    match &options.out {
+        Some(path) => fs::write(path, &generated.input).map_err(|source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        })?,
+        None => print!("{}", generated.input),
+    }
+    // The generated input only makes sense with its puzzle constants
+    if !generated.params.is_empty() {
+        let flags: Vec<String> = generated
+            .params
+            .iter()
+            .map(|(name, value)| format!("--param {}={}", name, value))
+            .collect();
+        eprintln!("Run with {}", flags.join(" "));
+    }
+    Ok(())
+}
+
+fn new_day(args: &[String]) -> Result<(), CliError> {
+    let number = parse_day(args.first())?;
+    let title = args
+        .get(1)
+        .ok_or_else(|| "Title is required (example: monkey_math).".to_owned())?;
+    if let Some(arg) = args.get(2) {
+        return Err(CliError::Usage(format!("Unexpected argument: {}", arg)));
+    }
+
+    let changes = scaffold::plan(Path::new("."), number, title)?;
+    scaffold::apply(&changes)?;
+    for change in &changes {
+        let verb = if change.created { "Created" } else { "Updated" };
+        println!("{} {}", verb, change.path.display());
+    }
+    println!("Add the inputs, the expected answers in answers.toml and an input generator");
+    Ok(())
+}
+
+/// A number, or else text
+fn parse_answer(answer: &str) -> Answer {
+    match answer.parse::<i64>() {
+        Ok(number) => Answer::Number(number),
+        Err(_) => Answer::Text(answer.to_owned()),
+    }
+}
+
+fn print_journal(day: &Day, part: Part) -> Result<(), CliError> {
+    let path = day.journal_path(part);
+    let journal = Journal::load(&path)?;
+    println!(
+        "Day {:02}, part {} ({}):\n{}",
+        day.number, part, path, journal
+    );
+    Ok(())
+}
+
+fn journal(args: &[String]) -> Result<(), CliError> {
+    let day = find_day(parse_day(args.first())?)?;
+    let Some(part) = args.get(1) else {
+        for part in Part::BOTH {
+            print_journal(day, part)?;
+        }
+        return Ok(());
+    };
+    let part: Part = part.parse()?;
+    let (Some(outcome), Some(answer), None) = (args.get(2), args.get(3), args.get(4)) else {
+        return Err(CliError::Usage(
+            "Expected the part, the verdict and the answer (example: 1 too_high 7305)".to_owned(),
+        ));
+    };
+    let outcome: Outcome = outcome.parse()?;
+    Journal::append(
+        &day.journal_path(part),
+        &Entry::new(parse_answer(answer), outcome),
+    )?;
+    print_journal(day, part)
+}
+
+fn parse_serve_options(args: &[String]) -> Result<(String, ServeOptions), String> {
+    let mut args = args.iter();
+    let mut addr = "127.0.0.1:2022".to_owned();
+    let mut options = ServeOptions::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--addr" => {
+                let value = args.next().ok_or("--addr requires a value")?;
+                addr = value.to_owned();
+            }
+            "--time-limit" => {
+                let value = args.next().ok_or("--time-limit requires a value")?;
+                options.time_limit = parse_time_limit(value)?;
+            }
+            "--workers" => {
+                let value = args.next().ok_or("--workers requires a value")?;
+                options.workers = match value.parse() {
+                    Ok(workers) if workers > 0 => workers,
+                    _ => return Err(format!("Invalid number of workers: {}", value)),
+                };
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok((addr, options))
+}
+
+fn serve(args: &[String]) -> Result<(), CliError> {
+    let (addr, options) = parse_serve_options(args)?;
+    let server = Server::bind(&addr, options)
+        .map_err(|e| CliError::System(format!("Cannot listen on {}: {}", addr, e)))?;
+    let local_addr = server
+        .local_addr()
+        .map_err(|e| CliError::System(format!("Cannot listen on {}: {}", addr, e)))?;
+    println!(
+        "Serving {} days on http://{} ({} workers, time limit {:?})",
+        DAYS.len(),
+        local_addr,
+        options.workers,
+        options.time_limit
+    );
+    server.run();
+    Ok(())
+}
+
+fn watch(args: &[String]) -> Result<(), CliError> {
+    let day = find_day(parse_day(args.first())?)?;
+    if let Some(arg) = args.get(1) {
+        return Err(CliError::Usage(format!("Unexpected argument: {}", arg)));
+    }
+    watch::watch(day, &mut io::stdout())
+        .map_err(|e| CliError::System(format!("Watching day {} failed: {}", day.number, e)))
+}
+
+/// A client for the site from AOC_BASE_URL, or the puzzle site
+#[cfg(feature = "client")]
+fn site_client() -> Result<Client, ClientError> {
+    let session = client::session_token(client::SESSION_PATH)?;
+    let site = env::var(client::SITE_ENV).unwrap_or_else(|_| client::SITE.to_owned());
+    let throttle = Throttle::persistent(client::THROTTLE_INTERVAL, client::THROTTLE_PATH);
+    Ok(Client::new(&site, &session, throttle))
+}
+
+#[cfg(feature = "client")]
+fn download(args: &[String]) -> Result<(), CliError> {
+    let day = find_day(parse_day(args.first())?)?;
+    let force = match args.get(1).map(|arg| arg.as_str()) {
+        None => false,
+        Some("--force") => true,
+        Some(arg) => return Err(CliError::Usage(format!("Unexpected argument: {}", arg))),
+    };
+    if let Some(arg) = args.get(2) {
+        return Err(CliError::Usage(format!("Unexpected argument: {}", arg)));
+    }
+
+    // `aoc new` leaves an empty file to fill in
+    let path = day.input_path();
+    let downloaded = fs::metadata(&path).is_ok_and(|file| file.len() > 0);
+    if downloaded && !force {
+        println!("{} is there already (--force downloads it again)", path);
+        return Ok(());
+    }
+    let input = site_client()?.fetch_input(day.number)?;
+    fs::write(&path, &input).map_err(|source| AocError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    println!(
+        "Day {:02} input written to {} ({} lines)",
+        day.number,
+        path,
+        input.lines().count()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+fn submit(args: &[String]) -> Result<(), CliError> {
+    let day = find_day(parse_day(args.first())?)?;
+    let part: Part = args
+        .get(1)
+        .ok_or_else(|| "Part is required (1 or 2).".to_owned())?
+        .parse()?;
+    if let Some(arg) = args.get(3) {
+        return Err(CliError::Usage(format!("Unexpected argument: {}", arg)));
+    }
+
+    let answer = match args.get(2) {
+        Some(answer) => parse_answer(answer),
+        None => {
+            let filename = day.input_path();
+            let contents = input::load(&filename)?;
+            let solver = configure(day, &filename, &None, &vec![])?;
+            let parsed = solver
+                .parse_input(&contents)
+                .map_err(|e| e.in_file(&filename))?;
+            solver.solve_parsed(parsed.as_ref(), part)?
+        }
+    };
+    // Answers that are known to be wrong would only cost a wait
+    let path = day.journal_path(part);
+    if let Some(conflict) = Journal::load(&path)?.check(&answer) {
+        return Err(CliError::Rejected(format!(
+            "Day {:02}, part {}: {} not submitted, {} ({})",
+            day.number, part, answer, conflict, path
+        )));
+    }
+    let verdict = site_client()?.submit(day.number, part, &answer)?;
+    if let Some(outcome) = verdict.outcome() {
+        Journal::append(&path, &Entry::new(answer.clone(), outcome))?;
+    }
+    let message = format!(
+        "Day {:02}, part {}: {} is {}",
+        day.number, part, answer, verdict
+    );
+    match verdict {
+        Verdict::Correct => {
+            println!("{}", message);
+            Ok(())
+        }
+        _ => Err(CliError::Client(ClientError::Refused(message))),
+    }
+}
+
+fn dispatch(args: &[String]) -> Result<(), CliError> {
+    match args.first().map(|s| s.as_str()) {
+        Some("--help" | "-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some("run") => run(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("compare") => compare(&args[1..]),
+        Some("render") => render(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("new") => new_day(&args[1..]),
+        Some("journal") => journal(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        #[cfg(feature = "client")]
+        Some("download") => download(&args[1..]),
+        #[cfg(feature = "client")]
+        Some("submit") => submit(&args[1..]),
+        #[cfg(not(feature = "client"))]
+        Some(command @ ("download" | "submit")) => Err(CliError::Usage(format!(
+            "aoc {} needs the client feature: cargo run --features client --bin aoc -- {} ...",
+            command, command
+        ))),
+        Some(command) => Err(CliError::Usage(format!(
+            "Unknown command: {}\n\n{}",
+            command, USAGE
+        ))),
+        None => Err(CliError::Usage(USAGE.to_owned())),
+    }
+}
+
+/// Removes the -v and -q flags from `args`. Returns how much more verbose
+/// than the default the log should be.
+fn take_verbosity(args: &mut Vec<String>) -> i32 {
+    let mut verbosity = 0;
+    args.retain(|arg| {
+        let steps = match arg.strip_prefix('-') {
+            Some("verbose") | Some("-verbose") => 1,
+            Some("quiet") | Some("-quiet") => -1,
+            Some(flags) if !flags.is_empty() && flags.chars().all(|c| c == 'v') => {
+                flags.len() as i32
+            }
+            Some(flags) if !flags.is_empty() && flags.chars().all(|c| c == 'q') => {
+                -(flags.len() as i32)
+            }
+            _ => return true,
+        };
+        verbosity += steps;
+        false
+    });
+    verbosity
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let verbosity = take_verbosity(&mut args);
+    let result = match log::init(verbosity) {
+        Err(message) => Err(CliError::Usage(message)),
+        Ok(()) => dispatch(&args),
+    };
+
+    match result {
+        Ok(()) => {}
+        Err(CliError::Usage(message)) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+        Err(CliError::Aoc(e)) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        Err(
+            CliError::Rejected(message) | CliError::System(message) | CliError::Failed(message),
+        ) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        #[cfg(feature = "client")]
+        Err(CliError::Client(e)) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
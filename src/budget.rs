@@ -0,0 +1,293 @@
+//! Time and memory budgets for solvers, with cooperative cancellation.
+//!
+//! A solver runs under a [`Budget`] with [`run`], or [`enter`] for several
+//! steps, which also gives its thread a [`Token`] that another thread can
+//! cancel. Long loops, like the rows of day 15 or the rocks of day 17, call
+//! [`check`] every now and then. It fails with [`AocError::Exceeded`] once the
+//! token was cancelled, the time ran out or the thread went over its memory
+//! budget, and never fails on a thread without a budget. Threads cannot be
+//! stopped, so a loop that doesn't check runs on until it ends.
+//!
+//! Memory is counted by [`CountingAllocator`], which the `aoc` runner installs
+//! as the global allocator. It counts the bytes allocated by each thread, so
+//! that the days solved at once by `aoc run all` are measured apart. Without
+//! it no memory is counted and memory budgets are never exceeded. Memory over
+//! budget is only noticed by the next check, the allocations themselves are
+//! not refused.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{AocError, Result};
+
+const MIB: usize = 1 << 20;
+
+/// Limits of a solver, none by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// Time from [`enter`] on
+    pub time: Option<Duration>,
+    /// Peak bytes allocated by the thread on top of those it had at
+    /// [`enter`], since the last [`reset_peak`]
+    pub memory: Option<usize>,
+}
+
+/// Which budget a solver exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exceeded {
+    Time(Duration),
+    /// The budget in bytes
+    Memory(usize),
+    /// The token was cancelled
+    Cancelled,
+}
+
+impl fmt::Display for Exceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Exceeded::Time(time) => write!(f, "exceeded the time budget of {:?}", time),
+            Exceeded::Memory(bytes) if bytes % MIB == 0 => {
+                write!(f, "exceeded the memory budget of {} MiB", bytes / MIB)
+            }
+            Exceeded::Memory(bytes) => {
+                write!(f, "exceeded the memory budget of {} bytes", bytes)
+            }
+            Exceeded::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Tells a solver on another thread to stop at its next [`check`].
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The budget of a thread, as it was entered
+struct Limits {
+    token: Token,
+    budget: Budget,
+    deadline: Option<Instant>,
+    /// Bytes the thread had allocated at [`enter`]
+    base: usize,
+}
+
+thread_local! {
+    static LIMITS: RefCell<Option<Limits>> = const { RefCell::new(None) };
+    /// Bytes allocated by the thread and not freed yet, or freed by other
+    /// threads
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+    /// The most bytes allocated at once since the last [`enter`] or
+    /// [`reset_peak`]
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The system allocator, counting the bytes allocated by each thread.
+pub struct CountingAllocator;
+
+fn count(allocated: impl FnOnce(usize) -> usize) {
+    // Allocations of a thread that is shutting down are not counted
+    let _ = ALLOCATED.try_with(|current| {
+        let allocated = allocated(current.get());
+        current.set(allocated);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(allocated)));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(|allocated| allocated + layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(|allocated| allocated + layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count(|allocated| allocated.saturating_sub(layout.size()));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count(|allocated| allocated.saturating_sub(layout.size()) + new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Leaves the budget of the thread when dropped.
+pub struct Guard(());
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        LIMITS.with(|limits| limits.borrow_mut().take());
+    }
+}
+
+/// Puts the thread under `budget` until the guard is dropped. `token` cancels
+/// it from other threads.
+pub fn enter(budget: &Budget, token: &Token) -> Guard {
+    let base = ALLOCATED.with(Cell::get);
+    PEAK.with(|peak| peak.set(base));
+    let limits = Limits {
+        token: token.clone(),
+        budget: *budget,
+        deadline: budget.time.map(|time| Instant::now() + time),
+        base,
+    };
+    LIMITS.with(|current| *current.borrow_mut() = Some(limits));
+    Guard(())
+}
+
+/// Measures the peak memory of the thread again from what it has now, so
+/// that a step over the budget doesn't fail the steps after it. What the
+/// thread still holds keeps counting.
+pub fn reset_peak() {
+    let allocated = ALLOCATED.with(Cell::get);
+    PEAK.with(|peak| peak.set(allocated));
+}
+
+/// Fails if the thread is over its budget or was cancelled.
+pub fn check() -> Result<()> {
+    LIMITS.with(|limits| {
+        let limits = limits.borrow();
+        let Some(limits) = limits.as_ref() else {
+            return Ok(());
+        };
+        let exceeded = if limits.token.is_cancelled() {
+            Exceeded::Cancelled
+        } else if limits
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Exceeded::Time(limits.budget.time.unwrap_or_default())
+        } else if limits
+            .budget
+            .memory
+            .is_some_and(|memory| PEAK.with(Cell::get).saturating_sub(limits.base) > memory)
+        {
+            Exceeded::Memory(limits.budget.memory.unwrap_or_default())
+        } else {
+            return Ok(());
+        };
+        Err(AocError::Exceeded(exceeded))
+    })
+}
+
+/// Runs `f` under `budget`. Fails if it ended over budget, even if it didn't
+/// check.
+pub fn run<T>(budget: &Budget, token: &Token, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let _guard = enter(budget, token);
+    let value = f()?;
+    check()?;
+    Ok(value)
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+    use std::thread;
+
+    fn exceeded<T>(result: Result<T>) -> Option<Exceeded> {
+        match result {
+            Err(AocError::Exceeded(exceeded)) => Some(exceeded),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_time() {
+        let budget = Budget {
+            time: Some(Duration::from_millis(20)),
+            memory: None,
+        };
+        let token = Token::default();
+        let result: Result<()> = run(&budget, &token, || loop {
+            check()?;
+        });
+        assert_eq!(exceeded(result), Some(Exceeded::Time(budget.time.unwrap())));
+        // Too late, without checking
+        let result = run(&budget, &token, || {
+            thread::sleep(Duration::from_millis(30));
+            Ok(())
+        });
+        assert_eq!(exceeded(result), Some(Exceeded::Time(budget.time.unwrap())));
+        assert!(run(&budget, &token, || Ok(())).is_ok());
+        // No budget outside of run
+        assert!(check().is_ok());
+    }
+
+    #[test]
+    fn test_memory() {
+        let budget = Budget {
+            time: None,
+            memory: Some(64 * 1024),
+        };
+        let token = Token::default();
+        let big = vec![0u8; MIB];
+        // What the thread had before doesn't count
+        assert!(run(&budget, &token, || Ok(black_box(vec![0u8; 1024]).len())).is_ok());
+        let result = run(&budget, &token, || {
+            drop(black_box(vec![0u8; MIB]));
+            Ok(())
+        });
+        assert_eq!(exceeded(result), Some(Exceeded::Memory(64 * 1024)));
+        // Each step on its own
+        let result = run(&budget, &token, || {
+            drop(black_box(vec![0u8; MIB]));
+            reset_peak();
+            check()
+        });
+        assert!(result.is_ok());
+        assert_eq!(
+            Exceeded::Memory(64 * 1024).to_string(),
+            "exceeded the memory budget of 65536 bytes"
+        );
+        drop(big);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let token = Token::default();
+        let solver = {
+            let token = token.clone();
+            thread::spawn(move || {
+                run(&Budget::default(), &token, || loop {
+                    check()?;
+                    thread::sleep(Duration::from_millis(1));
+                })
+            })
+        };
+        token.cancel();
+        assert_eq!(
+            exceeded::<()>(solver.join().unwrap()),
+            Some(Exceeded::Cancelled)
+        );
+    }
+}
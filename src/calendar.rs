@@ -0,0 +1,427 @@
+//! Solving every day at once on a pool of workers (`aoc run all`).
+//!
+//! Each day is parsed and solved on a thread of its own, at most `workers`
+//! of them at once, and gets the [`Budget`] for all of its parts, the memory
+//! budget for each part on its own. A day that ran out of time is cancelled,
+//! and its worker goes to the next day once its thread ends. The days that
+//! [check](budget::check) their budget stop soon after, the others hold on
+//! to their worker until they end, and are left running in the background
+//! once no day is waiting. Panics are caught per parse and per part, so one
+//! broken day doesn't hide the others.
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::budget::{self, Budget, Exceeded, Token};
+use crate::manifest::Expected;
+use crate::{Answer, AocError, Day, Part, Solver};
+
+/// One day to solve
+pub struct Job {
+    pub day: &'static Day,
+    /// Path of the input, for the results
+    pub input: String,
+    pub contents: String,
+    /// The solver with the puzzle constants of the input
+    pub solver: Box<dyn Solver>,
+    pub parts: Vec<Part>,
+    pub expected: Option<Expected>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The expected answer
+    Ok,
+    /// No answer is expected yet
+    Unchecked,
+    /// Not the expected answer, which is given
+    Mismatch(Answer),
+    /// Out of time and cancelled, though it runs on until it checks its
+    /// budget
+    Timeout,
+    /// Over the memory budget, in bytes
+    OverMemory(usize),
+    /// The message of the panic
+    Panic(String),
+    /// The input could not be read or parsed, or had no solution. The first
+    /// line of the error, without its `error: `.
+    Error(String),
+}
+
+impl Status {
+    /// The status of a part that failed with `e`
+    pub fn error(e: &AocError) -> Status {
+        // The first line of a parse error says what was expected
+        let message = e.to_string();
+        let line = message.lines().next().unwrap_or("");
+        Status::Error(line.strip_prefix("error: ").unwrap_or(line).to_owned())
+    }
+
+    /// Whether it points at a broken solver or input. A solution over its
+    /// budget may just be slow (day 17).
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Status::Mismatch(_) | Status::Panic(_) | Status::Error(_)
+        )
+    }
+
+    /// The status without the details: ok, unchecked, mismatch, ...
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unchecked => "unchecked",
+            Status::Mismatch(_) => "mismatch",
+            Status::Timeout => "timeout",
+            Status::OverMemory(_) => "memory",
+            Status::Panic(_) => "panic",
+            Status::Error(_) => "error",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Mismatch(expected) => write!(f, "mismatch, expected {}", expected.short()),
+            Status::OverMemory(bytes) => write!(f, "{}", Exceeded::Memory(*bytes)),
+            Status::Panic(message) => write!(f, "panic: {}", message),
+            Status::Error(message) => write!(f, "error: {}", message),
+            status => write!(f, "{}", status.name()),
+        }
+    }
+}
+
+/// The result of one part of one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: Option<Answer>,
+    /// Time to solve the part, without parsing
+    pub elapsed: Option<Duration>,
+    pub status: Status,
+}
+
+impl Row {
+    /// A part without an answer
+    pub fn failed(day: u8, part: Part, input: &str, status: Status) -> Row {
+        Row {
+            day,
+            part,
+            input: input.to_owned(),
+            answer: None,
+            elapsed: None,
+            status,
+        }
+    }
+}
+
+/// What the thread of a day reports
+enum Event {
+    /// The input could not be parsed, no part was solved
+    ParseFailed(Status),
+    Solved(Part, Result<(Answer, Duration), Status>),
+    Finished,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_owned(),
+        },
+    }
+}
+
+/// Runs `f`, turning its errors and panics into a status
+fn catch<T>(f: impl FnOnce() -> crate::Result<T>) -> Result<T, Status> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(AocError::Exceeded(Exceeded::Memory(bytes)))) => Err(Status::OverMemory(bytes)),
+        Ok(Err(AocError::Exceeded(_))) => Err(Status::Timeout),
+        Ok(Err(e)) => Err(Status::error(&e)),
+        Err(payload) => Err(Status::Panic(panic_message(payload))),
+    }
+}
+
+fn solve_day(solver: &dyn Solver, contents: &str, parts: &[Part], send: impl Fn(Event)) {
+    // The budget is checked after each step too, for the solvers that don't
+    fn checked<T>(result: crate::Result<T>) -> crate::Result<T> {
+        let value = result?;
+        budget::check()?;
+        Ok(value)
+    }
+    match catch(|| checked(solver.parse_input(contents))) {
+        Err(status) => send(Event::ParseFailed(status)),
+        Ok(parsed) => {
+            for &part in parts {
+                budget::reset_peak();
+                let start = Instant::now();
+                let result = catch(|| checked(solver.solve_parsed(parsed.as_ref(), part)))
+                    .map(|answer| (answer, start.elapsed()));
+                send(Event::Solved(part, result));
+            }
+        }
+    }
+    send(Event::Finished);
+}
+
+/// A job that was started, until its thread ends
+struct Running {
+    deadline: Option<Instant>,
+    /// Cancelled when the day ran out of time
+    token: Token,
+    /// Parts without a result yet
+    pending: Vec<Part>,
+}
+
+/// Solves the `jobs` on at most `workers` threads at once, each day within
+/// `budget`, and returns a row for each of their parts, ordered by day and
+/// part.
+pub fn run_all(jobs: Vec<Job>, workers: usize, budget: Budget) -> Vec<Row> {
+    let (sender, receiver) = mpsc::channel();
+    let mut rows = vec![];
+    let mut running: Vec<Option<Running>> = jobs.iter().map(|_| None).collect();
+    let mut describe = vec![];
+    let mut waiting = jobs.into_iter().enumerate().peekable();
+    let mut busy = 0;
+    loop {
+        while busy < workers.max(1) {
+            let Some((index, job)) = waiting.next() else {
+                break;
+            };
+            let token = Token::default();
+            running[index] = Some(Running {
+                deadline: budget.time.map(|time| Instant::now() + time),
+                token: token.clone(),
+                pending: job.parts.clone(),
+            });
+            describe.push((job.day.number, job.input, job.expected));
+            let (solver, contents, parts) = (job.solver, job.contents, job.parts);
+            let sender = sender.clone();
+            thread::spawn(move || {
+                // The receiver is gone when the day ran out of time
+                let send = |event| {
+                    let _ = sender.send((index, event));
+                };
+                let _budget = budget::enter(&budget, &token);
+                solve_day(solver.as_ref(), &contents, &parts, send);
+            });
+            busy += 1;
+        }
+        // The days that ran out of time are only waited for to free a worker
+        let cancelled = |job: &Running| job.token.is_cancelled();
+        if waiting.peek().is_none() && running.iter().flatten().all(cancelled) {
+            break;
+        }
+
+        let now = Instant::now();
+        let next_deadline = running
+            .iter()
+            .flatten()
+            .filter(|job| !cancelled(job))
+            .filter_map(|job| job.deadline)
+            .min();
+        let timeout = next_deadline.map_or(Duration::MAX, |deadline| deadline - now.min(deadline));
+        match receiver.recv_timeout(timeout) {
+            Ok((index, event)) => {
+                let Some(job) = running[index].as_mut() else {
+                    unreachable!("a day is running until it finishes");
+                };
+                let (day, input, expected) = &describe[index];
+                match event {
+                    Event::ParseFailed(status) => {
+                        for part in job.pending.drain(..) {
+                            rows.push(Row::failed(*day, part, input, status.clone()));
+                        }
+                    }
+                    // Late news from a day that ran out of time
+                    Event::Solved(part, _) if !job.pending.contains(&part) => {}
+                    Event::Solved(part, result) => {
+                        job.pending.retain(|&p| p != part);
+                        rows.push(match result {
+                            Ok((answer, elapsed)) => {
+                                let status = match expected.as_ref().and_then(|e| e.answer(part)) {
+                                    Some(expected) if expected == &answer => Status::Ok,
+                                    Some(expected) => Status::Mismatch(expected.clone()),
+                                    None => Status::Unchecked,
+                                };
+                                Row {
+                                    day: *day,
+                                    part,
+                                    input: input.clone(),
+                                    answer: Some(answer),
+                                    elapsed: Some(elapsed),
+                                    status,
+                                }
+                            }
+                            Err(status) => Row::failed(*day, part, input, status),
+                        });
+                    }
+                    Event::Finished => {
+                        running[index] = None;
+                        busy -= 1;
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                for (index, job) in running.iter_mut().enumerate() {
+                    let Some(job) = job.as_mut() else {
+                        continue;
+                    };
+                    if !cancelled(job) && job.deadline.is_some_and(|deadline| deadline <= now) {
+                        let (day, input, _) = &describe[index];
+                        job.token.cancel();
+                        for part in job.pending.drain(..) {
+                            rows.push(Row::failed(*day, part, input, Status::Timeout));
+                        }
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("a sender is kept"),
+        }
+    }
+
+    rows.sort_by_key(|row| (row.day, row.part == Part::Two));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AocError, Result, Solution};
+
+    /// Answers with the number in its input, after sleeping that many
+    /// milliseconds for part 2, checking its budget each millisecond for
+    /// even numbers. Panics for a negative number and fails for zero. Part 1
+    /// allocates that many bytes for a million and more.
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Solution for Echo {
+        type Input = i64;
+
+        fn parse(&self, input: &str) -> Result<i64> {
+            Ok(crate::parse_number(input, input.trim())?)
+        }
+
+        fn part_one(&self, n: &i64) -> Result<Answer> {
+            match n {
+                0 => Err(AocError::Solve("Nothing to echo".to_owned())),
+                n if *n < 0 => panic!("Negative echo"),
+                n if *n >= 1000000 => Ok(Answer::from(
+                    std::hint::black_box(vec![0u8; *n as usize]).len(),
+                )),
+                n => Ok(Answer::Number(*n)),
+            }
+        }
+
+        fn part_two(&self, n: &i64) -> Result<Answer> {
+            for _ in 0..n.unsigned_abs() {
+                if n % 2 == 0 {
+                    budget::check()?;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            Ok(Answer::Number(*n))
+        }
+    }
+
+    fn job(day: u8, contents: &str, expected: Option<i64>) -> Job {
+        Job {
+            day: Box::leak(Box::new(Day {
+                number: day,
+                title: "echo",
+                solver: &Echo,
+            })),
+            input: format!("day_{}.txt", day),
+            contents: contents.to_owned(),
+            solver: Box::new(Echo),
+            parts: Part::BOTH.to_vec(),
+            expected: Some(Expected {
+                input: format!("day_{}.txt", day),
+                day,
+                part_one: expected.map(Answer::Number),
+                part_two: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_run_all() {
+        let jobs = vec![
+            job(1, "5", Some(5)),
+            job(2, "6", Some(7)),
+            job(3, "-1", None),
+            job(4, "x", None),
+            job(5, "2000", None),
+            job(6, "0", None),
+        ];
+        let budget = Budget {
+            time: Some(Duration::from_millis(300)),
+            memory: None,
+        };
+        let mut rows = run_all(jobs, 2, budget);
+        // Apart, as the report of a panic allocates on the thread of its day.
+        // Part 2 checks its budget, and only runs out of time.
+        let budget = Budget {
+            memory: Some(1 << 16),
+            ..budget
+        };
+        rows.extend(run_all(vec![job(7, "1000000", None)], 2, budget));
+        let statuses: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row.input.as_str(), row.status.name()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("day_1.txt", "ok"),
+                ("day_1.txt", "unchecked"),
+                ("day_2.txt", "mismatch"),
+                ("day_2.txt", "unchecked"),
+                ("day_3.txt", "panic"),
+                ("day_3.txt", "unchecked"),
+                ("day_4.txt", "error"),
+                ("day_4.txt", "error"),
+                ("day_5.txt", "unchecked"),
+                ("day_5.txt", "timeout"),
+                ("day_6.txt", "error"),
+                ("day_6.txt", "unchecked"),
+                ("day_7.txt", "memory"),
+                ("day_7.txt", "timeout"),
+            ]
+        );
+        assert_eq!(rows[2].status.to_string(), "mismatch, expected 7");
+        assert_eq!(rows[4].status.to_string(), "panic: Negative echo");
+        assert_eq!(rows[6].status.to_string(), "error: expected number");
+        assert_eq!(rows[9].answer, None);
+        assert_eq!(
+            rows[12].status.to_string(),
+            "exceeded the memory budget of 65536 bytes"
+        );
+    }
+
+    #[test]
+    fn test_workers_of_cancelled_days() {
+        // Day 1 runs on past its time, without checking, and keeps the only
+        // worker until then
+        let jobs = vec![job(1, "301", None), job(2, "1", Some(1))];
+        let budget = Budget {
+            time: Some(Duration::from_millis(100)),
+            memory: None,
+        };
+        let start = Instant::now();
+        let rows = run_all(jobs, 1, budget);
+        assert!(start.elapsed() >= Duration::from_millis(301));
+        let statuses: Vec<&str> = rows.iter().map(|row| row.status.name()).collect();
+        assert_eq!(statuses, vec!["unchecked", "timeout", "ok", "unchecked"]);
+    }
+}
@@ -0,0 +1,624 @@
+//! Client for the puzzle site, behind the `client` feature: downloads the
+//! puzzle inputs and submits answers.
+//!
+//! The session token comes from the `AOC_SESSION` environment variable or
+//! the `.aoc_session` file. Requests are spaced out by a [`Throttle`] that
+//! remembers the last request in `.aoc_throttle`, so separate runs share it.
+//!
+//! Plain `http://` sites, like the [`MockServer`] the tests use, are talked
+//! to directly. The real site needs TLS, for which requests go through
+//! `curl`.
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::http::{self, Request, Response};
+use crate::journal::Outcome;
+use crate::{Answer, Part};
+
+pub const SITE: &str = "https://adventofcode.com";
+/// Overrides [`SITE`], e.g. with the address of a mock server
+pub const SITE_ENV: &str = "AOC_BASE_URL";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSION_PATH: &str = ".aoc_session";
+pub const THROTTLE_PATH: &str = ".aoc_throttle";
+/// Shortest time between two requests to the site
+pub const THROTTLE_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (aoc download/submit)"
+);
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    /// The site could not be reached or sent something unreadable
+    Connection(String),
+    /// The site answered, but not with what was asked for
+    Refused(String),
+    Io {
+        path: String,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "No session token: set {} or write the session cookie to {}",
+                SESSION_ENV, SESSION_PATH
+            ),
+            ClientError::Connection(message) | ClientError::Refused(message) => {
+                write!(f, "{}", message)
+            }
+            ClientError::Io { path, source } => write!(f, "Cannot access {}: {}", path, source),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// The session token from `AOC_SESSION`, or else from the file at `path`.
+pub fn session_token(path: &str) -> Result<String> {
+    let token = match std::env::var(SESSION_ENV) {
+        Ok(token) => token,
+        Err(_) => match fs::read_to_string(path) {
+            Ok(token) => token,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(ClientError::NoSession),
+            Err(source) => {
+                return Err(ClientError::Io {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        },
+    };
+    // The cookie value may be pasted with its name
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() {
+        return Err(ClientError::NoSession);
+    }
+    Ok(token.to_owned())
+}
+
+/// Keeps at least `interval` between requests. With a file, the time of
+/// the last request outlives the process.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    interval: Duration,
+    path: Option<PathBuf>,
+    last: Option<SystemTime>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Throttle {
+        Throttle {
+            interval,
+            path: None,
+            last: None,
+        }
+    }
+
+    /// A throttle that keeps the time of the last request in `path`.
+    pub fn persistent(interval: Duration, path: impl Into<PathBuf>) -> Throttle {
+        let path = path.into();
+        // A missing or garbled file means no recent request
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        Throttle {
+            interval,
+            path: Some(path),
+            last,
+        }
+    }
+
+    /// Sleeps until a request is allowed and records it. Returns how long
+    /// it slept.
+    pub fn wait(&mut self) -> Result<Duration> {
+        let now = SystemTime::now();
+        let since_last = self
+            .last
+            .map(|last| now.duration_since(last).unwrap_or_default());
+        let delay = match since_last {
+            Some(elapsed) => self.interval.saturating_sub(elapsed),
+            None => Duration::ZERO,
+        };
+        thread::sleep(delay);
+
+        let now = SystemTime::now();
+        self.last = Some(now);
+        if let Some(path) = &self.path {
+            let millis = now
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            fs::write(path, format!("{}\n", millis)).map_err(|source| ClientError::Io {
+                path: path.display().to_string(),
+                source,
+            })?;
+        }
+        Ok(delay)
+    }
+}
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way
+    Wrong,
+    /// An answer was submitted too recently. How long to wait, if the site
+    /// said.
+    RateLimited(Option<Duration>),
+    /// The part is solved already or not unlocked yet
+    WrongLevel,
+    /// Anything else, with the text of the page
+    Unknown(String),
+}
+
+impl Verdict {
+    /// What goes into the journal. Only answers the site judged count.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self {
+            Verdict::Correct => Some(Outcome::Correct),
+            Verdict::TooHigh => Some(Outcome::TooHigh),
+            Verdict::TooLow => Some(Outcome::TooLow),
+            Verdict::Wrong => Some(Outcome::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "rate-limited, wait {}s", wait.as_secs())
+            }
+            Verdict::RateLimited(None) => write!(f, "rate-limited"),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked"),
+            Verdict::Unknown(text) => write!(f, "unexpected response: {}", text),
+        }
+    }
+}
+
+/// Reads the verdict from the page the site shows after a submission.
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = page_text(html);
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited(wait_time(&text))
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// The text of the `<article>` of the page, or of the whole page, without
+/// tags and with the whitespace collapsed
+fn page_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article.split_once('>').map_or(article, |(_, a)| a))
+        .unwrap_or(html);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// From "You have 1m 5s left to wait."
+fn wait_time(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+    let mut seconds = 0;
+    for part in time.split_whitespace() {
+        let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+pub struct Client {
+    site: String,
+    session: String,
+    throttle: Throttle,
+}
+
+impl Client {
+    /// `site` is the address of the puzzle site without a trailing slash,
+    /// e.g. [`SITE`].
+    pub fn new(site: &str, session: &str, throttle: Throttle) -> Client {
+        Client {
+            site: site.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            throttle,
+        }
+    }
+
+    /// The input of `day`.
+    pub fn fetch_input(&mut self, day: u8) -> Result<String> {
+        let response = self.send(Request::new("GET", &format!("/2022/day/{}/input", day)))?;
+        match response.status {
+            200 => Ok(response.body),
+            404 => Err(ClientError::Refused(format!(
+                "The input of day {} is not available yet",
+                day
+            ))),
+            400 | 500 => Err(ClientError::Refused(format!(
+                "The site did not accept the session token ({})",
+                response
+            ))),
+            _ => Err(unexpected(&response)),
+        }
+    }
+
+    pub fn submit(&mut self, day: u8, part: Part, answer: &Answer) -> Result<Verdict> {
+        let mut request = Request::new("POST", &format!("/2022/day/{}/answer", day));
+        request.headers.push((
+            "Content-Type".to_owned(),
+            "application/x-www-form-urlencoded".to_owned(),
+        ));
+        let level = match part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+        request.body = format!(
+            "level={}&answer={}",
+            level,
+            http::form_encode(&answer.to_string())
+        );
+        let response = self.send(request)?;
+        match response.status {
+            200 => Ok(parse_verdict(&response.body)),
+            // Logged out visitors are sent to the puzzle
+            302 | 400 | 500 => Err(ClientError::Refused(format!(
+                "The site did not accept the session token ({})",
+                response
+            ))),
+            _ => Err(unexpected(&response)),
+        }
+    }
+
+    fn send(&mut self, mut request: Request) -> Result<Response> {
+        self.throttle.wait()?;
+        request
+            .headers
+            .push(("Cookie".to_owned(), format!("session={}", self.session)));
+        request
+            .headers
+            .push(("User-Agent".to_owned(), USER_AGENT.to_owned()));
+        match self.site.strip_prefix("http://") {
+            Some(host) => send_plain(host, request),
+            None => send_with_curl(&self.site, request),
+        }
+    }
+}
+
+fn unexpected(response: &Response) -> ClientError {
+    let text = page_text(&response.body);
+    let summary: String = text.chars().take(200).collect();
+    ClientError::Refused(format!("Unexpected response {}: {}", response, summary))
+}
+
+fn send_plain(host: &str, mut request: Request) -> Result<Response> {
+    let connection_error = |e: io::Error| ClientError::Connection(format!("{}: {}", host, e));
+    let stream = TcpStream::connect(host).map_err(connection_error)?;
+    stream
+        .set_read_timeout(Some(TIMEOUT))
+        .map_err(connection_error)?;
+    request.headers.push(("Host".to_owned(), host.to_owned()));
+    request
+        .headers
+        .push(("Connection".to_owned(), "close".to_owned()));
+    request.write_to(&mut &stream).map_err(connection_error)?;
+    Response::read(&mut BufReader::new(&stream)).map_err(connection_error)
+}
+
+/// Sends the request with `curl`, which is given the headers and body in a
+/// config on its standard input to keep the session token out of the
+/// process list.
+fn send_with_curl(site: &str, request: Request) -> Result<Response> {
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut config = format!(
+        "url = {}\nrequest = {}\n",
+        quote(&format!("{}{}", site, request.path)),
+        quote(&request.method)
+    );
+    for (name, value) in &request.headers {
+        config += &format!("header = {}\n", quote(&format!("{}: {}", name, value)));
+    }
+    if !request.body.is_empty() {
+        config += &format!("data-raw = {}\n", quote(&request.body));
+    }
+
+    let curl_error = |e: io::Error| ClientError::Connection(format!("Cannot run curl: {}", e));
+    let mut curl = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--include",
+            "--raw",
+            "--http1.1",
+        ])
+        .args([
+            "--max-time",
+            &TIMEOUT.as_secs().to_string(),
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(curl_error)?;
+    if let Some(mut stdin) = curl.stdin.take() {
+        stdin.write_all(config.as_bytes()).map_err(curl_error)?;
+    }
+    let output = curl.wait_with_output().map_err(curl_error)?;
+    if !output.status.success() {
+        return Err(ClientError::Connection(format!(
+            "{}: {}",
+            site,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Response::read(&mut &output.stdout[..])
+        .map_err(|e| ClientError::Connection(format!("{}: {}", site, e)))
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// A local HTTP server standing in for the puzzle site. It answers every
+/// request with `handler` and keeps the requests for inspection.
+pub struct MockServer {
+    /// e.g. http://127.0.0.1:36095
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    pub fn start(
+        handler: impl Fn(&Request) -> Response + Send + Sync + 'static,
+    ) -> io::Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let requests = Arc::new(Mutex::new(vec![]));
+        let handler: Arc<Handler> = Arc::new(handler);
+        let received = Arc::clone(&requests);
+        // Serves until the process ends
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (handler, received) = (Arc::clone(&handler), Arc::clone(&received));
+                thread::spawn(move || {
+                    if let Ok(Some(request)) = Request::read(&mut BufReader::new(&stream)) {
+                        let response = handler(&request);
+                        received.lock().unwrap().push(request);
+                        let _ = response.write_to(&mut &stream);
+                    }
+                });
+            }
+        });
+        Ok(MockServer { url, requests })
+    }
+
+    /// A server that behaves like the puzzle site for a user with the
+    /// session token `session`: it serves `inputs` by day and judges
+    /// answers against `answers`, by day and part.
+    pub fn puzzle_site(
+        session: &str,
+        inputs: Vec<(u8, String)>,
+        answers: Vec<(u8, Part, i64)>,
+    ) -> io::Result<MockServer> {
+        let cookie = format!("session={}", session);
+        MockServer::start(move |request| {
+            if request.header("Cookie") != Some(&cookie) {
+                return Response::new(400, "Puzzle inputs differ by user.  Please log in.");
+            }
+            let path: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+            let day = match path[..] {
+                ["2022", "day", day, _] => day.parse::<u8>().ok(),
+                _ => None,
+            };
+            match (request.method.as_str(), day, path.last()) {
+                ("GET", Some(day), Some(&"input")) => inputs
+                    .iter()
+                    .find(|(d, _)| *d == day)
+                    .map(|(_, input)| Response::new(200, input.clone()))
+                    .unwrap_or_else(|| Response::new(404, "404 Not Found")),
+                ("POST", Some(day), Some(&"answer")) => {
+                    let field = |name: &str| {
+                        request.body.split('&').find_map(|pair| {
+                            pair.strip_prefix(name)?
+                                .strip_prefix('=')
+                                .map(str::to_owned)
+                        })
+                    };
+                    let part = match field("level").as_deref() {
+                        Some("1") => Part::One,
+                        Some("2") => Part::Two,
+                        _ => return Response::new(400, "Bad level"),
+                    };
+                    let expected = answers
+                        .iter()
+                        .find(|(d, p, _)| *d == day && *p == part)
+                        .map(|(_, _, answer)| *answer);
+                    let given = field("answer").and_then(|a| a.parse::<i64>().ok());
+                    let message = match (expected, given) {
+                        (None, _) => "You don't seem to be solving the right level.",
+                        (Some(e), Some(g)) if g == e => "That's the right answer!",
+                        (Some(e), Some(g)) if g > e => {
+                            "That's not the right answer; your answer is too high."
+                        }
+                        (Some(_), Some(_)) => {
+                            "That's not the right answer; your answer is too low."
+                        }
+                        (Some(_), None) => "That's not the right answer.",
+                    };
+                    Response::new(
+                        200,
+                        format!("<main><article><p>{}</p></article></main>", message),
+                    )
+                }
+                _ => Response::new(404, "404 Not Found"),
+            }
+        })
+    }
+
+    /// The requests received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(server: &MockServer, session: &str) -> Client {
+        Client::new(&server.url, session, Throttle::new(Duration::ZERO))
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let server =
+            MockServer::puzzle_site("abc", vec![(1, "1000\n2000\n".to_owned())], vec![]).unwrap();
+        assert_eq!(
+            client(&server, "abc").fetch_input(1).unwrap(),
+            "1000\n2000\n"
+        );
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc"));
+        assert!(requests[0].header("User-Agent").is_some());
+
+        let e = client(&server, "abc").fetch_input(2).unwrap_err();
+        assert!(e.to_string().contains("not available yet"), "{}", e);
+        let e = client(&server, "wrong").fetch_input(1).unwrap_err();
+        assert!(e.to_string().contains("session token"), "{}", e);
+    }
+
+    #[test]
+    fn test_submit() {
+        let answers = vec![(1, Part::One, 24000)];
+        let server = MockServer::puzzle_site("abc", vec![], answers).unwrap();
+        let mut client = client(&server, "abc");
+        let mut submit = |part, answer: i64| client.submit(1, part, &answer.into()).unwrap();
+        assert_eq!(submit(Part::One, 24000), Verdict::Correct);
+        assert_eq!(submit(Part::One, 25000), Verdict::TooHigh);
+        assert_eq!(submit(Part::One, 1), Verdict::TooLow);
+        assert_eq!(submit(Part::Two, 1), Verdict::WrongLevel);
+        assert_eq!(server.requests()[0].body, "level=1&answer=24000");
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text: &str| {
+            format!(
+                "<html><main><article><p>{}</p></article></main></html>",
+                text
+            )
+        };
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.")),
+            Verdict::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer.  If you're stuck, try again."
+            )),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_verdict("<p>Something else</p>"),
+            Verdict::Unknown("Something else".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_throttle() {
+        let server = MockServer::start(|_| Response::new(200, "ok")).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc_throttle_{}", std::process::id()));
+        let interval = Duration::from_millis(200);
+        let mut client = Client::new(&server.url, "abc", Throttle::persistent(interval, &path));
+        client.fetch_input(1).unwrap();
+
+        // A second run waits for the first one's request
+        let mut throttle = Throttle::persistent(interval, &path);
+        let waited = throttle.wait().unwrap();
+        assert!(waited > Duration::from_millis(100), "{:?}", waited);
+        assert!(throttle.wait().unwrap() > Duration::from_millis(100));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let mut client = Client::new(&url, "abc", Throttle::new(Duration::ZERO));
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::Connection(_))
+        ));
+    }
+}
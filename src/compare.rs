@@ -0,0 +1,173 @@
+//! Solving an input with every strategy of a day, to check that they agree
+//! and see which is faster (`aoc compare`).
+//!
+//! The strategies of a day share its input type, so the input is parsed once.
+//! Each part of each strategy is timed over repeated runs, every run within
+//! the budget, so that a slow strategy like the brute force of day 17 gives
+//! up without holding up the others.
+use std::time::Instant;
+
+use crate::bench::Stats;
+use crate::budget::{self, Budget, Token};
+use crate::params::STRATEGY;
+use crate::{Answer, Part, Result, Solver};
+
+/// One part solved with one strategy
+#[derive(Debug)]
+pub struct Run {
+    pub strategy: &'static str,
+    pub part: Part,
+    /// The answer of the last run, or why there is none
+    pub answer: Result<Answer>,
+    /// Times of the runs, if they all gave an answer
+    pub stats: Option<Stats>,
+}
+
+pub struct Comparison {
+    pub parse: Stats,
+    /// By part, then by strategy
+    pub runs: Vec<Run>,
+}
+
+impl Comparison {
+    /// Whether the strategies that answered `part` gave the same answer
+    pub fn agree(&self, part: Part) -> bool {
+        let mut answers = self
+            .runs
+            .iter()
+            .filter(|run| run.part == part)
+            .filter_map(|run| run.answer.as_ref().ok());
+        match answers.next() {
+            Some(first) => answers.all(|answer| answer == first),
+            None => true,
+        }
+    }
+
+    /// The parts the strategies disagree on
+    pub fn disagreements(&self) -> Vec<Part> {
+        let mut parts: Vec<Part> = self.runs.iter().map(|run| run.part).collect();
+        parts.dedup();
+        parts.retain(|&part| !self.agree(part));
+        parts
+    }
+}
+
+/// Parses `input` once and solves `parts` of it with every strategy of
+/// `solver`, `runs` times within `budget` each. Fails if the input cannot be
+/// parsed.
+pub fn compare(
+    solver: &dyn Solver,
+    input: &str,
+    parts: &[Part],
+    runs: usize,
+    budget: &Budget,
+) -> Result<Comparison> {
+    let mut parse_times = vec![];
+    let mut parsed = None;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        parsed = Some(solver.parse_input(input)?);
+        parse_times.push(start.elapsed());
+    }
+    let parsed = parsed.unwrap();
+
+    let mut strategies = vec![];
+    for name in solver.strategies() {
+        let overrides = [(STRATEGY.to_owned(), name.to_owned())];
+        strategies.push((name, solver.with_params(&overrides)?));
+    }
+
+    let mut results = vec![];
+    for &part in parts {
+        for (name, strategy) in &strategies {
+            let mut times = vec![];
+            let answer = loop {
+                let start = Instant::now();
+                let answer = budget::run(budget, &Token::default(), || {
+                    strategy.solve_parsed(parsed.as_ref(), part)
+                });
+                if answer.is_ok() {
+                    times.push(start.elapsed());
+                }
+                if answer.is_err() || times.len() >= runs.max(1) {
+                    break answer;
+                }
+            };
+            results.push(Run {
+                strategy: name,
+                part,
+                stats: answer.is_ok().then(|| Stats::new(&times)),
+                answer,
+            });
+        }
+    }
+    Ok(Comparison {
+        parse: Stats::new(&parse_times),
+        runs: results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, AocError};
+    use std::time::Duration;
+
+    #[test]
+    fn test_compare() {
+        let solver = days::find(17).unwrap().solver;
+        let budget = Budget {
+            time: Some(Duration::from_millis(100)),
+            memory: None,
+        };
+        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+        let comparison = compare(solver, input, &Part::BOTH, 2, &budget).unwrap();
+        let runs: Vec<(Part, &str, bool)> = comparison
+            .runs
+            .iter()
+            .map(|run| (run.part, run.strategy, run.stats.is_some()))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                (Part::One, "cycles", true),
+                (Part::One, "brute_force", true),
+                (Part::Two, "cycles", true),
+                (Part::Two, "brute_force", false),
+            ]
+        );
+        assert_eq!(comparison.runs[0].stats.unwrap().runs, 2);
+        assert!(matches!(
+            comparison.runs[3].answer,
+            Err(AocError::Exceeded(_))
+        ));
+        assert_eq!(
+            comparison.runs[2].answer.as_ref().unwrap(),
+            &Answer::from(1514285714288i64)
+        );
+        assert!(comparison.disagreements().is_empty());
+    }
+
+    #[test]
+    fn test_disagreements() {
+        let run = |strategy, part, answer: Result<Answer>| Run {
+            strategy,
+            part,
+            answer,
+            stats: None,
+        };
+        let failed = || Err(AocError::Solve("No answer".to_owned()));
+        let comparison = Comparison {
+            parse: Stats::new(&[Duration::ZERO]),
+            runs: vec![
+                run("a", Part::One, Ok(Answer::from(1))),
+                run("b", Part::One, failed()),
+                run("c", Part::One, Ok(Answer::from(1))),
+                run("a", Part::Two, Ok(Answer::from(2))),
+                run("b", Part::Two, Ok(Answer::from(3))),
+            ],
+        };
+        assert!(comparison.agree(Part::One));
+        assert_eq!(comparison.disagreements(), vec![Part::Two]);
+    }
+}
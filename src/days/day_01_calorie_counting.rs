@@ -0,0 +1,101 @@
+//! Solution to an Advent of Code problem, day 01, 2022
+//! https://adventofcode.com/2022/day/01
+use itertools::Itertools;
+use std::cmp::Reverse;
+
+use crate::stream::{self, LineStream, Stream};
+use crate::{parse_number, Answer, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct CalorieCounting;
+
+fn top_sums(elves: &[Vec<u64>], n: usize) -> Vec<u64> {
+    elves
+        .iter()
+        .map(|calories| Reverse(calories.iter().sum::<u64>()))
+        .k_smallest(n)
+        .map(|n| n.0) // Remove Reverse()
+        .collect()
+}
+
+/// The largest sums of calories, counted line by line
+struct TopSums {
+    /// The sums of the elves so far, largest first
+    top: Vec<u64>,
+    n: usize,
+    /// The calories of the elf whose lines are being read
+    current: Option<u64>,
+}
+
+impl TopSums {
+    fn end_elf(&mut self) {
+        if let Some(sum) = self.current.take() {
+            let at = self.top.partition_point(|&top| top >= sum);
+            self.top.insert(at, sum);
+            self.top.truncate(self.n);
+        }
+    }
+}
+
+impl LineStream for TopSums {
+    fn line(&mut self, line: &str) -> Result<()> {
+        if line.is_empty() {
+            self.end_elf();
+        } else {
+            let calories: u64 = parse_number(line, line)?;
+            self.current = Some(self.current.unwrap_or(0) + calories);
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Answer> {
+        self.end_elf();
+        if self.top.is_empty() {
+            return Err(ParseError::at_offset("", 0, "number of calories").into());
+        }
+        Ok(self.top.iter().sum::<u64>().into())
+    }
+}
+
+impl Solution for CalorieCounting {
+    /// Calories carried by each elf
+    type Input = Vec<Vec<u64>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        // Elves are separated by an empty line
+        let mut elves: Vec<Vec<u64>> = vec![vec![]];
+        for line in input.lines() {
+            if line.is_empty() {
+                elves.push(vec![]);
+            } else {
+                elves.last_mut().unwrap().push(parse_number(input, line)?);
+            }
+        }
+        elves.retain(|calories| !calories.is_empty());
+
+        if elves.is_empty() {
+            return Err(ParseError::at_offset(input, 0, "number of calories").into());
+        }
+        Ok(elves)
+    }
+
+    fn part_one(&self, elves: &Self::Input) -> Result<Answer> {
+        Ok(top_sums(elves, 1)[0].into())
+    }
+
+    fn part_two(&self, elves: &Self::Input) -> Result<Answer> {
+        Ok(top_sums(elves, 3).iter().sum::<u64>().into())
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        let n = match part {
+            Part::One => 1,
+            Part::Two => 3,
+        };
+        Some(stream::lines(TopSums {
+            top: vec![],
+            n,
+            current: None,
+        }))
+    }
+}
@@ -0,0 +1,113 @@
+//! Solution to an Advent of Code problem, day 02, 2022
+//! https://adventofcode.com/2022/day/02
+
+use std::collections::HashMap;
+
+use crate::stream::{self, LineStream, Stream};
+use crate::{Answer, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct RockPaperScissors;
+
+/// Checks that the line looks like "A X": A, B or C, a space and X, Y or Z.
+fn validate_round(input: &str, line: &str) -> std::result::Result<(), ParseError> {
+    let expected = [("A, B or C", "ABC"), ("space", " "), ("X, Y or Z", "XYZ")];
+    let mut chars = line.char_indices();
+    for (what, valid) in expected {
+        match chars.next() {
+            Some((_, c)) if valid.contains(c) => {}
+            Some((i, _)) => return Err(ParseError::at(input, &line[i..], what)),
+            None => return Err(ParseError::after(input, line, what)),
+        }
+    }
+    if let Some((i, _)) = chars.next() {
+        return Err(ParseError::at(input, &line[i..], "end of line"));
+    }
+    Ok(())
+}
+
+/// The score of each round
+fn scores(part: Part) -> HashMap<&'static str, i32> {
+    let mut combinations = HashMap::new();
+    match part {
+        Part::One => {
+            combinations.insert("A X", 4); // 1 + 3
+            combinations.insert("A Y", 8); // 2 + 6
+            combinations.insert("A Z", 3); // 3 + 0
+            combinations.insert("B X", 1); // 1 + 0
+            combinations.insert("B Y", 5); // 2 + 3
+            combinations.insert("B Z", 9); // 3 + 6
+            combinations.insert("C X", 7); // 1 + 6
+            combinations.insert("C Y", 2); // 2 + 0
+            combinations.insert("C Z", 6); // 3 + 3
+        }
+        Part::Two => {
+            combinations.insert("A X", 3); // lose; rock + scissors; 3 + 0
+            combinations.insert("A Y", 4); // draw; rock + rock; 1 + 3
+            combinations.insert("A Z", 8); //  win; rock + paper; 2 + 6
+            combinations.insert("B X", 1); // lose; paper + rock; 1 + 0
+            combinations.insert("B Y", 5); // draw; paper + paper; 2 + 3
+            combinations.insert("B Z", 9); //  win; paper + scissors; 3 + 6
+            combinations.insert("C X", 2); // lose; scissors + paper; 2 + 0
+            combinations.insert("C Y", 6); // draw; scissors + scissors; 3 + 3
+            combinations.insert("C Z", 7); //  win; scissors + rock; 1 + 6
+        }
+    }
+    combinations
+}
+
+/// The total score, round by round
+struct Score {
+    scores: HashMap<&'static str, i32>,
+    total: i32,
+}
+
+impl LineStream for Score {
+    fn line(&mut self, line: &str) -> Result<()> {
+        validate_round(line, line)?;
+        self.total += self.scores[line];
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Answer> {
+        Ok(self.total.into())
+    }
+}
+
+impl Solution for RockPaperScissors {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut lines = vec![];
+        for line in input.lines() {
+            validate_round(input, line)?;
+            lines.push(line.to_owned());
+        }
+        Ok(lines)
+    }
+
+    fn part_one(&self, lines: &Self::Input) -> Result<Answer> {
+        let combinations_part_1 = scores(Part::One);
+        let score_part_1: i32 = lines
+            .iter()
+            .map(|l| combinations_part_1.get(l.as_str()).unwrap())
+            .sum();
+        Ok(score_part_1.into())
+    }
+
+    fn part_two(&self, lines: &Self::Input) -> Result<Answer> {
+        let combinations_part_2 = scores(Part::Two);
+        let score_part_2: i32 = lines
+            .iter()
+            .map(|l| combinations_part_2.get(l.as_str()).unwrap())
+            .sum();
+        Ok(score_part_2.into())
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        Some(stream::lines(Score {
+            scores: scores(part),
+            total: 0,
+        }))
+    }
+}
@@ -0,0 +1,135 @@
+//! Solution to an Advent of Code problem, day 03, 2022
+//! https://adventofcode.com/2022/day/03
+use itertools::Itertools;
+
+use crate::stream::{self, LineStream, Stream};
+use crate::{Answer, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct RucksackReorganization;
+
+fn priority(c: char) -> u64 {
+    if c.is_ascii_lowercase() {
+        1 + u64::from(c) - u64::from('a')
+    } else if c.is_ascii_uppercase() {
+        27 + u64::from(c) - u64::from('A')
+    } else {
+        panic!("Invalid char: {}", c);
+    }
+}
+
+fn find_common_item_priority_in_both_compartments(s: &str) -> u64 {
+    let mut types_in_first_compartment = [false; 52 + 1];
+    let chars = s.chars().collect::<Vec<char>>();
+    let len = chars.len();
+    let (first_compartment, second_compartment) = chars.split_at(len / 2);
+
+    for &char in first_compartment {
+        types_in_first_compartment[priority(char) as usize] = true;
+    }
+    for &char in second_compartment {
+        if types_in_first_compartment[priority(char) as usize] {
+            return priority(char);
+        }
+    }
+    0
+}
+
+fn find_badge_priority<'a, T: Iterator<Item = &'a str>>(s: T) -> u64 {
+    let mut counts_by_priority = [0_u8; 53];
+    for line in s {
+        let mut counted = [false; 53];
+        for c in line.chars() {
+            let p = priority(c) as usize;
+            if !counted[p] {
+                counted[p] = true;
+                counts_by_priority[p] += 1;
+                if counts_by_priority[p] >= 3 {
+                    return priority(c);
+                }
+            }
+        }
+    }
+    0
+}
+
+/// Checks that `line`, a slice of `input`, holds only item types.
+fn validate_rucksack(input: &str, line: &str) -> std::result::Result<(), ParseError> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((i, _)) => Err(ParseError::at(input, &line[i..], "item type (a-z or A-Z)")),
+        None => Ok(()),
+    }
+}
+
+/// The sum of priorities, rucksack by rucksack
+struct Priorities {
+    part: Part,
+    /// The rucksacks of the group of three being read
+    group: Vec<String>,
+    sum: u64,
+}
+
+impl LineStream for Priorities {
+    fn line(&mut self, line: &str) -> Result<()> {
+        validate_rucksack(line, line)?;
+        match self.part {
+            Part::One => self.sum += find_common_item_priority_in_both_compartments(line),
+            Part::Two => {
+                self.group.push(line.to_owned());
+                if self.group.len() == 3 {
+                    self.sum += find_badge_priority(self.group.iter().map(String::as_str));
+                    self.group.clear();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Answer> {
+        // An incomplete group at the end, as `chunks(3)` leaves it
+        if !self.group.is_empty() {
+            self.sum += find_badge_priority(self.group.iter().map(String::as_str));
+        }
+        Ok(self.sum.into())
+    }
+}
+
+impl Solution for RucksackReorganization {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut lines = vec![];
+        for line in input.lines() {
+            validate_rucksack(input, line)?;
+            lines.push(line.to_owned());
+        }
+        Ok(lines)
+    }
+
+    fn part_one(&self, lines: &Self::Input) -> Result<Answer> {
+        let s1: u64 = lines
+            .iter()
+            .map(|l| find_common_item_priority_in_both_compartments(l))
+            .sum();
+        Ok(s1.into())
+    }
+
+    fn part_two(&self, lines: &Self::Input) -> Result<Answer> {
+        let s2: u64 = lines
+            .iter()
+            .map(|l| l.as_str())
+            .chunks(3)
+            .into_iter()
+            .map(find_badge_priority)
+            .sum();
+        Ok(s2.into())
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        Some(stream::lines(Priorities {
+            part,
+            group: vec![],
+            sum: 0,
+        }))
+    }
+}
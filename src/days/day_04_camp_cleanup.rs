@@ -0,0 +1,123 @@
+//! Solution to an Advent of Code problem, day 04, 2022
+//! https://adventofcode.com/2022/day/04
+use nom::character::complete::char;
+use nom::combinator::verify;
+use nom::sequence::separated_pair;
+
+use crate::interval::IntervalSet;
+use crate::parse::{self, context, uint, IResult};
+use crate::stream::{self, LineStream, Stream};
+use crate::{Answer, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct CampCleanup;
+
+fn is_fully_contained(l: &IntervalSet<i64>, r: &IntervalSet<i64>) -> bool {
+    l.is_subset(r) || r.is_subset(l)
+}
+
+fn is_overlapping(l: &IntervalSet<i64>, r: &IntervalSet<i64>) -> bool {
+    !l.intersection(r).is_empty()
+}
+
+/// Sections `a-b`, with `a` no higher than `b` since a reversed range
+/// would be empty, and contained in any other
+fn interval(input: &str) -> IResult<'_, IntervalSet<i64>> {
+    let assignment = context(
+        "section assignment (a-b)",
+        separated_pair(uint, char('-'), uint),
+    );
+    let (input, (a, b)) = context(
+        "section assignment with a <= b",
+        verify(assignment, |(a, b)| a <= b),
+    )(input)?;
+    Ok((input, IntervalSet::from(a..=b)))
+}
+
+type Pair = (IntervalSet<i64>, IntervalSet<i64>);
+
+/// The pair of assignments in `line`, a slice of `input`
+fn parse_pair(input: &str, line: &str) -> std::result::Result<Pair, ParseError> {
+    parse::line(input, line, separated_pair(interval, char(','), interval))
+}
+
+/// The number of pairs whose assignments match, pair by pair
+struct Count {
+    matches: fn(&IntervalSet<i64>, &IntervalSet<i64>) -> bool,
+    count: usize,
+}
+
+impl LineStream for Count {
+    fn line(&mut self, line: &str) -> Result<()> {
+        let (l, r) = parse_pair(line, line)?;
+        if (self.matches)(&l, &r) {
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Answer> {
+        Ok(self.count.into())
+    }
+}
+
+impl Solution for CampCleanup {
+    type Input = Vec<Pair>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut parsed_lines = vec![];
+        for line in input.lines() {
+            parsed_lines.push(parse_pair(input, line)?);
+        }
+        Ok(parsed_lines)
+    }
+
+    fn part_one(&self, parsed_lines: &Self::Input) -> Result<Answer> {
+        let count_of_fully_contained = parsed_lines
+            .iter()
+            .filter(|(l, r)| is_fully_contained(l, r))
+            .count();
+        Ok(count_of_fully_contained.into())
+    }
+
+    fn part_two(&self, parsed_lines: &Self::Input) -> Result<Answer> {
+        let count_of_overlapping = parsed_lines
+            .iter()
+            .filter(|(l, r)| is_overlapping(l, r))
+            .count();
+        Ok(count_of_overlapping.into())
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        let matches = match part {
+            Part::One => is_fully_contained,
+            Part::Two => is_overlapping,
+        };
+        Some(stream::lines(Count { matches, count: 0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pair() {
+        let (l, r) = parse_pair("2-4,6-8", "2-4,6-8").unwrap();
+        assert_eq!((l, r), (IntervalSet::from(2..=4), IntervalSet::from(6..=8)));
+
+        for (line, column, expected) in [
+            ("1-3,5-2", 5, "section assignment with a <= b"),
+            ("1-3,5", 5, "section assignment (a-b)"),
+            ("1-3;5-6", 4, "','"),
+        ] {
+            let e = parse_pair(line, line).unwrap_err();
+            assert_eq!(
+                (e.column, e.expected.as_str()),
+                (column, expected),
+                "{}",
+                line
+            );
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Solution to an Advent of Code problem, day 05, 2022
+//! https://adventofcode.com/2022/day/05
+use nom::bytes::complete::tag;
+use nom::combinator::recognize;
+use nom::sequence::{preceded, tuple};
+
+use crate::animate::{Frame, Simulation};
+use crate::grid::Grid;
+use crate::parse::{self, context, uint, IResult};
+use crate::{Answer, AocError, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct SupplyStacks;
+
+/// (how many, from, to), with zero-based stack indices
+type Move = (usize, usize, usize);
+
+/// The crane working through the moves. The CrateMover 9000 of part 1 moves
+/// one crate at a time, the CrateMover 9001 of part 2 all crates of a move
+/// at once.
+struct Crane<'a> {
+    stacks: Vec<Vec<u8>>,
+    moves: &'a [Move],
+    part: Part,
+    /// Index of the current move
+    next: usize,
+    /// Crates of the current move moved so far
+    moved: usize,
+    /// Stack that ran out of crates
+    empty: Option<usize>,
+}
+
+impl<'a> Crane<'a> {
+    fn new(stacks: &[Vec<u8>], moves: &'a [Move], part: Part) -> Crane<'a> {
+        Crane {
+            stacks: stacks.to_vec(),
+            moves,
+            part,
+            next: 0,
+            moved: 0,
+            empty: None,
+        }
+    }
+
+    fn run(mut self) -> Result<String> {
+        while self.step() {}
+        match self.empty {
+            Some(index) => Err(empty_stack(index)),
+            None => Ok(top_crates(self.stacks)),
+        }
+    }
+}
+
+impl Simulation for Crane<'_> {
+    fn step(&mut self) -> bool {
+        let Some(&(how_many, from, to)) = self.moves.get(self.next) else {
+            return false;
+        };
+        if self.empty.is_some() {
+            return false;
+        }
+        let count = match self.part {
+            Part::One => how_many.min(1),
+            Part::Two => how_many,
+        };
+        let Some(split_index) = self.stacks[from].len().checked_sub(count) else {
+            self.empty = Some(from);
+            return false;
+        };
+        let mut crates = self.stacks[from].split_off(split_index);
+        self.stacks[to].append(&mut crates);
+        self.moved += count;
+        if self.moved >= how_many {
+            self.next += 1;
+            self.moved = 0;
+        }
+        true
+    }
+
+    /// The stacks as drawn in the puzzle
+    fn frame(&self) -> Frame {
+        let depth = self.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+        let mut cells = Grid::new(self.stacks.len() * 4, depth + 1, ' ');
+        for (i, stack) in self.stacks.iter().enumerate() {
+            for (level, &letter) in stack.iter().enumerate() {
+                let y = depth - 1 - level;
+                cells[(i * 4, y)] = '[';
+                cells[(i * 4 + 1, y)] = letter as char;
+                cells[(i * 4 + 2, y)] = ']';
+            }
+            for (x, digit) in (i + 1).to_string().chars().enumerate() {
+                cells[(i * 4 + 1 + x, depth)] = digit;
+            }
+        }
+
+        let current = self.moves.get(self.next).or(self.moves.last());
+        let status = match (self.empty, current) {
+            (Some(index), _) => empty_stack(index).to_string(),
+            (None, Some(&(how_many, from, to))) => format!(
+                "move {} of {}: {} from {} to {}",
+                (self.next + 1).min(self.moves.len()),
+                self.moves.len(),
+                how_many,
+                from + 1,
+                to + 1
+            ),
+            (None, None) => "no moves".to_owned(),
+        };
+        let stack = current.map_or(0, |&(_, _, to)| to);
+        let height = self.stacks.get(stack).map_or(0, |s| s.len());
+        let focus = (stack as i64 * 4 + 1, (depth - height) as i64);
+        Frame {
+            focus,
+            status,
+            ..Frame::new(cells)
+        }
+    }
+}
+
+fn empty_stack(index: usize) -> AocError {
+    AocError::Solve(format!("Not enough crates on stack {}", index + 1))
+}
+
+fn top_crates(stacks: Vec<Vec<u8>>) -> String {
+    let tops = stacks
+        .into_iter()
+        .filter_map(|s| s.last().copied())
+        .collect::<Vec<u8>>();
+    String::from_utf8(tops).unwrap()
+}
+
+/// A move with the text of the stack numbers, to be checked against the
+/// number of stacks
+fn parse_move(input: &str) -> IResult<'_, (usize, &str, &str)> {
+    tuple((
+        preceded(context("\"move\"", tag("move ")), uint),
+        preceded(context("\"from\"", tag(" from ")), recognize(uint::<usize>)),
+        preceded(context("\"to\"", tag(" to ")), recognize(uint::<usize>)),
+    ))(input)
+}
+
+fn parse_stack_index(
+    input: &str,
+    token: &str,
+    stacks_count: usize,
+) -> std::result::Result<usize, ParseError> {
+    let expected = format!("stack number between 1 and {}", stacks_count);
+    match token.parse::<usize>() {
+        Ok(n) if n >= 1 && n <= stacks_count => Ok(n - 1),
+        _ => Err(ParseError::at(input, token, expected)),
+    }
+}
+
+impl Solution for SupplyStacks {
+    type Input = (Vec<Vec<u8>>, Vec<Move>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines();
+
+        let mut diagram = vec![];
+        loop {
+            match lines.next() {
+                Some("") if !diagram.is_empty() => break,
+                Some(line) => diagram.push(line),
+                None if diagram.is_empty() => {
+                    return Err(ParseError::at_offset(input, 0, "stacks of crates").into())
+                }
+                None => {
+                    return Err(ParseError::after(
+                        input,
+                        diagram[diagram.len() - 1],
+                        "empty line between the stacks and the moves",
+                    )
+                    .into())
+                }
+            }
+        }
+
+        // The stacks are counted by their numbers below the crates, the rows
+        // of crates may have lost the spaces at their ends
+        let (numbers, rows) = diagram.split_last().unwrap();
+        let mut stacks_count = 0;
+        for number in numbers.split_whitespace() {
+            let expected = format!("stack number {}", stacks_count + 1);
+            if number.parse() != Ok(stacks_count + 1) {
+                return Err(ParseError::at(input, number, expected).into());
+            }
+            stacks_count += 1;
+        }
+        if stacks_count == 0 {
+            return Err(ParseError::at(input, numbers, "stack number 1").into());
+        }
+        let mut stacks: Vec<Vec<u8>> = vec![vec![]; stacks_count];
+
+        for line in rows {
+            for (idx, column) in line.as_bytes().chunks(4).enumerate() {
+                let letter = column.get(1).copied().unwrap_or(b' ');
+                if letter.is_ascii_uppercase() {
+                    if idx >= stacks_count {
+                        return Err(
+                            ParseError::at(input, &line[idx * 4 + 1..], "end of line").into()
+                        );
+                    }
+                    stacks[idx].push(letter);
+                }
+            }
+        }
+
+        for stack in &mut stacks {
+            stack.reverse();
+        }
+
+        let mut moves: Vec<Move> = vec![];
+
+        for m in lines {
+            let (how_many, from, to) = parse::line(input, m, parse_move)?;
+            let from = parse_stack_index(input, from, stacks_count)?;
+            let to = parse_stack_index(input, to, stacks_count)?;
+
+            moves.push((how_many, from, to));
+        }
+
+        Ok((stacks, moves))
+    }
+
+    fn part_one(&self, (stacks, moves): &Self::Input) -> Result<Answer> {
+        Ok(Crane::new(stacks, moves, Part::One).run()?.into())
+    }
+
+    fn part_two(&self, (stacks, moves): &Self::Input) -> Result<Answer> {
+        Ok(Crane::new(stacks, moves, Part::Two).run()?.into())
+    }
+
+    fn simulate<'a>(
+        &'a self,
+        (stacks, moves): &'a Self::Input,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        Some(Box::new(Crane::new(stacks, moves, part)))
+    }
+}
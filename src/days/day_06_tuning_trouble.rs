@@ -0,0 +1,178 @@
+//! Solution to an Advent of Code problem, day 06, 2022
+//! https://adventofcode.com/2022/day/06
+
+use crate::stream::Stream;
+use crate::{Answer, AocError, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct TuningTrouble;
+
+fn is_start_of_packet(c: &[u8]) -> bool {
+    if c.len() < 4 {
+        return false;
+    }
+
+    for i in 0..4 {
+        for j in (i + 1)..4 {
+            if c[i] == c[j] {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn is_start_of_message(c: &[u8]) -> bool {
+    if c.len() < 14 {
+        return false;
+    }
+
+    // This is crude. But it still works well at 14 characters.
+    for i in 0..14 {
+        for j in (i + 1)..14 {
+            if c[i] == c[j] {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn no_marker(length: usize) -> AocError {
+    AocError::Solve(format!("No {} distinct characters in a row", length))
+}
+
+fn find_marker(contents: &[u8], length: usize, is_marker: fn(&[u8]) -> bool) -> Result<usize> {
+    (length..=contents.len())
+        .find(|&i| is_marker(&contents[i - length..i]))
+        .ok_or_else(|| no_marker(length))
+}
+
+/// Looks for the marker character by character, so that the datastream
+/// can be as long as it likes
+struct MarkerSearch {
+    length: usize,
+    is_marker: fn(&[u8]) -> bool,
+    /// The last `length` characters
+    recent: Vec<u8>,
+    /// Characters read so far
+    read: usize,
+    marker: Option<usize>,
+    /// Line breaks read so far, the first one ends the datastream
+    line_breaks: usize,
+    /// The start of a line after the datastream that isn't blank
+    leftover: String,
+}
+
+impl MarkerSearch {
+    fn search(&mut self, datastream: &str) {
+        for b in datastream.bytes() {
+            if self.marker.is_some() {
+                return;
+            }
+            if self.recent.len() == self.length {
+                self.recent.remove(0);
+            }
+            self.recent.push(b);
+            self.read += 1;
+            if (self.is_marker)(&self.recent) {
+                self.marker = Some(self.read);
+            }
+        }
+    }
+
+    /// The error of the whole input, with the leftover line as its only line
+    fn leftover_error(&self) -> AocError {
+        let mut e = ParseError::at(&self.leftover, &self.leftover, "end of input");
+        e.line = self.line_breaks + 1;
+        e.into()
+    }
+}
+
+impl Stream for MarkerSearch {
+    fn push(&mut self, text: &str) -> Result<()> {
+        for piece in text.split_inclusive('\n') {
+            let line = piece.strip_suffix('\n');
+            if self.line_breaks == 0 {
+                self.search(line.unwrap_or(piece));
+            } else {
+                self.leftover.push_str(line.unwrap_or(piece));
+            }
+            if line.is_some() {
+                if !self.leftover.is_empty() {
+                    return Err(self.leftover_error());
+                }
+                self.line_breaks += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Answer> {
+        if !self.leftover.is_empty() {
+            return Err(self.leftover_error());
+        }
+        Ok(self.marker.ok_or_else(|| no_marker(self.length))?.into())
+    }
+}
+
+impl Solution for TuningTrouble {
+    type Input = Vec<u8>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        // The datastream is a single line, blank lines after it are harmless
+        let mut lines = input.lines();
+        let stream = lines.next().unwrap_or("");
+        if let Some(line) = lines.find(|line| !line.is_empty()) {
+            return Err(ParseError::at(input, line, "end of input").into());
+        }
+        Ok(stream.as_bytes().to_vec())
+    }
+
+    fn part_one(&self, contents: &Self::Input) -> Result<Answer> {
+        Ok(find_marker(contents, 4, is_start_of_packet)?.into())
+    }
+
+    fn part_two(&self, contents: &Self::Input) -> Result<Answer> {
+        Ok(find_marker(contents, 14, is_start_of_message)?.into())
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        let (length, is_marker): (usize, fn(&[u8]) -> bool) = match part {
+            Part::One => (4, is_start_of_packet),
+            Part::Two => (14, is_start_of_message),
+        };
+        Some(Box::new(MarkerSearch {
+            length,
+            is_marker,
+            recent: Vec::with_capacity(length),
+            read: 0,
+            marker: None,
+            line_breaks: 0,
+            leftover: String::new(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stream, Solver};
+
+    #[test]
+    fn test_leftover() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n\nabc\nd\n";
+        let whole = match TuningTrouble.parse_input(input).unwrap_err() {
+            AocError::Parse(e) => e,
+            e => panic!("Unexpected error: {}", e),
+        };
+        assert_eq!((whole.line, whole.column), (3, 1));
+        assert_eq!(whole.line_text, "abc");
+        match stream::solve(&TuningTrouble, &[Part::One], input.as_bytes(), "x") {
+            Some(Err(AocError::Parse(e))) => assert_eq!(e, whole.in_file("x")),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Solution to an Advent of Code problem, day 07, 2022
+//! https://adventofcode.com/2022/day/07
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{not_line_ending, space1};
+use nom::sequence::tuple;
+use std::collections::HashMap;
+
+use crate::parse::{self, context, uint, IResult};
+use crate::{debug, params, trace, Answer, AocError, Result, Solution};
+
+#[derive(Clone)]
+pub struct NoSpaceLeftOnDevice {
+    pub total_disk_space: u64,
+    /// Free space needed to run the update
+    pub required_free_space: u64,
+}
+
+impl NoSpaceLeftOnDevice {
+    pub const DEFAULT: Self = NoSpaceLeftOnDevice {
+        total_disk_space: 70000000,
+        required_free_space: 30000000,
+    };
+}
+
+#[derive(Debug)]
+pub enum Line {
+    Cd(String),
+    Dir(String),
+    File(String, u64),
+    Ls,
+}
+
+fn parse_cd(input: &str) -> IResult<'_, Line> {
+    let (input, (_, d)) = tuple((tag("$ cd "), not_line_ending))(input)?;
+    Ok((input, Line::Cd(d.to_owned())))
+}
+
+fn parse_dir(input: &str) -> IResult<'_, Line> {
+    let (input, (_, d)) = tuple((tag("dir "), not_line_ending))(input)?;
+    Ok((input, Line::Dir(d.to_owned())))
+}
+
+fn parse_file(input: &str) -> IResult<'_, Line> {
+    let (input, (size, _, d)) = tuple((uint, space1, not_line_ending))(input)?;
+    Ok((input, Line::File(d.to_owned(), size)))
+}
+
+fn parse_ls(input: &str) -> IResult<'_, Line> {
+    let (input, _) = tag("$ ls")(input)?;
+    Ok((input, Line::Ls))
+}
+
+fn parse_line(input: &str) -> IResult<'_, Line> {
+    context(
+        "command (\"$ cd dir\", \"$ ls\") or listing",
+        alt((parse_cd, parse_dir, parse_file, parse_ls)),
+    )(input)
+}
+
+fn dir_sizes(lines: &[Line]) -> HashMap<String, u64> {
+    let mut working_dir: Vec<&str> = vec![""];
+    let mut dir_sizes: HashMap<String, u64> = HashMap::new();
+    for line in lines {
+        match line {
+            Line::Cd(d) => match d.as_str() {
+                "/" => {
+                    working_dir.clear();
+                    working_dir.push("");
+                }
+                ".." => {
+                    working_dir.pop();
+                }
+                s => {
+                    working_dir.push(s);
+                }
+            },
+            Line::Dir(_d) => {} // Doesn't contribute to the result
+            Line::File(_filename, size) => {
+                let mut path = "".to_owned();
+                for d in &working_dir {
+                    path = path + d + "/";
+                    *dir_sizes.entry(path.to_string()).or_insert(0) += size;
+                }
+            }
+            Line::Ls => {}
+        }
+    }
+
+    debug!("{:?}", dir_sizes);
+
+    dir_sizes
+}
+
+impl Solution for NoSpaceLeftOnDevice {
+    type Input = Vec<Line>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = parse::lines(input, parse_line)?;
+        trace!("{:?}", lines);
+        Ok(lines)
+    }
+
+    fn part_one(&self, lines: &Self::Input) -> Result<Answer> {
+        let sum_p1: u64 = dir_sizes(lines).values().filter(|&&v| v <= 100000).sum();
+        Ok(sum_p1.into())
+    }
+
+    fn part_two(&self, lines: &Self::Input) -> Result<Answer> {
+        let dir_sizes = dir_sizes(lines);
+
+        let used_space: u64 = *dir_sizes.get("/").unwrap_or(&0);
+
+        let amount_to_delete =
+            (self.required_free_space + used_space).saturating_sub(self.total_disk_space);
+        let smallest_d: u64 = *dir_sizes
+            .values()
+            .filter(|&&v| v >= amount_to_delete)
+            .min()
+            .ok_or_else(|| AocError::Solve("No directory is large enough".to_owned()))?;
+        Ok(smallest_d.into())
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("total_disk_space", self.total_disk_space.to_string()),
+            ("required_free_space", self.required_free_space.to_string()),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "total_disk_space" => self.total_disk_space = params::value(name, value)?,
+            "required_free_space" => self.required_free_space = params::value(name, value)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+}
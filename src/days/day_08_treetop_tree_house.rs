@@ -0,0 +1,68 @@
+//! Solution to an Advent of Code problem, day 08, 2022
+//! https://adventofcode.com/2022/day/08
+use crate::grid::{Grid, Pos, DIRECTIONS_4};
+use crate::render::Picture;
+use crate::{Answer, Result, Solution};
+
+#[derive(Clone)]
+pub struct TreetopTreeHouse;
+
+/// Number of trees that can be seen from the tree at `pos` in `direction`,
+/// and whether the view reaches the edge of the map.
+fn viewing_distance(map: &Grid<u32>, pos: Pos, direction: (i64, i64)) -> (u64, bool) {
+    let mut distance = 0;
+    for tree in map.ray(pos, direction) {
+        distance += 1;
+        if map[tree] >= map[pos] {
+            return (distance, false);
+        }
+    }
+    (distance, true)
+}
+
+fn scenic_score(map: &Grid<u32>, pos: Pos) -> u64 {
+    DIRECTIONS_4
+        .into_iter()
+        .map(|direction| viewing_distance(map, pos, direction).0)
+        .product()
+}
+
+impl Solution for TreetopTreeHouse {
+    type Input = Grid<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(Grid::parse(input, |c| c.to_digit(10), "digit")?)
+    }
+
+    fn part_one(&self, map: &Self::Input) -> Result<Answer> {
+        let visible_count = map
+            .positions()
+            .filter(|&pos| {
+                DIRECTIONS_4
+                    .into_iter()
+                    .any(|direction| viewing_distance(map, pos, direction).1)
+            })
+            .count();
+        Ok(visible_count.into())
+    }
+
+    fn part_two(&self, map: &Self::Input) -> Result<Answer> {
+        let max_scenic_score = map
+            .positions()
+            .map(|pos| scenic_score(map, pos))
+            .max()
+            .unwrap_or(0);
+        Ok(max_scenic_score.into())
+    }
+
+    fn render(&self, map: &Self::Input) -> Result<Vec<(&'static str, Picture)>> {
+        let heights = map.map(|&height| height as f64 / 9.0);
+        let mut scores = Grid::new(map.width(), map.height(), 0.0);
+        for pos in map.positions() {
+            scores[pos] = scenic_score(map, pos) as f64;
+        }
+        let max_score = scores.iter().map(|(_, &score)| score).fold(1.0, f64::max);
+        let scores = scores.map(|&score| score / max_score);
+        Ok(vec![("heights", heights), ("scenic_scores", scores)])
+    }
+}
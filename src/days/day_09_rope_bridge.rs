@@ -0,0 +1,217 @@
+//! Solution to an Advent of Code problem, day 09, 2022
+//! https://adventofcode.com/2022/day/09
+use std::collections::HashSet;
+
+use nom::branch::alt;
+use nom::character::complete::char;
+use nom::combinator::value;
+use nom::sequence::separated_pair;
+
+use crate::animate::{Frame, Simulation};
+use crate::grid::Grid;
+use crate::parse::{self, context, uint, IResult};
+use crate::stream::{self, LineStream, Stream};
+use crate::{Answer, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct RopeBridge;
+
+fn next_following_knot_position(following: (i64, i64), leading: (i64, i64)) -> (i64, i64) {
+    // Touching, including diagonally and overlapping
+    if (following.0 - leading.0).abs() <= 1 && (following.1 - leading.1).abs() <= 1 {
+        return following;
+    }
+
+    if following.0 == leading.0 && following.1 + 2 == leading.1 {
+        return (following.0, following.1 + 1);
+    }
+    if following.0 == leading.0 && following.1 - 2 == leading.1 {
+        return (following.0, following.1 - 1);
+    }
+    if following.1 == leading.1 && following.0 + 2 == leading.0 {
+        return (following.0 + 1, following.1);
+    }
+    if following.1 == leading.1 && following.0 - 2 == leading.0 {
+        return (following.0 - 1, following.1);
+    }
+
+    if leading.0 > following.0 && leading.1 > following.1 {
+        return (following.0 + 1, following.1 + 1);
+    }
+    if leading.0 < following.0 && leading.1 < following.1 {
+        return (following.0 - 1, following.1 - 1);
+    }
+    if leading.0 > following.0 && leading.1 < following.1 {
+        return (following.0 + 1, following.1 - 1);
+    }
+    if leading.0 < following.0 && leading.1 > following.1 {
+        return (following.0 - 1, following.1 + 1);
+    }
+
+    panic!("Shouldn't get here.");
+}
+
+/// Direction of the head movement and the number of steps
+type Motion = ((i64, i64), i64);
+
+fn parse_motion(input: &str) -> IResult<'_, Motion> {
+    let direction = alt((
+        value((1, 0), char('R')),
+        value((-1, 0), char('L')),
+        value((0, 1), char('U')),
+        value((0, -1), char('D')),
+    ));
+    separated_pair(
+        context("direction (R, L, U or D)", direction),
+        char(' '),
+        uint,
+    )(input)
+}
+
+/// The rope following the motions of its head, one step at a time
+struct Rope<'a> {
+    knot_positions: Vec<(i64, i64)>,
+    last_knot_positions: HashSet<(i64, i64)>,
+    motions: &'a [Motion],
+    /// Index of the current motion
+    next: usize,
+    /// Steps of the current motion taken so far
+    moved: i64,
+}
+
+impl<'a> Rope<'a> {
+    fn new(n_knots: usize, motions: &'a [Motion]) -> Rope<'a> {
+        Rope {
+            knot_positions: vec![(0, 0); n_knots],
+            last_knot_positions: HashSet::from([(0, 0)]),
+            motions,
+            next: 0,
+            moved: 0,
+        }
+    }
+
+    fn move_head(&mut self, (dx, dy): (i64, i64)) {
+        let knots = &mut self.knot_positions;
+        knots[0] = (knots[0].0 + dx, knots[0].1 + dy);
+        for i in 1..knots.len() {
+            knots[i] = next_following_knot_position(knots[i], knots[i - 1]);
+        }
+        self.last_knot_positions.insert(knots[knots.len() - 1]);
+    }
+}
+
+/// Without motions of its own, the rope follows the lines as they are read
+impl LineStream for Rope<'_> {
+    fn line(&mut self, line: &str) -> Result<()> {
+        let (direction, how_many) = parse::line(line, line, parse_motion)?;
+        for _ in 0..how_many {
+            self.move_head(direction);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Answer> {
+        Ok(self.last_knot_positions.len().into())
+    }
+}
+
+impl Simulation for Rope<'_> {
+    fn step(&mut self) -> bool {
+        // Motions of no steps at all
+        while self.motions.get(self.next).is_some_and(|&(_, n)| n == 0) {
+            self.next += 1;
+        }
+        let Some(&(direction, how_many)) = self.motions.get(self.next) else {
+            return false;
+        };
+        self.move_head(direction);
+
+        self.moved += 1;
+        if self.moved == how_many {
+            self.next += 1;
+            self.moved = 0;
+        }
+        true
+    }
+
+    /// The knots and the positions visited by the last one, as drawn in the
+    /// puzzle
+    fn frame(&self) -> Frame {
+        let positions = self.last_knot_positions.iter().chain(&self.knot_positions);
+        let (min_x, max_x, min_y, max_y) =
+            positions.fold((0, 0, 0, 0), |(min_x, max_x, min_y, max_y), &(x, y)| {
+                (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+            });
+        // Up is y + 1
+        let cell = |(x, y): (i64, i64)| ((x - min_x) as usize, (max_y - y) as usize);
+        let width = (max_x - min_x + 1) as usize;
+        let mut cells = Grid::new(width, (max_y - min_y + 1) as usize, '.');
+        for &position in &self.last_knot_positions {
+            cells[cell(position)] = '#';
+        }
+        cells[cell((0, 0))] = 's';
+        let knots = self.knot_positions.len();
+        for (i, &position) in self.knot_positions.iter().enumerate().rev() {
+            cells[cell(position)] = match i {
+                0 => 'H',
+                _ if knots == 2 => 'T',
+                _ => char::from_digit(i as u32, 36).unwrap_or('*'),
+            };
+        }
+
+        let (head_x, head_y) = self.knot_positions[0];
+        Frame {
+            origin: (min_x, -max_y),
+            focus: (head_x, -head_y),
+            status: format!(
+                "motion {} of {}, {} positions visited by the tail",
+                (self.next + 1).min(self.motions.len()),
+                self.motions.len(),
+                self.last_knot_positions.len()
+            ),
+            ..Frame::new(cells)
+        }
+    }
+}
+
+/// The number of knots of the rope
+fn knots(part: Part) -> usize {
+    match part {
+        Part::One => 2,
+        Part::Two => 10,
+    }
+}
+
+fn count_distinct_last_knot_positions(n_knots: usize, motions: &[Motion]) -> usize {
+    let mut rope = Rope::new(n_knots, motions);
+    while rope.step() {}
+    rope.last_knot_positions.len()
+}
+
+impl Solution for RopeBridge {
+    type Input = Vec<Motion>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_motion)?)
+    }
+
+    fn part_one(&self, motions: &Self::Input) -> Result<Answer> {
+        Ok(count_distinct_last_knot_positions(knots(Part::One), motions).into())
+    }
+
+    fn part_two(&self, motions: &Self::Input) -> Result<Answer> {
+        Ok(count_distinct_last_knot_positions(knots(Part::Two), motions).into())
+    }
+
+    fn simulate<'a>(
+        &'a self,
+        motions: &'a Self::Input,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        Some(Box::new(Rope::new(knots(part), motions)))
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        Some(stream::lines(Rope::new(knots(part), &[])))
+    }
+}
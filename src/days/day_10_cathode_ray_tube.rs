@@ -0,0 +1,222 @@
+//! Solution to an Advent of Code problem, day 10, 2022
+//! https://adventofcode.com/2022/day/10
+use std::collections::VecDeque;
+use std::slice;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{map, value};
+use nom::sequence::preceded;
+
+use crate::animate::{Frame, Simulation};
+use crate::grid::Grid;
+use crate::parse::{self, context, int, IResult};
+use crate::render::Picture;
+use crate::stream::{self, LineStream, Stream};
+use crate::{debug, trace, Answer, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct CathodeRayTube;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+fn parse_instruction(input: &str) -> IResult<'_, Instruction> {
+    context(
+        "instruction (noop or addx)",
+        alt((
+            value(Instruction::Noop, tag("noop")),
+            map(preceded(tag("addx "), int), Instruction::Addx),
+        )),
+    )(input)
+}
+
+/// The CPU running the program one cycle at a time, and the CRT it drives
+struct Cpu<'a> {
+    instructions: slice::Iter<'a, Instruction>,
+    pipeline: VecDeque<i64>,
+    x: i64,
+    cycle: i64,
+    signal_strengths: i64,
+    picture: Vec<Vec<bool>>,
+    halted: bool,
+}
+
+impl<'a> Cpu<'a> {
+    fn new(instructions: &'a [Instruction]) -> Cpu<'a> {
+        Cpu {
+            instructions: instructions.iter(),
+            pipeline: VecDeque::new(),
+            x: 1,
+            cycle: 1,
+            signal_strengths: 0,
+            picture: vec![vec![false; 40]; 6],
+            halted: false,
+        }
+    }
+
+    /// Runs a cycle that starts `instruction`, if any
+    fn tick(&mut self, instruction: Option<&Instruction>) {
+        let (cycle, x) = (self.cycle, self.x);
+
+        if cycle == 20
+            || cycle == 60
+            || cycle == 100
+            || cycle == 140
+            || cycle == 180
+            || cycle == 220
+        {
+            self.signal_strengths += cycle * x;
+            debug!("Signal strength at cycle {}: {} * {}", cycle, cycle, x);
+        }
+        trace!("{}: x = {}", cycle, x);
+
+        let py = (cycle - 1) / 40;
+        let px = (cycle - 1) % 40;
+        if x - 1 <= (px) && (px) <= x + 1 && py < 6 {
+            self.picture[py as usize][px as usize] = true;
+        }
+
+        match instruction {
+            Some(Instruction::Addx(factor)) => {
+                self.pipeline.push_front(0);
+                self.pipeline.push_front(*factor);
+            }
+            Some(Instruction::Noop) => {
+                self.pipeline.push_front(0);
+            }
+            None => {}
+        }
+        if let Some(factor) = self.pipeline.pop_back() {
+            self.x += factor;
+        }
+        self.cycle += 1;
+
+        self.halted = instruction.is_none() && self.pipeline.is_empty() && self.cycle > 220;
+    }
+}
+
+impl Simulation for Cpu<'_> {
+    fn step(&mut self) -> bool {
+        if self.halted {
+            return false;
+        }
+        let instruction = self.instructions.next();
+        self.tick(instruction);
+        true
+    }
+
+    /// The CRT as drawn so far, with the sprite below it
+    fn frame(&self) -> Frame {
+        let mut cells = Grid::new(40, 8, ' ');
+        for (y, row) in self.picture.iter().enumerate() {
+            for (x, &lit) in row.iter().enumerate() {
+                if ((y * 40 + x) as i64) < self.cycle - 1 {
+                    cells[(x, y)] = if lit { '#' } else { '.' };
+                }
+            }
+        }
+        for dx in -1..=1 {
+            if let Some(pos) = cells.pos(self.x + dx, 7) {
+                cells[pos] = '#';
+            }
+        }
+        let beam = (self.cycle - 1).min(239);
+        Frame {
+            focus: (beam % 40, beam / 40),
+            status: format!(
+                "cycle {}  x = {}  signal strengths {}",
+                self.cycle, self.x, self.signal_strengths
+            ),
+            ..Frame::new(cells)
+        }
+    }
+}
+
+/// Runs the program and returns the sum of signal strengths and the picture
+/// drawn on the CRT.
+fn run(instructions: &[Instruction]) -> (i64, Vec<Vec<bool>>) {
+    let mut cpu = Cpu::new(instructions);
+    while cpu.step() {}
+    (cpu.signal_strengths, cpu.picture)
+}
+
+fn picture_text(picture: &[Vec<bool>]) -> String {
+    picture
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The CPU running the program as it is read
+struct StreamedCpu {
+    cpu: Cpu<'static>,
+    part: Part,
+}
+
+impl LineStream for StreamedCpu {
+    fn line(&mut self, line: &str) -> Result<()> {
+        let instruction = parse::line(line, line, parse_instruction)?;
+        self.cpu.tick(Some(&instruction));
+        // The cycles of the instructions read so far are run, so that the
+        // pipeline doesn't grow
+        while self.cpu.pipeline.len() > 1 {
+            self.cpu.tick(None);
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Answer> {
+        while self.cpu.step() {}
+        Ok(match self.part {
+            Part::One => self.cpu.signal_strengths.into(),
+            Part::Two => picture_text(&self.cpu.picture).into(),
+        })
+    }
+}
+
+impl Solution for CathodeRayTube {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_instruction)?)
+    }
+
+    fn part_one(&self, instructions: &Self::Input) -> Result<Answer> {
+        Ok(run(instructions).0.into())
+    }
+
+    fn part_two(&self, instructions: &Self::Input) -> Result<Answer> {
+        let (_, picture) = run(instructions);
+        Ok(picture_text(&picture).into())
+    }
+
+    fn render(&self, instructions: &Self::Input) -> Result<Vec<(&'static str, Picture)>> {
+        let (_, picture) = run(instructions);
+        let crt = Grid::from_rows(picture).map(|&lit| if lit { 1.0 } else { 0.0 });
+        Ok(vec![("crt", crt)])
+    }
+
+    fn simulate<'a>(
+        &'a self,
+        instructions: &'a Self::Input,
+        _part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        Some(Box::new(Cpu::new(instructions)))
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        Some(stream::lines(StreamedCpu {
+            cpu: Cpu::new(&[]),
+            part,
+        }))
+    }
+}
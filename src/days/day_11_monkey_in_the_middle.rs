@@ -0,0 +1,530 @@
+//! Solution to an Advent of Code problem, day 11, 2022
+//! https://adventofcode.com/2022/day/11
+use std::cmp::Reverse;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, space0};
+use nom::combinator::{consumed, map, opt, value, verify};
+use nom::sequence::{preceded, terminated, tuple};
+
+use crate::animate::{Frame, Simulation};
+use crate::parse::{self, comma_list, context, labelled, uint, IResult};
+use crate::{debug, params, Answer, AocError, ParseError, Part, Result, Solution};
+
+#[derive(Clone)]
+pub struct MonkeyInTheMiddle {
+    pub rounds_part_one: u64,
+    pub rounds_part_two: u64,
+}
+
+impl MonkeyInTheMiddle {
+    pub const DEFAULT: Self = MonkeyInTheMiddle {
+        rounds_part_one: 20,
+        rounds_part_two: 10000,
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Operand {
+    Old,
+    Number(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Test {
+    DivisibleBy(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Operation {
+    operator: Operator,
+    left: Operand,
+    right: Operand,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Action {
+    ThrowToMonkey(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    test: Test,
+    action_if_true: Action,
+    action_if_false: Action,
+    inspections_count: u64,
+}
+
+// Input example:
+//   Starting items: 74, 60, 97
+fn parse_items(input: &str) -> IResult<'_, Vec<u64>> {
+    labelled(
+        "Starting items:",
+        map(opt(comma_list(uint)), Option::unwrap_or_default),
+    )(input)
+}
+
+// Input examples:
+// 1234
+// old
+fn parse_operand(input: &str) -> IResult<'_, Operand> {
+    context(
+        "operand (old or a number)",
+        alt((value(Operand::Old, tag("old")), map(uint, Operand::Number))),
+    )(input)
+}
+
+// Input examples:
+// +
+// *
+fn parse_operator(input: &str) -> IResult<'_, Operator> {
+    context(
+        "operator (+ or *)",
+        alt((
+            value(Operator::Add, char('+')),
+            value(Operator::Multiply, char('*')),
+        )),
+    )(input)
+}
+
+// Input examples:
+//   Operation: new = old * old
+//   Operation: new = old + 6
+fn parse_operation(input: &str) -> IResult<'_, Operation> {
+    let (input, (left, _, operator, _, right)) = labelled(
+        "Operation:",
+        preceded(
+            tag("new = "),
+            tuple((parse_operand, space0, parse_operator, space0, parse_operand)),
+        ),
+    )(input)?;
+    Ok((
+        input,
+        Operation {
+            operator,
+            left,
+            right,
+        },
+    ))
+}
+
+// Input example:
+//    Test: divisible by 19
+fn parse_test(input: &str) -> IResult<'_, Test> {
+    map(
+        labelled(
+            "Test:",
+            preceded(
+                tag("divisible by "),
+                context("divisor above 0", verify(uint, |&divisor| divisor > 0)),
+            ),
+        ),
+        Test::DivisibleBy,
+    )(input)
+}
+
+// Input example:
+//  throw to monkey 2
+fn parse_action(input: &str) -> IResult<'_, Action> {
+    map(
+        preceded(tag("throw to monkey "), uint),
+        Action::ThrowToMonkey,
+    )(input)
+}
+
+// Input example:
+// Monkey 2:
+//   Starting items: 79, 60, 97
+//   Operation: new = old * old
+//   Test: divisible by 13
+//     If true: throw to monkey 1
+//     If false: throw to monkey 3
+//
+// Monkeys are separated by an empty line
+fn parse_monkeys(input: &str) -> std::result::Result<Vec<Monkey>, ParseError> {
+    let mut lines = input.lines().filter(|line| !line.is_empty());
+    let mut monkeys = vec![];
+    // Monkeys thrown to, with the text of the action, to be checked against
+    // the number of monkeys
+    let mut targets = vec![];
+    while let Some(header) = lines.next() {
+        parse::line(
+            input,
+            header,
+            labelled("Monkey", terminated(uint::<u64>, char(':'))),
+        )?;
+        let mut next = |expected| {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::at_offset(input, input.len(), expected))
+        };
+        let items = parse::line(input, next("Starting items:")?, parse_items)?;
+        let operation = parse::line(input, next("Operation:")?, parse_operation)?;
+        let test = parse::line(input, next("Test:")?, parse_test)?;
+        let if_true = labelled("If true:", consumed(parse_action));
+        let (at, action_if_true) = parse::line(input, next("If true:")?, if_true)?;
+        let Action::ThrowToMonkey(target) = action_if_true;
+        targets.push((at, target));
+        let if_false = labelled("If false:", consumed(parse_action));
+        let (at, action_if_false) = parse::line(input, next("If false:")?, if_false)?;
+        let Action::ThrowToMonkey(target) = action_if_false;
+        targets.push((at, target));
+        monkeys.push(Monkey {
+            items,
+            operation,
+            test,
+            action_if_true,
+            action_if_false,
+            inspections_count: 0,
+        });
+    }
+    if monkeys.len() < 2 {
+        return Err(ParseError::at_offset(
+            input,
+            input.len(),
+            "at least two monkeys",
+        ));
+    }
+    for (at, target) in targets {
+        if target as usize >= monkeys.len() {
+            let expected = format!("a monkey from 0 to {}", monkeys.len() - 1);
+            return Err(ParseError::at(input, at, expected));
+        }
+    }
+    Ok(monkeys)
+}
+
+/// The monkeys throwing items for a number of rounds, one monkey's turn at a
+/// time
+struct Troop<F> {
+    monkeys: Vec<Monkey>,
+    worry_manager: F,
+    rounds: u64,
+    round: u64,
+    /// Index of the monkey whose turn is next
+    turn: usize,
+    /// The monkey whose operation overflowed a worry level, which ends the
+    /// rounds
+    overflowed: Option<usize>,
+}
+
+impl<F: FnMut(u64) -> u64> Troop<F> {
+    fn new(rounds: u64, worry_manager: F, initial_state: &[Monkey]) -> Troop<F> {
+        Troop {
+            monkeys: initial_state.to_vec(),
+            worry_manager,
+            rounds,
+            round: 0,
+            turn: 0,
+            overflowed: None,
+        }
+    }
+
+    fn run(mut self) -> Result<u64> {
+        while self.step() {}
+        match self.overflowed {
+            Some(index) => Err(worry_overflow(index)),
+            None => self.monkey_business(),
+        }
+    }
+
+    fn monkey_business(&self) -> Result<u64> {
+        let mut inspections_counts = self
+            .monkeys
+            .iter()
+            .map(|m| m.inspections_count)
+            .collect::<Vec<u64>>();
+        inspections_counts.sort_unstable_by_key(|&v| Reverse(v));
+        inspections_counts[0]
+            .checked_mul(inspections_counts[1])
+            .ok_or_else(|| AocError::Solve("The monkey business overflows".to_owned()))
+    }
+}
+
+impl<F: FnMut(u64) -> u64> Simulation for Troop<F> {
+    fn step(&mut self) -> bool {
+        if self.round == self.rounds || self.overflowed.is_some() {
+            return false;
+        }
+        let monkeys = &mut self.monkeys;
+        let monkey_index = self.turn;
+        let operation = &monkeys[monkey_index].operation;
+        let worry_levels: Option<Vec<u64>> = monkeys[monkey_index]
+            .items
+            .iter()
+            .map(|&original_worry_level| {
+                let left = match operation.left {
+                    Operand::Old => original_worry_level,
+                    Operand::Number(n) => n,
+                };
+                let right = match operation.right {
+                    Operand::Old => original_worry_level,
+                    Operand::Number(n) => n,
+                };
+                match operation.operator {
+                    Operator::Add => left.checked_add(right),
+                    Operator::Multiply => left.checked_mul(right),
+                }
+            })
+            .collect();
+        let Some(worry_levels) = worry_levels else {
+            self.overflowed = Some(monkey_index);
+            return false;
+        };
+
+        for worry_level in worry_levels {
+            let worry_level = (self.worry_manager)(worry_level);
+
+            let Test::DivisibleBy(divisor) = monkeys[monkey_index].test;
+            let Action::ThrowToMonkey(target_if_true) = monkeys[monkey_index].action_if_true;
+            let Action::ThrowToMonkey(target_if_false) = monkeys[monkey_index].action_if_false;
+
+            if worry_level.is_multiple_of(divisor) {
+                monkeys[target_if_true as usize].items.push(worry_level);
+            } else {
+                monkeys[target_if_false as usize].items.push(worry_level);
+            }
+        }
+
+        monkeys[monkey_index].inspections_count += monkeys[monkey_index].items.len() as u64;
+        monkeys[monkey_index].items.clear();
+
+        self.turn += 1;
+        if self.turn == monkeys.len() {
+            self.turn = 0;
+            self.round += 1;
+        }
+        true
+    }
+
+    /// The items each monkey holds, with the one whose turn is next marked
+    fn frame(&self) -> Frame {
+        let lines: Vec<String> = self
+            .monkeys
+            .iter()
+            .enumerate()
+            .map(|(i, monkey)| {
+                let items: Vec<String> = monkey.items.iter().map(|w| w.to_string()).collect();
+                format!(
+                    "{} Monkey {} ({} inspections): {}",
+                    if i == self.turn { '>' } else { ' ' },
+                    i,
+                    monkey.inspections_count,
+                    items.join(", ")
+                )
+            })
+            .collect();
+        let status = match self.overflowed {
+            Some(index) => worry_overflow(index).to_string(),
+            None => format!(
+                "round {} of {}",
+                (self.round + 1).min(self.rounds),
+                self.rounds
+            ),
+        };
+        Frame {
+            focus: (0, self.turn as i64),
+            status,
+            ..Frame::text(&lines)
+        }
+    }
+}
+
+fn worry_overflow(index: usize) -> AocError {
+    AocError::Solve(format!("Monkey {} overflows a worry level", index))
+}
+
+fn measure_monkey_business<F: FnMut(u64) -> u64>(
+    rounds: u64,
+    worry_manager: F,
+    initial_state: &[Monkey],
+) -> Result<u64> {
+    Troop::new(rounds, worry_manager, initial_state).run()
+}
+
+/// A common multiple of the divisors, which keeps the tests the same when
+/// the worry levels are taken modulo it. None if it doesn't fit, then the
+/// worry levels grow until they overflow.
+fn least_common_multiple(monkeys: &[Monkey]) -> Option<u64> {
+    monkeys.iter().try_fold(1u64, |product, m| match m.test {
+        Test::DivisibleBy(n) => product.checked_mul(n),
+    })
+}
+
+/// Takes the worry levels modulo the common multiple, if there is one
+fn keep_worry_levels_manageable(least_common_multiple: Option<u64>) -> impl Fn(u64) -> u64 {
+    move |w| least_common_multiple.map_or(w, |m| w % m)
+}
+
+impl Solution for MonkeyInTheMiddle {
+    type Input = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let monkeys = parse_monkeys(input)?;
+        debug!("{:?}", monkeys);
+
+        Ok(monkeys)
+    }
+
+    fn part_one(&self, monkeys: &Self::Input) -> Result<Answer> {
+        Ok(measure_monkey_business(self.rounds_part_one, |w| w / 3, monkeys)?.into())
+    }
+
+    fn part_two(&self, monkeys: &Self::Input) -> Result<Answer> {
+        let worry_manager = keep_worry_levels_manageable(least_common_multiple(monkeys));
+        Ok(measure_monkey_business(self.rounds_part_two, worry_manager, monkeys)?.into())
+    }
+
+    fn simulate<'a>(
+        &'a self,
+        monkeys: &'a Self::Input,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        Some(match part {
+            Part::One => Box::new(Troop::new(self.rounds_part_one, |w| w / 3, monkeys)),
+            Part::Two => Box::new(Troop::new(
+                self.rounds_part_two,
+                keep_worry_levels_manageable(least_common_multiple(monkeys)),
+                monkeys,
+            )),
+        })
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rounds_part_one", self.rounds_part_one.to_string()),
+            ("rounds_part_two", self.rounds_part_two.to_string()),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "rounds_part_one" => self.rounds_part_one = params::value(name, value)?,
+            "rounds_part_two" => self.rounds_part_two = params::value(name, value)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_items() {
+        assert_eq!(
+            parse_items("  Starting items: 54, 65, 75, 74"),
+            Ok(("", vec![54, 65, 75, 74]))
+        );
+    }
+
+    #[test]
+    fn test_parse_operation() {
+        assert_eq!(
+            parse_operation("  Operation: new = old + 6"),
+            Ok((
+                "",
+                Operation {
+                    operator: Operator::Add,
+                    left: Operand::Old,
+                    right: Operand::Number(6)
+                }
+            ))
+        );
+        assert_eq!(
+            parse_operation("  Operation: new = old * old"),
+            Ok((
+                "",
+                Operation {
+                    operator: Operator::Multiply,
+                    left: Operand::Old,
+                    right: Operand::Old
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_test() {
+        assert_eq!(
+            parse_test("  Test: divisible by 13"),
+            Ok(("", Test::DivisibleBy(13)))
+        );
+    }
+
+    const TWO_MONKEYS: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items:
+  Operation: new = old + old
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+
+    #[test]
+    fn test_parse_monkeys() {
+        let monkeys = parse_monkeys(TWO_MONKEYS).unwrap();
+        assert_eq!(monkeys[1].items, vec![]);
+
+        let input = TWO_MONKEYS.replace("new = old + old", "new = old / old");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!((e.line, e.column), (10, 24));
+        assert_eq!(e.expected, "operator (+ or *)");
+
+        let input = TWO_MONKEYS.replace("\n    If false: throw to monkey 0\n", "\n");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!(e.expected, "If false:");
+
+        let input = TWO_MONKEYS.replace("divisible by 19", "divisible by 0");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!((e.line, e.column), (11, 22));
+        assert_eq!(e.expected, "divisor above 0");
+
+        let input =
+            TWO_MONKEYS.replace("If false: throw to monkey 1", "If false: throw to monkey 2");
+        let e = parse_monkeys(&input).unwrap_err();
+        assert_eq!((e.line, e.column), (6, 15));
+        assert_eq!(e.expected, "a monkey from 0 to 1");
+    }
+
+    #[test]
+    fn test_worry_overflow() {
+        let day = MonkeyInTheMiddle {
+            rounds_part_one: 100,
+            ..MonkeyInTheMiddle::DEFAULT
+        };
+        let monkeys = day.parse(TWO_MONKEYS).unwrap();
+        match day.part_one(&monkeys) {
+            Err(AocError::Solve(message)) => {
+                assert_eq!(message, "Monkey 0 overflows a worry level")
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(day.part_two(&monkeys).is_ok());
+    }
+
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(
+            parse_action("throw to monkey 0"),
+            Ok(("", Action::ThrowToMonkey(0)))
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Solution to an Advent of Code problem, day 12, 2022
+//! https://adventofcode.com/2022/day/12
+use crate::grid::{Grid, Pos};
+use crate::render::Picture;
+use crate::search;
+use crate::{params, Answer, AocError, ParseError, Result, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Searches up from the start, or one search from all the `a`s at once
+    MultiSource,
+    /// Searches all of the map down from the end, then picks the start, or
+    /// the nearest `a`
+    Reverse,
+}
+
+const STRATEGIES: [(&str, Strategy); 2] = [
+    ("multi_source", Strategy::MultiSource),
+    ("reverse", Strategy::Reverse),
+];
+
+#[derive(Clone)]
+pub struct HillClimbingAlgorithm {
+    pub strategy: Strategy,
+}
+
+impl HillClimbingAlgorithm {
+    pub const DEFAULT: Self = HillClimbingAlgorithm {
+        strategy: Strategy::MultiSource,
+    };
+}
+
+pub struct HeightMap {
+    map: Grid<u8>,
+    start: Pos,
+    end: Pos,
+}
+
+/// Moves to a neighbour at most one step higher
+fn climbs(map: &Grid<u8>) -> impl Fn(Pos) -> Vec<Pos> + '_ {
+    move |from| {
+        map.neighbours4(from)
+            .filter(|&to| map[to] <= map[from] + 1)
+            .collect()
+    }
+}
+
+/// Moves back to a neighbour at most one step lower, the moves of [`climbs`]
+/// the other way around
+fn descends(map: &Grid<u8>) -> impl Fn(Pos) -> Vec<Pos> + '_ {
+    move |from| {
+        map.neighbours4(from)
+            .filter(|&to| map[from] <= map[to] + 1)
+            .collect()
+    }
+}
+
+/// Steps from the end down to the nearest position that is `start`
+fn steps_down(height_map: &HeightMap, start: impl Fn(Pos) -> bool) -> Option<u64> {
+    let search = search::bfs(&descends(&height_map.map), height_map.end);
+    search
+        .distances
+        .iter()
+        .filter(|(&pos, _)| start(pos))
+        .map(|(_, &steps)| steps)
+        .min()
+}
+
+fn reachable(steps: Option<u64>) -> Result<Answer> {
+    match steps {
+        Some(steps) => Ok(steps.into()),
+        None => Err(AocError::Solve("The end is not reachable".to_owned())),
+    }
+}
+
+impl Solution for HillClimbingAlgorithm {
+    type Input = HeightMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut map = Grid::parse(
+            input.trim_end(),
+            |c| match c {
+                'a'..='z' | 'S' | 'E' => Some(c as u8),
+                _ => None,
+            },
+            "elevation (a-z)",
+        )?;
+
+        let find = |mark: u8, expected: &str| -> std::result::Result<Pos, ParseError> {
+            let mut positions = map.iter().filter(|(_, &c)| c == mark).map(|(pos, _)| pos);
+            match (positions.next(), positions.next()) {
+                (Some(pos), None) => Ok(pos),
+                (None, _) => Err(ParseError::at_offset(input, input.len(), expected)),
+                (Some(_), Some((x, y))) => {
+                    let line = input.lines().nth(y).unwrap_or("");
+                    let expected = format!("a single {}", expected);
+                    Err(ParseError::at(input, &line[x..], expected))
+                }
+            }
+        };
+        let start = find(b'S', "start (S)")?;
+        let end = find(b'E', "end (E)")?;
+
+        map[start] = b'a';
+        map[end] = b'z';
+
+        Ok(HeightMap { map, start, end })
+    }
+
+    fn part_one(&self, height_map: &Self::Input) -> Result<Answer> {
+        if self.strategy == Strategy::Reverse {
+            return reachable(steps_down(height_map, |pos| pos == height_map.start));
+        }
+        let search = search::bfs(&climbs(&height_map.map), height_map.start);
+        reachable(search.distance(height_map.end))
+    }
+
+    fn part_two(&self, height_map: &Self::Input) -> Result<Answer> {
+        let map = &height_map.map;
+        if self.strategy == Strategy::Reverse {
+            return reachable(steps_down(height_map, |pos| map[pos] == b'a'));
+        }
+        let starts = map
+            .iter()
+            .filter(|(_, &elevation)| elevation == b'a')
+            .map(|(start, _)| start);
+        let search = search::bfs_multi(&climbs(map), starts);
+        reachable(search.distance(height_map.end))
+    }
+
+    fn render(&self, height_map: &Self::Input) -> Result<Vec<(&'static str, Picture)>> {
+        let heights = height_map.map.map(|&e| (e - b'a') as f64 / 25.0);
+
+        // The shortest path of part 1 on top of the dimmed heights
+        let mut path = heights.map(|&height| height * 0.75);
+        let search = search::bfs(&climbs(&height_map.map), height_map.start);
+        for pos in search.path(height_map.end).unwrap_or_default() {
+            path[pos] = 1.0;
+        }
+        Ok(vec![("heights", heights), ("path", path)])
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![(
+            params::STRATEGY,
+            params::strategy_name(self.strategy, &STRATEGIES),
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            params::STRATEGY => self.strategy = params::strategy(value, &STRATEGIES)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+
+    fn strategies(&self) -> Vec<&'static str> {
+        params::strategy_names(&STRATEGIES)
+    }
+}
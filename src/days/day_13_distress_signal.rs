@@ -0,0 +1,169 @@
+//! Solution to an Advent of Code problem, day 13, 2022
+//! https://adventofcode.com/2022/day/13
+use nom::branch::alt;
+use nom::character::complete::char;
+use nom::combinator::{map, opt};
+use nom::sequence::delimited;
+
+use crate::parse::{self, comma_list, context, uint, IResult};
+use crate::{trace, Answer, ParseError, Result, Solution};
+
+#[derive(Clone)]
+pub struct DistressSignal;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Packet {
+    Number(u64),
+    List(Vec<Packet>),
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Packet::Number(a), Packet::Number(b)) => a.cmp(b),
+            (Packet::List(_), Packet::Number(b)) => {
+                self.cmp(&Packet::List(vec![Packet::Number(*b)]))
+            }
+            (Packet::Number(a), Packet::List(_)) => {
+                Packet::List(vec![Packet::Number(*a)]).cmp(other)
+            }
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+        }
+    }
+}
+
+fn parse_list(input: &str) -> IResult<'_, Packet> {
+    let (input, a) = delimited(
+        char('['),
+        opt(comma_list(parse_packet)),
+        context("',' or ']'", char(']')),
+    )(input)?;
+    Ok((input, Packet::List(a.unwrap_or_default())))
+}
+
+fn parse_number(input: &str) -> IResult<'_, Packet> {
+    map(uint, Packet::Number)(input)
+}
+
+fn parse_packet(input: &str) -> IResult<'_, Packet> {
+    context("packet", alt((parse_number, parse_list)))(input)
+}
+
+impl Solution for DistressSignal {
+    /// Pairs of packets
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut pairs = vec![];
+        let mut lines = input.lines();
+        let mut previous_line = &input[..0];
+        loop {
+            let mut packet = || -> std::result::Result<Packet, ParseError> {
+                let line = lines
+                    .next()
+                    .ok_or_else(|| ParseError::after(input, previous_line, "packet"))?;
+                previous_line = line;
+                parse::line(input, line, parse_packet)
+            };
+            let a = packet()?;
+            let b = packet()?;
+
+            trace!("{:?}", a);
+            trace!("{:?}", b);
+
+            pairs.push((a, b));
+
+            // Pairs are separated by an empty line
+            match lines.next() {
+                Some("") => {}
+                Some(line) => return Err(ParseError::at(input, line, "empty line").into()),
+                None => break,
+            }
+        }
+        Ok(pairs)
+    }
+
+    fn part_one(&self, pairs: &Self::Input) -> Result<Answer> {
+        let mut sum_of_ordered_indices: u64 = 0;
+
+        for (pair_index, (a, b)) in pairs.iter().enumerate() {
+            if a < b {
+                sum_of_ordered_indices += pair_index as u64 + 1;
+            }
+        }
+
+        Ok(sum_of_ordered_indices.into())
+    }
+
+    fn part_two(&self, pairs: &Self::Input) -> Result<Answer> {
+        let mut all_packets: Vec<Packet> = pairs
+            .iter()
+            .flat_map(|(a, b)| [a.clone(), b.clone()])
+            .collect();
+
+        // Insert divider packets
+        let divider_packet_1 = Packet::List(vec![Packet::List(vec![Packet::Number(2)])]);
+        let divider_packet_2 = Packet::List(vec![Packet::List(vec![Packet::Number(6)])]);
+        all_packets.push(divider_packet_1.clone());
+        all_packets.push(divider_packet_2.clone());
+
+        all_packets.sort();
+
+        let decoder_key: u64 = all_packets
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| {
+                if v == &divider_packet_1 || v == &divider_packet_2 {
+                    Some(i as u64 + 1)
+                } else {
+                    None
+                }
+            })
+            .product();
+
+        Ok(decoder_key.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AocError;
+
+    #[test]
+    fn test_parse_packet() {
+        let packet = parse::line("[[],[1,[2]],3]", "[[],[1,[2]],3]", parse_packet).unwrap();
+        assert_eq!(
+            packet,
+            Packet::List(vec![
+                Packet::List(vec![]),
+                Packet::List(vec![
+                    Packet::Number(1),
+                    Packet::List(vec![Packet::Number(2)])
+                ]),
+                Packet::Number(3),
+            ])
+        );
+
+        for (input, column, expected) in [
+            ("[1,[2,x]]\n[]\n", 7, "packet"),
+            ("[1,2\n[]\n", 5, "',' or ']'"),
+        ] {
+            match DistressSignal.parse(input).unwrap_err() {
+                AocError::Parse(e) => {
+                    assert_eq!(
+                        (e.line, e.column, e.expected.as_str()),
+                        (1, column, expected)
+                    )
+                }
+                e => panic!("Unexpected error: {}", e),
+            }
+        }
+    }
+}
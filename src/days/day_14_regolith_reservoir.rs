@@ -0,0 +1,253 @@
+//! Solution to an Advent of Code problem, day 14, 2022
+//! https://adventofcode.com/2022/day/14
+use std::cmp::max;
+use std::cmp::min;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::combinator::consumed;
+use nom::sequence::separated_pair;
+
+use crate::animate::{Frame, Simulation};
+use crate::grid::{Grid, Pos};
+use crate::parse::{self, context, list, uint, IResult};
+use crate::render::Picture;
+use crate::{params, Answer, AocError, ParseError, Part, Result, Solution};
+
+/// The cave is a fixed grid, big enough for sand poured from x=500 to pile up
+/// on the floor.
+#[derive(Clone)]
+pub struct RegolithReservoir {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl RegolithReservoir {
+    pub const DEFAULT: Self = RegolithReservoir {
+        width: 1024,
+        height: 1024,
+    };
+}
+
+const GRAIN: char = 'o';
+const ROCK: char = '#';
+const EMPTY: char = '.';
+const SOURCE_X: usize = 500;
+
+/// Printing the grid draws the cave the way the puzzle does.
+pub struct Cave {
+    grid: Grid<char>,
+    floor_y: usize,
+}
+
+impl Cave {
+    fn with_floor(&self) -> Grid<char> {
+        let mut grid = self.grid.clone();
+        grid.row_mut(self.floor_y).fill(ROCK);
+        grid
+    }
+}
+
+/// Sand poured into the cave, one grain moving at a time
+struct Sand {
+    grid: Grid<char>,
+    /// The grain falling
+    grain: Option<Pos>,
+    grains_that_came_to_rest: u64,
+    floor_y: usize,
+}
+
+impl Sand {
+    fn new(grid: Grid<char>, floor_y: usize) -> Sand {
+        Sand {
+            grid,
+            grain: None,
+            grains_that_came_to_rest: 0,
+            floor_y,
+        }
+    }
+}
+
+impl Simulation for Sand {
+    fn step(&mut self) -> bool {
+        let grid = &mut self.grid;
+        match self.grain {
+            None if grid[(SOURCE_X, 0)] != EMPTY => return false,
+            None => self.grain = Some((SOURCE_X, 0)),
+            // Falls out of the bottom
+            Some((_, y)) if y + 1 == grid.height() => return false,
+            Some((x, y)) => {
+                let below = [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)];
+                match below.into_iter().find(|&pos| grid[pos] == EMPTY) {
+                    Some(pos) => self.grain = Some(pos),
+                    None => {
+                        grid[(x, y)] = GRAIN;
+                        self.grains_that_came_to_rest += 1;
+                        self.grain = None;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// The cave as far as the sand can pile up on the floor
+    fn frame(&self) -> Frame {
+        let left = SOURCE_X - self.floor_y - 1;
+        let mut cells = self
+            .grid
+            .crop((left, 0), 2 * self.floor_y + 3, self.floor_y + 1);
+        cells[(SOURCE_X - left, 0)] = '+';
+        let grain = self.grain.unwrap_or((SOURCE_X, 0));
+        if let Some(pos) = cells.pos(grain.0 as i64 - left as i64, grain.1 as i64) {
+            cells[pos] = GRAIN;
+        }
+        Frame {
+            origin: (left as i64, 0),
+            focus: (grain.0 as i64, grain.1 as i64),
+            status: format!("{} grains at rest", self.grains_that_came_to_rest),
+            ..Frame::new(cells)
+        }
+    }
+}
+
+fn pour_the_sand(grid: Grid<char>, floor_y: usize) -> Sand {
+    let mut sand = Sand::new(grid, floor_y);
+    while sand.step() {}
+    sand
+}
+
+/// Corners of a rock path, with their text for error messages
+fn parse_path(input: &str) -> IResult<'_, Vec<(&str, (usize, usize))>> {
+    let corner = separated_pair(uint, char(','), uint);
+    list(tag(" -> "), consumed(context("coordinates (x,y)", corner)))(input)
+}
+
+impl Solution for RegolithReservoir {
+    type Input = Cave;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let max_x = self.width;
+        let max_y = self.height;
+
+        let mut grid = Grid::new(max_x, max_y, EMPTY);
+
+        let mut floor_y: usize = 0;
+
+        // Parse and fill grid
+        for path in parse::lines(input, parse_path)? {
+            let mut prev_xy: Option<(usize, usize)> = None;
+
+            for (xystr, (x, y)) in path {
+                // Sand can spread one cell left and right of the rock and the
+                // floor is two rows below it.
+                if x == 0 || x + 1 >= max_x || y + 2 >= max_y {
+                    let expected = format!("coordinates within {}x{}", max_x - 1, max_y - 2);
+                    return Err(ParseError::at(input, xystr, expected).into());
+                }
+                grid[(x, y)] = ROCK;
+
+                // Fill the line
+                match prev_xy {
+                    Some((prev_x, prev_y)) if prev_x == x => {
+                        for line_y in min(prev_y, y)..=max(prev_y, y) {
+                            grid[(x, line_y)] = ROCK;
+                        }
+                    }
+                    Some((prev_x, prev_y)) if prev_y == y => {
+                        for cell in &mut grid.row_mut(y)[min(prev_x, x)..=max(prev_x, x)] {
+                            *cell = ROCK;
+                        }
+                    }
+                    Some(_) => {
+                        let expected = "horizontal or vertical line";
+                        return Err(ParseError::at(input, xystr, expected).into());
+                    }
+                    None => {}
+                };
+
+                prev_xy = Some((x, y));
+
+                floor_y = max(y + 2, floor_y);
+            }
+        }
+
+        // On the floor the sand piles up in a triangle centered at x=500
+        if floor_y >= SOURCE_X || SOURCE_X + floor_y + 1 >= max_x {
+            return Err(AocError::Param(format!(
+                "width must be more than {} for this input",
+                SOURCE_X + floor_y + 1
+            )));
+        }
+
+        Ok(Cave { grid, floor_y })
+    }
+
+    fn part_one(&self, cave: &Self::Input) -> Result<Answer> {
+        let sand = pour_the_sand(cave.grid.clone(), cave.floor_y);
+        Ok(sand.grains_that_came_to_rest.into())
+    }
+
+    fn part_two(&self, cave: &Self::Input) -> Result<Answer> {
+        let sand = pour_the_sand(cave.with_floor(), cave.floor_y);
+        Ok(sand.grains_that_came_to_rest.into())
+    }
+
+    fn render(&self, cave: &Self::Input) -> Result<Vec<(&'static str, Picture)>> {
+        let part_one = pour_the_sand(cave.grid.clone(), cave.floor_y).grid;
+        let part_two = pour_the_sand(cave.with_floor(), cave.floor_y).grid;
+
+        // The pile of part 2 is the widest, down to the floor
+        let columns = part_two
+            .iter()
+            .filter(|&((_, y), &cell)| cell != EMPTY && y < cave.floor_y)
+            .map(|((x, _), _)| x);
+        let (min_x, max_x) = columns.fold((SOURCE_X, SOURCE_X), |(min_x, max_x), x| {
+            (min(min_x, x), max(max_x, x))
+        });
+        let picture = |grid: &Grid<char>| {
+            grid.crop((min_x - 1, 0), max_x - min_x + 3, cave.floor_y + 1)
+                .map(|&cell| match cell {
+                    ROCK => 0.5,
+                    GRAIN => 1.0,
+                    _ => 0.0,
+                })
+        };
+        Ok(vec![
+            ("part1", picture(&part_one)),
+            ("part2", picture(&part_two)),
+        ])
+    }
+
+    fn simulate<'a>(
+        &'a self,
+        cave: &'a Self::Input,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        let grid = match part {
+            Part::One => cave.grid.clone(),
+            Part::Two => cave.with_floor(),
+        };
+        Some(Box::new(Sand::new(grid, cave.floor_y)))
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("width", self.width.to_string()),
+            ("height", self.height.to_string()),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "width" => self.width = params::value(name, value)?,
+            "height" => self.height = params::value(name, value)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        // A rock needs a free column on either side and the floor below it
+        if self.width < 3 || self.height < 3 {
+            return Err(AocError::Param(format!("{} must be at least 3", name)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+//! Solution to an Advent of Code problem, day 15, 2022
+//! https://adventofcode.com/2022/day/15
+use std::cmp::max;
+use std::cmp::min;
+use std::ops::RangeInclusive;
+
+use nom::bytes::complete::tag;
+use nom::sequence::tuple;
+
+use crate::interval::IntervalSet;
+use crate::parse::{self, context, coords, IResult};
+use crate::{budget, debug, params, Answer, AocError, ParseError, Result, Solution};
+
+/// How part 2 finds the distress beacon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Looks for a gap in the coverage of each row of the search space
+    RowScan,
+    /// Only looks where the lines just outside the sensors' ranges cross
+    /// each other or the edges of the search space
+    Boundaries,
+}
+
+const STRATEGIES: [(&str, Strategy); 2] = [
+    ("row_scan", Strategy::RowScan),
+    ("boundaries", Strategy::Boundaries),
+];
+
+#[derive(Clone)]
+pub struct BeaconExclusionZone {
+    /// Row in which to count the positions where a beacon cannot be
+    pub interesting_row: i64,
+    /// The distress beacon has both coordinates between 0 and this
+    pub search_space: i64,
+    pub strategy: Strategy,
+}
+
+impl BeaconExclusionZone {
+    pub const DEFAULT: Self = BeaconExclusionZone {
+        interesting_row: 2000000,
+        search_space: 4000000,
+        strategy: Strategy::RowScan,
+    };
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Sensor {
+    pos: (i64, i64),
+    closest_beacon: (i64, i64),
+}
+
+fn parse_sensor(input: &str) -> IResult<'_, Sensor> {
+    let (input, (_, pos, _, closest_beacon)) = context(
+        "sensor (Sensor at x=.., y=..: closest beacon is at x=.., y=..)",
+        tuple((
+            tag("Sensor at "),
+            coords,
+            tag(": closest beacon is at "),
+            coords,
+        )),
+    )(input)?;
+
+    Ok((
+        input,
+        Sensor {
+            pos,
+            closest_beacon,
+        },
+    ))
+}
+
+/// Distance from the sensor to its closest beacon
+fn range(sensor: &Sensor) -> i64 {
+    (sensor.pos.0 - sensor.closest_beacon.0).abs() + (sensor.pos.1 - sensor.closest_beacon.1).abs()
+}
+
+fn row_coverage(sensor: &Sensor, y: i64) -> Option<RangeInclusive<i64>> {
+    let mhd = range(sensor);
+
+    let dy = (y - sensor.pos.1).abs();
+    if dy > mhd {
+        return None;
+    }
+    Some(sensor.pos.0 - (mhd - dy)..=sensor.pos.0 + (mhd - dy))
+}
+
+fn find_uncovered_spot(coverage: &IntervalSet, range_min: i64, range_max: i64) -> Option<i64> {
+    coverage
+        .complement(range_min..=range_max)
+        .iter()
+        .next()
+        .map(|range| *range.start())
+}
+
+fn scan_rows(sensors: &[Sensor], search_space: i64) -> Result<Option<(i64, i64)>> {
+    for y in 0..search_space + 1 {
+        if y % 4096 == 0 {
+            budget::check()?;
+        }
+        let coverage = sensors.iter().filter_map(|s| row_coverage(s, y)).collect();
+        if let Some(x) = find_uncovered_spot(&coverage, 0, search_space) {
+            return Ok(Some((x, y)));
+        }
+    }
+    Ok(None)
+}
+
+/// A lone uncovered position is next to the ranges of the sensors around
+/// it, on the lines `x + y = a` and `x - y = b` just outside of them, or at
+/// the edge of the search space. The first of the crossings of these lines
+/// that no sensor covers, by row and column.
+fn find_at_boundaries(sensors: &[Sensor], search_space: i64) -> Option<(i64, i64)> {
+    let mut sums = vec![0, search_space, 2 * search_space];
+    let mut differences = vec![-search_space, 0, search_space];
+    for sensor in sensors {
+        let (x, y) = sensor.pos;
+        let outside = range(sensor) + 1;
+        sums.extend([x + y - outside, x + y + outside]);
+        differences.extend([x - y - outside, x - y + outside]);
+    }
+    let edges = [0, search_space];
+    let mut candidates = vec![];
+    for &a in &sums {
+        for &b in &differences {
+            if (a + b) % 2 == 0 {
+                candidates.push(((a + b) / 2, (a - b) / 2));
+            }
+        }
+        for &edge in &edges {
+            candidates.extend([(edge, a - edge), (a - edge, edge)]);
+        }
+    }
+    for &b in &differences {
+        for &edge in &edges {
+            candidates.extend([(edge, edge - b), (edge + b, edge)]);
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|&(x, y)| (0..=search_space).contains(&x) && (0..=search_space).contains(&y))
+        .filter(|&(x, y)| {
+            sensors
+                .iter()
+                .all(|s| (s.pos.0 - x).abs() + (s.pos.1 - y).abs() > range(s))
+        })
+        .min_by_key(|&(x, y)| (y, x))
+}
+
+impl Solution for BeaconExclusionZone {
+    type Input = Vec<Sensor>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let sensors = parse::lines(input, parse_sensor)?;
+        if sensors.is_empty() {
+            return Err(ParseError::at_offset(input, 0, "sensor").into());
+        }
+        debug!("{:?}", sensors);
+        Ok(sensors)
+    }
+
+    fn part_one(&self, sensors: &Self::Input) -> Result<Answer> {
+        let mut min_x = i64::MAX;
+        let mut max_x = i64::MIN;
+        let mut min_y = i64::MAX;
+        let mut max_y = i64::MIN;
+        for s in sensors.iter() {
+            min_x = min(min_x, min(s.pos.0, s.closest_beacon.0));
+            max_x = max(max_x, max(s.pos.0, s.closest_beacon.0));
+            min_y = min(min_y, min(s.pos.1, s.closest_beacon.1));
+            max_y = max(max_y, max(s.pos.1, s.closest_beacon.1));
+        }
+
+        debug!(
+            "Coord ranges: X: [{}, {}]; Y: [{}, {}]",
+            min_x, max_x, min_y, max_y
+        );
+        debug!(
+            "Dimensions: W={}, H={}, Cell count={}",
+            max_x - min_x,
+            max_y - min_y,
+            (max_x - min_x) * (max_y - min_y)
+        );
+
+        let interesting_row = self.interesting_row;
+
+        let coverage: IntervalSet = sensors
+            .iter()
+            .filter_map(|s| row_coverage(s, interesting_row))
+            .collect();
+
+        // Remove known beacons
+        let mut beacons: Vec<i64> = sensors
+            .iter()
+            .filter(|s| s.closest_beacon.1 == interesting_row)
+            .map(|s| s.closest_beacon.0)
+            .collect();
+        beacons.sort_unstable();
+        beacons.dedup();
+        let known_beacons = beacons.iter().filter(|&&x| coverage.contains(x)).count();
+
+        Ok((coverage.len() - known_beacons as u64).into())
+    }
+
+    fn part_two(&self, sensors: &Self::Input) -> Result<Answer> {
+        let distress = match self.strategy {
+            Strategy::RowScan => scan_rows(sensors, self.search_space)?,
+            Strategy::Boundaries => find_at_boundaries(sensors, self.search_space),
+        };
+        match distress {
+            Some((x, y)) => {
+                debug!("Distress from x={}, y={}", x, y);
+                Ok((x * 4000000 + y).into())
+            }
+            None => Err(AocError::Solve(
+                "No position for the distress beacon".to_owned(),
+            )),
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("interesting_row", self.interesting_row.to_string()),
+            ("search_space", self.search_space.to_string()),
+            (
+                params::STRATEGY,
+                params::strategy_name(self.strategy, &STRATEGIES),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "interesting_row" => self.interesting_row = params::value(name, value)?,
+            "search_space" => self.search_space = params::value(name, value)?,
+            params::STRATEGY => self.strategy = params::strategy(value, &STRATEGIES)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+
+    fn strategies(&self) -> Vec<&'static str> {
+        params::strategy_names(&STRATEGIES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    fn manhattan(a: (i64, i64), b: (i64, i64)) -> i64 {
+        (a.0 - b.0).abs() + (a.1 - b.1).abs()
+    }
+
+    fn covered(sensors: &[Sensor], pos: (i64, i64)) -> bool {
+        sensors
+            .iter()
+            .any(|s| manhattan(s.pos, pos) <= manhattan(s.pos, s.closest_beacon))
+    }
+
+    /// Checks every position of the row that any sensor could reach.
+    fn reference_part_one(sensors: &[Sensor], y: i64) -> u64 {
+        let reach = |s: &Sensor| manhattan(s.pos, s.closest_beacon);
+        let min_x = sensors.iter().map(|s| s.pos.0 - reach(s)).min().unwrap();
+        let max_x = sensors.iter().map(|s| s.pos.0 + reach(s)).max().unwrap();
+        (min_x..=max_x)
+            .filter(|&x| covered(sensors, (x, y)))
+            .filter(|&x| sensors.iter().all(|s| s.closest_beacon != (x, y)))
+            .count() as u64
+    }
+
+    /// Checks every position of the search space.
+    fn reference_part_two(sensors: &[Sensor], search_space: i64) -> Option<i64> {
+        (0..=search_space)
+            .flat_map(|y| (0..=search_space).map(move |x| (x, y)))
+            .find(|&pos| !covered(sensors, pos))
+            .map(|(x, y)| x * 4000000 + y)
+    }
+
+    #[test]
+    fn test_find_uncovered_spot() {
+        let coverage = |ranges: &[RangeInclusive<i64>]| ranges.iter().cloned().collect();
+        assert_eq!(find_uncovered_spot(&coverage(&[]), 0, 10), Some(0));
+        assert_eq!(find_uncovered_spot(&coverage(&[0..=10]), 0, 10), None);
+        assert_eq!(find_uncovered_spot(&coverage(&[0..=10]), 0, 11), Some(11));
+        assert_eq!(
+            find_uncovered_spot(&coverage(&[0..=4, 5..=10]), 0, 10),
+            None
+        );
+        assert_eq!(
+            find_uncovered_spot(&coverage(&[0..=4, 6..=10]), 0, 10),
+            Some(5)
+        );
+        assert_eq!(find_uncovered_spot(&coverage(&[2..=12]), 0, 10), Some(0));
+    }
+
+    #[test]
+    fn test_against_reference() {
+        generate::check_generated(15, 0..20, &[1, 2, 3], |generated| {
+            let mut day = BeaconExclusionZone::DEFAULT;
+            for (name, value) in &generated.params {
+                day.set_param(name, value).unwrap();
+            }
+            let sensors = day.parse(&generated.input).unwrap();
+            assert_eq!(
+                day.part_one(&sensors).unwrap(),
+                reference_part_one(&sensors, day.interesting_row).into()
+            );
+            let expected = reference_part_two(&sensors, day.search_space).unwrap();
+            for (name, strategy) in STRATEGIES {
+                let day = BeaconExclusionZone {
+                    strategy,
+                    ..day.clone()
+                };
+                assert_eq!(day.part_two(&sensors).unwrap(), expected.into(), "{}", name);
+            }
+        });
+    }
+}
@@ -0,0 +1,551 @@
+//! Solution to an Advent of Code problem, day 17, 2022
+//! https://adventofcode.com/2022/day/17
+use std::collections::HashMap;
+
+use crate::animate::{Frame, Simulation};
+use crate::grid::Grid;
+use crate::render::Picture;
+use crate::{budget, params, Answer, ParseError, Part, Result, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Drops rocks until the chamber is as it was before, then skips the
+    /// cycles that would follow
+    Cycles,
+    /// Drops every rock
+    BruteForce,
+}
+
+const STRATEGIES: [(&str, Strategy); 2] = [
+    ("cycles", Strategy::Cycles),
+    ("brute_force", Strategy::BruteForce),
+];
+
+/// Most rocks dropped one by one in a few seconds
+const BRUTE_FORCE_ROCKS: usize = 10_000_000;
+
+#[derive(Clone)]
+pub struct PyroclasticFlow {
+    pub rocks_part_one: usize,
+    pub rocks_part_two: usize,
+    pub strategy: Strategy,
+}
+
+impl PyroclasticFlow {
+    pub const DEFAULT: Self = PyroclasticFlow {
+        rocks_part_one: 2022,
+        rocks_part_two: 1000000000000,
+        strategy: Strategy::Cycles,
+    };
+}
+
+const LEFT: u8 = b'<';
+const RIGHT: u8 = b'>';
+/// Rows kept of the tower while solving, the rows further down cannot be
+/// reached by falling rocks anymore
+const N: usize = 128;
+/// Rows kept of the tower for rendering, enough for the whole tower of 4094
+/// rocks
+const RENDER_ROWS: usize = 16384;
+/// Rows of the tower shown below its top by the animation
+const ANIMATION_ROWS: i64 = 48;
+/// Rocks dropped between two checks of the budget
+const ROCKS_PER_CHECK: usize = 4096;
+
+/// The rocks in the order they fall, as drawn in the puzzle
+const ROCKS: [&str; 5] = [
+    "####",
+    ".#.\n###\n.#.",
+    "..#\n..#\n###",
+    "#\n#\n#\n#",
+    "##\n##",
+];
+
+/// One bit per cell of the 7 units wide chamber, leftmost cell in the highest
+/// bit and the lowest bit for the right wall, bottom row of the rock first.
+fn rock_pattern(rock: &str) -> [u8; 4] {
+    let shape = Grid::parse(rock, |c| Some(c == '#'), "rock").expect("Invalid rock shape");
+    let mut pattern = [0; 4];
+    for (line, row) in pattern.iter_mut().zip(shape.rows().rev()) {
+        for (x, _) in row.iter().enumerate().filter(|(_, &rock)| rock) {
+            *line |= 0b10000000 >> x;
+        }
+    }
+    pattern
+}
+
+fn is_legal_position_u8<const N: usize>(
+    tower: &[u8; N],
+    rock_pattern: [u8; 4],
+    top: i64,
+    left: i64,
+) -> bool {
+    if top < 0 || left < 0 {
+        return false;
+    }
+
+    for y in 0..4_i64 {
+        let line = rock_pattern[y as usize] >> left;
+        if line & 0b00000001 > 0 {
+            return false;
+        }
+        if line & tower[(top + y) as usize % N] > 0 {
+            return false;
+        }
+    }
+    true
+}
+
+fn settle_u8<const N: usize>(
+    tower: &mut [u8; N],
+    rock_pattern: [u8; 4],
+    top: i64,
+    left: i64,
+) -> i64 {
+    let mut highest_rock = top;
+    for y in 0..4_i64 {
+        let i = (top + y) as usize % N;
+        tower[i] |= rock_pattern[y as usize] >> left;
+        if tower[i] > 0 {
+            highest_rock = top + y;
+        }
+    }
+    highest_rock
+}
+
+/// The tower in the chamber as a ring buffer of its top `N` rows, indexed by
+/// height modulo `N`, and the falling rock.
+struct Chamber<'a, const N: usize> {
+    jets: &'a [i64],
+    rock_patterns: [[u8; 4]; 5],
+    tower: [u8; N],
+    highest_rock: i64,
+    jet_index: usize,
+    /// Rocks that started falling so far
+    rocks: usize,
+    /// Pattern, top and left of the falling rock
+    falling: Option<([u8; 4], i64, i64)>,
+}
+
+impl<'a, const N: usize> Chamber<'a, N> {
+    fn new(jets: &'a [i64]) -> Chamber<'a, N> {
+        Chamber {
+            jets,
+            rock_patterns: ROCKS.map(rock_pattern),
+            tower: [0; N],
+            highest_rock: -1,
+            jet_index: 0,
+            rocks: 0,
+            falling: None,
+        }
+    }
+
+    fn height(&self) -> i64 {
+        self.highest_rock + 1
+    }
+
+    /// The top `N` rows of the tower from the highest rock down, the floor
+    /// as a full row. Together with the next rock and jet they decide where
+    /// the next rocks come to rest.
+    fn surface(&self) -> [u8; N] {
+        let mut surface = [0xff; N];
+        for (depth, row) in surface.iter_mut().enumerate() {
+            let height = self.highest_rock - depth as i64;
+            if height < 0 {
+                break;
+            }
+            *row = self.tower[height as usize % N];
+        }
+        surface
+    }
+
+    fn drop_rock(&mut self) {
+        while !self.push_and_fall() {}
+    }
+
+    /// Starts the next rock falling if there is none, then pushes the rock
+    /// by the next jet and lets it fall one unit. Returns whether it came to
+    /// rest.
+    fn push_and_fall(&mut self) -> bool {
+        let (rock_pattern, top, mut left) = match self.falling {
+            Some(falling) => falling,
+            None => {
+                let highest_rock = self.highest_rock;
+                for y in highest_rock + 5..highest_rock + 9 {
+                    self.tower[y as usize % N] = 0;
+                }
+                self.rocks += 1;
+                (
+                    self.rock_patterns[(self.rocks - 1) % 5],
+                    highest_rock + 4,
+                    2,
+                )
+            }
+        };
+
+        let left_after_jet = left + self.jets[self.jet_index];
+        if is_legal_position_u8(&self.tower, rock_pattern, top, left_after_jet) {
+            left = left_after_jet;
+        }
+        self.jet_index = (self.jet_index + 1) % self.jets.len();
+
+        if !is_legal_position_u8(&self.tower, rock_pattern, top - 1, left) {
+            let new_highest = settle_u8(&mut self.tower, rock_pattern, top, left);
+            if new_highest > self.highest_rock {
+                self.highest_rock = new_highest;
+            }
+            self.falling = None;
+            return true;
+        }
+        self.falling = Some((rock_pattern, top - 1, left));
+        false
+    }
+}
+
+/// The chamber dropping a number of rocks, one unit at a time
+struct Flow<'a> {
+    chamber: Chamber<'a, N>,
+    rocks_count: usize,
+}
+
+impl Simulation for Flow<'_> {
+    fn step(&mut self) -> bool {
+        let chamber = &mut self.chamber;
+        if chamber.falling.is_none() && chamber.rocks == self.rocks_count {
+            return false;
+        }
+        chamber.push_and_fall();
+        true
+    }
+
+    /// The top of the tower and the falling rock, as drawn in the puzzle
+    fn frame(&self) -> Frame {
+        let chamber = &self.chamber;
+        let top = chamber.highest_rock + 7;
+        let bottom = (chamber.highest_rock - ANIMATION_ROWS).max(0);
+        let mut cells = Grid::new(9, (top - bottom + 2) as usize, '.');
+        for (y, height) in (bottom..=top).rev().enumerate() {
+            cells[(0, y)] = '|';
+            cells[(8, y)] = '|';
+            let mut row = chamber.tower[height as usize % N];
+            if height > chamber.highest_rock {
+                row = 0;
+            }
+            let mut falling = 0;
+            if let Some((pattern, rock_top, left)) = chamber.falling {
+                if (rock_top..rock_top + 4).contains(&height) {
+                    falling = pattern[(height - rock_top) as usize] >> left;
+                }
+            }
+            for x in 0..7 {
+                let bit = 0b10000000 >> x;
+                if falling & bit != 0 {
+                    cells[(x + 1, y)] = '@';
+                } else if row & bit != 0 {
+                    cells[(x + 1, y)] = '#';
+                }
+            }
+        }
+        let last = cells.height() - 1;
+        for (x, cell) in cells.row_mut(last).iter_mut().enumerate() {
+            *cell = match (bottom, x) {
+                (0, 0 | 8) => '+',
+                (0, _) => '-',
+                _ => '~',
+            };
+        }
+
+        let focus = chamber
+            .falling
+            .map_or(chamber.highest_rock + 4, |(_, top, _)| top);
+        Frame {
+            origin: (0, -top),
+            focus: (4, -focus),
+            status: format!(
+                "rock {} of {}, tower height {}",
+                chamber.rocks,
+                self.rocks_count,
+                chamber.height()
+            ),
+            ..Frame::new(cells)
+        }
+    }
+}
+
+/// Drops `rocks_count` rocks and returns the top `N` rows of the tower, as a
+/// ring buffer indexed by height modulo `N`, and the height of the tower.
+fn drop_rocks<const N: usize>(jets: &[i64], rocks_count: usize) -> Result<([u8; N], i64)> {
+    let mut chamber = Chamber::<N>::new(jets);
+    for rock in 0..rocks_count {
+        if rock % ROCKS_PER_CHECK == 0 {
+            budget::check()?;
+        }
+        chamber.drop_rock();
+    }
+    Ok((chamber.tower, chamber.height()))
+}
+
+fn tower_height(jets: &[i64], rocks_count: usize) -> Result<i64> {
+    Ok(drop_rocks::<N>(jets, rocks_count)?.1)
+}
+
+/// [`tower_height`] without dropping the rocks of the cycles: once the
+/// chamber looks as it did after an earlier rock, the rocks in between are
+/// bound to repeat until the end.
+fn tower_height_with_cycles(jets: &[i64], rocks_count: usize) -> Result<i64> {
+    let mut chamber = Chamber::<N>::new(jets);
+    // Rocks dropped and height after each state of the chamber
+    let mut seen = HashMap::new();
+    while chamber.rocks < rocks_count {
+        if chamber.rocks.is_multiple_of(ROCKS_PER_CHECK) {
+            budget::check()?;
+        }
+        chamber.drop_rock();
+        let state = (chamber.rocks % 5, chamber.jet_index, chamber.surface());
+        let Some((rocks, height)) = seen.insert(state, (chamber.rocks, chamber.height())) else {
+            continue;
+        };
+        let period = chamber.rocks - rocks;
+        let cycles = (rocks_count - chamber.rocks) / period;
+        let skipped = cycles as i64 * (chamber.height() - height);
+        for _ in 0..(rocks_count - chamber.rocks) % period {
+            chamber.drop_rock();
+        }
+        return Ok(chamber.height() + skipped);
+    }
+    Ok(chamber.height())
+}
+
+impl PyroclasticFlow {
+    fn rocks_count(&self, part: Part) -> usize {
+        match part {
+            Part::One => self.rocks_part_one,
+            Part::Two => self.rocks_part_two,
+        }
+    }
+
+    fn tower_height(&self, jets: &[i64], rocks_count: usize) -> Result<i64> {
+        match self.strategy {
+            Strategy::BruteForce => tower_height(jets, rocks_count),
+            Strategy::Cycles => tower_height_with_cycles(jets, rocks_count),
+        }
+    }
+}
+
+impl Solution for PyroclasticFlow {
+    /// Jet directions, -1 for left and 1 for right
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        // The jet pattern is a single line, blank lines after it are harmless
+        let mut lines = input.lines();
+        let line = lines.next().unwrap_or("");
+        if let Some(extra) = lines.find(|line| !line.is_empty()) {
+            return Err(ParseError::at(input, extra, "end of input").into());
+        }
+        let mut jets = vec![];
+        for (i, b) in line.bytes().enumerate() {
+            match b {
+                LEFT => jets.push(-1),
+                RIGHT => jets.push(1),
+                _ => return Err(ParseError::at_offset(input, i, "jet direction (< or >)").into()),
+            }
+        }
+        if jets.is_empty() {
+            return Err(ParseError::at_offset(input, 0, "jet direction (< or >)").into());
+        }
+        Ok(jets)
+    }
+
+    fn part_one(&self, jets: &Self::Input) -> Result<Answer> {
+        Ok(self.tower_height(jets, self.rocks_part_one)?.into())
+    }
+
+    fn part_two(&self, jets: &Self::Input) -> Result<Answer> {
+        Ok(self.tower_height(jets, self.rocks_part_two)?.into())
+    }
+
+    fn render(&self, jets: &Self::Input) -> Result<Vec<(&'static str, Picture)>> {
+        // Each rock adds at most 4 rows and the ring buffer must not wrap
+        let rocks_count = self.rocks_part_one.min((RENDER_ROWS - 8) / 4);
+        let (tower, height) = drop_rocks::<RENDER_ROWS>(jets, rocks_count)?;
+        let mut picture = Grid::new(7, height as usize, 0.0);
+        for (y, row) in tower[..height as usize].iter().rev().enumerate() {
+            for x in 0..7 {
+                if row & (0b10000000 >> x) != 0 {
+                    picture[(x, y)] = 1.0;
+                }
+            }
+        }
+        Ok(vec![("tower", picture)])
+    }
+
+    fn simulate<'a>(
+        &'a self,
+        jets: &'a Self::Input,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        Some(Box::new(Flow {
+            chamber: Chamber::new(jets),
+            rocks_count: self.rocks_count(part),
+        }))
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rocks_part_one", self.rocks_part_one.to_string()),
+            ("rocks_part_two", self.rocks_part_two.to_string()),
+            (
+                params::STRATEGY,
+                params::strategy_name(self.strategy, &STRATEGIES),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "rocks_part_one" => self.rocks_part_one = params::value(name, value)?,
+            "rocks_part_two" => self.rocks_part_two = params::value(name, value)?,
+            params::STRATEGY => self.strategy = params::strategy(value, &STRATEGIES)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+
+    fn strategies(&self) -> Vec<&'static str> {
+        params::strategy_names(&STRATEGIES)
+    }
+
+    fn finishes(&self, part: Part) -> bool {
+        // Brute force takes about 10h for the 10^12 rocks of part 2 (`aoc
+        // bench 17` measures the rate), cycles a fraction of a second
+        self.strategy == Strategy::Cycles || self.rocks_count(part) <= BRUTE_FORCE_ROCKS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::{Budget, Exceeded, Token};
+    use crate::{generate, AocError, Solver};
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    /// Height of the tower after each rock, keeping every cell of rock in a
+    /// set. `y` counts up from the floor, at 0.
+    fn reference_heights(jets: &[i64], rocks_count: usize) -> Vec<i64> {
+        let shapes: Vec<Vec<(i64, i64)>> = ROCKS
+            .iter()
+            .map(|rock| {
+                let rows: Vec<&str> = rock.lines().rev().collect();
+                let mut cells = vec![];
+                for (y, row) in rows.iter().enumerate() {
+                    for (x, c) in row.chars().enumerate() {
+                        if c == '#' {
+                            cells.push((x as i64, y as i64));
+                        }
+                    }
+                }
+                cells
+            })
+            .collect();
+        let mut settled: HashSet<(i64, i64)> = HashSet::new();
+        let free = |settled: &HashSet<(i64, i64)>, cells: &[(i64, i64)], x: i64, y: i64| {
+            cells.iter().all(|&(dx, dy)| {
+                (0..7).contains(&(x + dx)) && y + dy > 0 && !settled.contains(&(x + dx, y + dy))
+            })
+        };
+
+        let mut heights = vec![];
+        let mut height = 0;
+        let mut jet = 0;
+        for rock in 0..rocks_count {
+            let cells = &shapes[rock % 5];
+            let (mut x, mut y) = (2, height + 4);
+            loop {
+                if free(&settled, cells, x + jets[jet], y) {
+                    x += jets[jet];
+                }
+                jet = (jet + 1) % jets.len();
+                if !free(&settled, cells, x, y - 1) {
+                    break;
+                }
+                y -= 1;
+            }
+            for &(dx, dy) in cells {
+                settled.insert((x + dx, y + dy));
+                height = height.max(y + dy);
+            }
+            heights.push(height);
+        }
+        heights
+    }
+
+    #[test]
+    fn test_parse() {
+        let solver = PyroclasticFlow::DEFAULT;
+        assert_eq!(
+            solver.parse_input(">><\r\n").unwrap().downcast_ref(),
+            Some(&vec![1i64, 1, -1])
+        );
+        match solver.parse_input(">><\n<<\n").unwrap_err() {
+            AocError::Parse(e) => assert_eq!((e.line, e.column), (2, 1)),
+            e => panic!("Unexpected error: {}", e),
+        }
+        match solver.parse_input(">><\n\n\n <\n").unwrap_err() {
+            AocError::Parse(e) => assert_eq!((e.line, e.column), (4, 1)),
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    /// The 10^12 rocks of part 2 dropped one by one stop at the end of the
+    /// budget
+    #[test]
+    fn test_budget() {
+        let brute_force = PyroclasticFlow {
+            strategy: Strategy::BruteForce,
+            ..PyroclasticFlow::DEFAULT
+        };
+        assert!(!Solution::finishes(&brute_force, Part::Two));
+        let jets = brute_force.parse(">><\n").unwrap();
+        let budget = Budget {
+            time: Some(Duration::from_millis(50)),
+            memory: None,
+        };
+        let result = budget::run(&budget, &Token::default(), || brute_force.part_two(&jets));
+        assert!(matches!(result, Err(AocError::Exceeded(Exceeded::Time(_)))));
+    }
+
+    #[test]
+    fn test_tower_against_reference() {
+        let jets = PyroclasticFlow::DEFAULT
+            .parse(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n")
+            .unwrap();
+        assert_eq!(reference_heights(&jets, 2022)[2021], 3068);
+        // Part 2 of the example, from the puzzle
+        assert_eq!(
+            PyroclasticFlow::DEFAULT.part_two(&jets).unwrap(),
+            Answer::from(1514285714288i64)
+        );
+
+        generate::check_generated(17, 0..30, &[1, 2, 5, 40], |generated| {
+            let jets = PyroclasticFlow::DEFAULT.parse(&generated.input).unwrap();
+            let heights = reference_heights(&jets, 2022);
+            for rocks_count in [1, 2, 5, 10, 100, 2022] {
+                for (name, strategy) in STRATEGIES {
+                    let day = PyroclasticFlow {
+                        strategy,
+                        ..PyroclasticFlow::DEFAULT
+                    };
+                    assert_eq!(
+                        day.tower_height(&jets, rocks_count).unwrap(),
+                        heights[rocks_count - 1],
+                        "{} rocks, {}",
+                        rocks_count,
+                        name
+                    );
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,105 @@
+//! Solution to an Advent of Code problem, day 18, 2022
+//! https://adventofcode.com/2022/day/18
+use nom::character::complete::char;
+use nom::combinator::verify;
+use nom::sequence::{preceded, tuple};
+
+use crate::parse::{self, context, uint, IResult};
+use crate::search;
+use crate::{Answer, Result, Solution};
+
+#[derive(Clone)]
+pub struct BoilingBoulders;
+
+type Cubes = [[[bool; 32]; 32]; 32];
+
+type Cube = (usize, usize, usize);
+
+/// The up to 6 cubes sharing a face with `(x, y, z)`
+fn adjacent((x, y, z): Cube) -> impl Iterator<Item = Cube> {
+    [
+        (x.wrapping_sub(1), y, z),
+        (x + 1, y, z),
+        (x, y.wrapping_sub(1), z),
+        (x, y + 1, z),
+        (x, y, z.wrapping_sub(1)),
+        (x, y, z + 1),
+    ]
+    .into_iter()
+    .filter(|&(x, y, z)| x < 32 && y < 32 && z < 32)
+}
+
+fn count_foggy_faces(shape: &Cubes, is_foggy: &Cubes) -> u64 {
+    let mut count = 0;
+    for cube in all_cubes().filter(|&(x, y, z)| shape[z][y][x]) {
+        count += adjacent(cube)
+            .filter(|&(x, y, z)| is_foggy[z][y][x])
+            .count() as u64;
+    }
+    count
+}
+
+fn all_cubes() -> impl Iterator<Item = Cube> {
+    (0..32).flat_map(|z| (0..32).flat_map(move |y| (0..32).map(move |x| (x, y, z))))
+}
+
+fn invert_3d_slice(slice: &Cubes) -> Cubes {
+    let mut inverted = *slice;
+    for plane in inverted.iter_mut() {
+        for line in plane.iter_mut() {
+            for cube in line.iter_mut() {
+                *cube = !*cube;
+            }
+        }
+    }
+    inverted
+}
+
+fn parse_coordinate(input: &str) -> IResult<'_, usize> {
+    // The shape is padded with a layer of air on each side
+    context("coordinate between 0 and 29", verify(uint, |&c| c <= 29))(input)
+}
+
+fn parse_cube(input: &str) -> IResult<'_, Cube> {
+    context(
+        "three coordinates (x,y,z)",
+        tuple((
+            parse_coordinate,
+            preceded(char(','), parse_coordinate),
+            preceded(char(','), parse_coordinate),
+        )),
+    )(input)
+}
+
+impl Solution for BoilingBoulders {
+    type Input = Box<Cubes>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut shape = Box::new([[[false; 32]; 32]; 32]);
+
+        for (x, y, z) in parse::lines(input, parse_cube)? {
+            shape[z + 1][y + 1][x + 1] = true;
+        }
+
+        Ok(shape)
+    }
+
+    fn part_one(&self, shape: &Self::Input) -> Result<Answer> {
+        Ok(count_foggy_faces(shape, &invert_3d_slice(shape)).into())
+    }
+
+    fn part_two(&self, shape: &Self::Input) -> Result<Answer> {
+        // For part 2, mark parts, that cannot be reached by fog
+        let air = |cube: Cube| adjacent(cube).filter(|&(x, y, z)| !shape[z][y][x]);
+        let outer_layer =
+            all_cubes().filter(|&(x, y, z)| [x, y, z].iter().any(|&c| c == 0 || c == 31));
+        let fog = search::bfs_multi(&air, outer_layer);
+
+        let mut is_foggy = [[[false; 32]; 32]; 32];
+        for &(x, y, z) in fog.distances.keys() {
+            is_foggy[z][y][x] = true;
+        }
+
+        Ok(count_foggy_faces(shape, &is_foggy).into())
+    }
+}
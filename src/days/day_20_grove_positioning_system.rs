@@ -0,0 +1,395 @@
+//! Solution to an Advent of Code problem, day 20, 2022
+//! https://adventofcode.com/2022/day/20
+use itertools::Itertools;
+
+use crate::{params, parse_number, trace, Answer, AocError, Result, Solution};
+
+/// How the numbers are kept in order while mixing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// A `Vec`, finding each number and moving it with remove and insert
+    Vec,
+    /// A [`Treap`], moving each number in logarithmic time
+    Tree,
+}
+
+const STRATEGIES: [(&str, Strategy); 2] = [("vec", Strategy::Vec), ("tree", Strategy::Tree)];
+
+#[derive(Clone)]
+pub struct GrovePositioningSystem {
+    /// Part 2 multiplies every number by the key before mixing
+    pub decryption_key: i64,
+    pub mixing_rounds: usize,
+    pub strategy: Strategy,
+}
+
+impl GrovePositioningSystem {
+    pub const DEFAULT: Self = GrovePositioningSystem {
+        decryption_key: 811589153,
+        mixing_rounds: 10,
+        strategy: Strategy::Vec,
+    };
+}
+
+const NIL: usize = usize::MAX;
+
+/// The circle as a binary tree whose in-order is the order of the numbers,
+/// kept balanced by random priorities. The nodes are the indices of the
+/// numbers in the file, and know their parents so that their positions can
+/// be found.
+struct Treap {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    parent: Vec<usize>,
+    /// Nodes in the subtree
+    size: Vec<usize>,
+    priority: Vec<u64>,
+    root: usize,
+}
+
+impl Treap {
+    /// The nodes `0..count` in order
+    fn new(count: usize) -> Treap {
+        // Splitmix64, seeded with the node
+        let priority = (0..count as u64)
+            .map(|node| {
+                let mut z = node.wrapping_add(1).wrapping_mul(0x9e3779b97f4a7c15);
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+                z ^ (z >> 31)
+            })
+            .collect();
+        let mut treap = Treap {
+            left: vec![NIL; count],
+            right: vec![NIL; count],
+            parent: vec![NIL; count],
+            size: vec![1; count],
+            priority,
+            root: NIL,
+        };
+        for node in 0..count {
+            treap.root = treap.merge(treap.root, node);
+        }
+        treap
+    }
+
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.size[node]
+        }
+    }
+
+    /// Recounts the size of `node` and adopts its children.
+    fn update(&mut self, node: usize) {
+        self.size[node] = 1 + self.size(self.left[node]) + self.size(self.right[node]);
+        for child in [self.left[node], self.right[node]] {
+            if child != NIL {
+                self.parent[child] = node;
+            }
+        }
+    }
+
+    /// The tree of the nodes of `a` followed by those of `b`
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL || b == NIL {
+            return if a == NIL { b } else { a };
+        }
+        if self.priority[a] > self.priority[b] {
+            self.right[a] = self.merge(self.right[a], b);
+            self.update(a);
+            a
+        } else {
+            self.left[b] = self.merge(a, self.left[b]);
+            self.update(b);
+            b
+        }
+    }
+
+    /// The trees of the first `k` nodes of `node` and of the rest
+    fn split(&mut self, node: usize, k: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        let left_size = self.size(self.left[node]);
+        if k <= left_size {
+            let (first, rest) = self.split(self.left[node], k);
+            self.left[node] = rest;
+            self.update(node);
+            (first, node)
+        } else {
+            let (first, rest) = self.split(self.right[node], k - left_size - 1);
+            self.right[node] = first;
+            self.update(node);
+            (node, rest)
+        }
+    }
+
+    fn position(&self, mut node: usize) -> usize {
+        let mut position = self.size(self.left[node]);
+        while self.parent[node] != NIL {
+            let parent = self.parent[node];
+            if self.right[parent] == node {
+                position += self.size(self.left[parent]) + 1;
+            }
+            node = parent;
+        }
+        position
+    }
+
+    /// Moves `node` to `position` among the other nodes.
+    fn move_to(&mut self, node: usize, position: usize) {
+        let (first, rest) = self.split(self.root, self.position(node));
+        let (node, rest) = self.split(rest, 1);
+        let others = self.merge(first, rest);
+        self.parent[others] = NIL;
+        let (first, rest) = self.split(others, position);
+        let first = self.merge(first, node);
+        self.root = self.merge(first, rest);
+        self.parent[self.root] = NIL;
+    }
+
+    fn in_order(&self) -> Vec<usize> {
+        let mut nodes = Vec::with_capacity(self.size(self.root));
+        let mut stack = vec![];
+        let mut node = self.root;
+        while node != NIL || !stack.is_empty() {
+            while node != NIL {
+                stack.push(node);
+                node = self.left[node];
+            }
+            node = stack.pop().unwrap();
+            nodes.push(node);
+            node = self.right[node];
+        }
+        nodes
+    }
+}
+
+/// [`remix`] on a [`Treap`]: mixes `numbers`, which stay in the order of the
+/// file, `rounds` times and returns them in their mixed order.
+fn mix_tree(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let count = numbers.len();
+    let mut treap = Treap::new(count);
+    if count > 1 {
+        for _ in 0..rounds {
+            for (node, &number) in numbers.iter().enumerate() {
+                let position = treap.position(node) as i64 + number % (count as i64 - 1);
+                treap.move_to(node, position.rem_euclid(count as i64 - 1) as usize);
+            }
+        }
+    }
+    treap
+        .in_order()
+        .into_iter()
+        .map(|node| numbers[node])
+        .collect()
+}
+
+fn remix(numbers: &mut Vec<i64>, new_positions: &mut Vec<usize>) {
+    let count = numbers.len();
+    if count < 2 {
+        return;
+    }
+    let mut _i: usize = 0;
+    loop {
+        if _i >= count {
+            break;
+        }
+        let i = new_positions.iter().find_position(|&&p| p == _i).unwrap().0;
+        _i += 1;
+
+        let number = numbers[i];
+        // Whole laps around the other numbers leave the order as it is
+        let steps = number % (count as i64 - 1);
+
+        let mut new_index = i as i64;
+        if steps > 0 {
+            new_index += steps;
+            let overflows_count = new_index / (count as i64 - 1);
+            new_index -= (count as i64 - 1) * overflows_count;
+            if new_index >= count as i64 {
+                new_index = new_index - count as i64 + 1;
+            }
+        }
+        if steps < 0 {
+            new_index += steps;
+            let overflows_count = new_index.abs() / (count as i64 - 1);
+            new_index += overflows_count * (count as i64 - 1);
+            if new_index <= 0 {
+                new_index = new_index + count as i64 - 1;
+            }
+        }
+        numbers.remove(i);
+        let np = new_positions.remove(i);
+        numbers.insert(new_index as usize, number);
+        new_positions.insert(new_index as usize, np);
+    }
+}
+
+fn the_sum(numbers: &[i64]) -> Result<i64> {
+    let count = numbers.len();
+    let index_of_0 = numbers.iter().find_position(|&&n| n == 0).unwrap().0;
+    [1000, 2000, 3000]
+        .iter()
+        .try_fold(0i64, |sum, offset| {
+            sum.checked_add(numbers[(index_of_0 + offset) % count])
+        })
+        .ok_or_else(|| AocError::Solve("The sum of the grove coordinates overflows".to_owned()))
+}
+
+impl Solution for GrovePositioningSystem {
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut numbers = vec![];
+        for line in input.lines() {
+            numbers.push(parse_number(input, line)?);
+        }
+        // Grove coordinates are counted from the number 0
+        if !numbers.contains(&0) {
+            return Err(AocError::Solve("The file contains no 0".to_owned()));
+        }
+        Ok(numbers)
+    }
+
+    fn part_one(&self, numbers: &Self::Input) -> Result<Answer> {
+        if self.strategy == Strategy::Tree {
+            return Ok(the_sum(&mix_tree(numbers, 1))?.into());
+        }
+        let mut numbers_p1 = numbers.clone();
+        let mut positions_p1 = (0..numbers.len()).collect::<Vec<usize>>();
+        remix(&mut numbers_p1, &mut positions_p1);
+        Ok(the_sum(&numbers_p1)?.into())
+    }
+
+    fn part_two(&self, numbers: &Self::Input) -> Result<Answer> {
+        let mut numbers_p2 = numbers
+            .iter()
+            .map(|&n| n.checked_mul(self.decryption_key))
+            .collect::<Option<Vec<i64>>>()
+            .ok_or_else(|| {
+                AocError::Solve("A number times the decryption key overflows".to_owned())
+            })?;
+        if self.strategy == Strategy::Tree {
+            return Ok(the_sum(&mix_tree(&numbers_p2, self.mixing_rounds))?.into());
+        }
+        let mut positions_p2 = (0..numbers.len()).collect::<Vec<usize>>();
+        for _ in 0..self.mixing_rounds {
+            remix(&mut numbers_p2, &mut positions_p2);
+            trace!("{:?}", numbers_p2.get(0..7));
+        }
+        Ok(the_sum(&numbers_p2)?.into())
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("decryption_key", self.decryption_key.to_string()),
+            ("mixing_rounds", self.mixing_rounds.to_string()),
+            (
+                params::STRATEGY,
+                params::strategy_name(self.strategy, &STRATEGIES),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "decryption_key" => self.decryption_key = params::value(name, value)?,
+            "mixing_rounds" => self.mixing_rounds = params::value(name, value)?,
+            params::STRATEGY => self.strategy = params::strategy(value, &STRATEGIES)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+
+    fn strategies(&self) -> Vec<&'static str> {
+        params::strategy_names(&STRATEGIES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    /// Mixes by moving each number one position at a time around the
+    /// circle, as the puzzle describes it. Moving a number by one less than
+    /// the count brings it back to where it was, so only the rest is moved.
+    fn reference_mix(numbers: &[i64], rounds: usize) -> i64 {
+        let count = numbers.len();
+        // Original index and value, in circle order
+        let mut circle: Vec<(usize, i64)> = numbers.iter().copied().enumerate().collect();
+        for _ in 0..rounds {
+            for original in 0..count {
+                let mut i = circle.iter().position(|&(o, _)| o == original).unwrap();
+                let number = circle[i].1;
+                for _ in 0..number.unsigned_abs() % (count as u64 - 1) {
+                    let next = if number > 0 {
+                        (i + 1) % count
+                    } else {
+                        (i + count - 1) % count
+                    };
+                    circle.swap(i, next);
+                    i = next;
+                }
+            }
+        }
+        let zero = circle.iter().position(|&(_, n)| n == 0).unwrap();
+        [1000, 2000, 3000]
+            .iter()
+            .map(|offset| circle[(zero + offset) % count].1)
+            .sum()
+    }
+
+    #[test]
+    fn test_remix_against_reference() {
+        let example = [1, 2, -3, 3, -2, 0, 4];
+        assert_eq!(reference_mix(&example, 1), 3);
+        let decrypted = example.map(|n| n * GrovePositioningSystem::DEFAULT.decryption_key);
+        assert_eq!(reference_mix(&decrypted, 10), 1623178306);
+
+        for (_, strategy) in STRATEGIES {
+            let day = GrovePositioningSystem {
+                strategy,
+                ..GrovePositioningSystem::DEFAULT
+            };
+            check_against_reference(&day);
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let day = GrovePositioningSystem {
+            decryption_key: i64::MAX,
+            ..GrovePositioningSystem::DEFAULT
+        };
+        let numbers = day.parse("0\n1\n-1\n").unwrap();
+        assert!(day.part_two(&numbers).is_ok());
+        let numbers = day.parse("0\n2\n-1\n").unwrap();
+        assert!(matches!(day.part_two(&numbers), Err(AocError::Solve(_))));
+
+        // Numbers as big as they get still move around the circle
+        let numbers = vec![0, i64::MIN, i64::MAX, 1];
+        assert_eq!(
+            day.part_one(&numbers).unwrap(),
+            reference_mix(&numbers, 1).into()
+        );
+    }
+
+    fn check_against_reference(day: &GrovePositioningSystem) {
+        generate::check_generated(20, 0..100, &[3, 4, 7, 30], |generated| {
+            let numbers = day.parse(&generated.input).unwrap();
+            assert_eq!(
+                day.part_one(&numbers).unwrap(),
+                reference_mix(&numbers, 1).into()
+            );
+            let decrypted: Vec<i64> = numbers.iter().map(|n| n * day.decryption_key).collect();
+            assert_eq!(
+                day.part_two(&numbers).unwrap(),
+                reference_mix(&decrypted, day.mixing_rounds).into()
+            );
+        });
+    }
+}
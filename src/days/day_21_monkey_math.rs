@@ -0,0 +1,432 @@
+//! Solution to an Advent of Code problem, day 21, 2022
+//! https://adventofcode.com/2022/day/21
+use itertools::Itertools;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::bytes::complete::take_while_m_n;
+use nom::character::complete::space0;
+use nom::combinator::{map_res, value};
+use nom::sequence::tuple;
+use std::collections::HashMap;
+
+use crate::parse::{self, context, int, IResult};
+use crate::{budget, debug, params, trace, Answer, AocError, Result, Solution};
+
+/// Part 2 searches for the number to yell by bisection between `search_min`
+/// and `search_max`.
+#[derive(Clone)]
+pub struct MonkeyMath {
+    pub search_min: i64,
+    pub search_max: i64,
+}
+
+impl MonkeyMath {
+    pub const DEFAULT: Self = MonkeyMath {
+        search_min: 0,
+        search_max: 5000000000000,
+    };
+}
+
+type Name = [u8; 4];
+const HUMN: Name = *b"humn";
+const ROOT: Name = *b"root";
+const BLANK: Name = *b"____";
+
+#[derive(Clone, Debug)]
+pub enum Operator {
+    Plus,
+    Minus,
+    Times,
+    DivideBy,
+}
+
+#[derive(Clone, Debug)]
+pub enum Monkey {
+    CalculatingMonkey {
+        name: Name,
+        left: Name,
+        right: Name,
+        operator: Operator,
+    },
+    ShoutingMonkey {
+        name: Name,
+        number: i64,
+    },
+    PausedMonkey {
+        name: Name,
+    },
+}
+
+fn parse_name(input: &str) -> IResult<'_, Name> {
+    context(
+        "monkey name (4 lowercase letters)",
+        map_res(
+            take_while_m_n(4, 4, |c: char| c.is_ascii_lowercase()),
+            |name: &str| name.as_bytes().try_into(),
+        ),
+    )(input)
+}
+
+fn no_such_monkey(name: Name) -> AocError {
+    AocError::Solve(format!(
+        "No number for monkey {}",
+        String::from_utf8_lossy(&name)
+    ))
+}
+
+fn parse_operator(input: &str) -> IResult<'_, Operator> {
+    alt((
+        value(Operator::Plus, tag("+")),
+        value(Operator::Minus, tag("-")),
+        value(Operator::Times, tag("*")),
+        value(Operator::DivideBy, tag("/")),
+    ))(input)
+}
+
+fn parse_calculating_monkey(input: &str) -> IResult<'_, Monkey> {
+    let (input, (name, _, left, _, operator, _, right)) = tuple((
+        parse_name,
+        tag(": "),
+        parse_name,
+        space0,
+        parse_operator,
+        space0,
+        parse_name,
+    ))(input)?;
+
+    Ok((
+        input,
+        Monkey::CalculatingMonkey {
+            name,
+            left,
+            right,
+            operator,
+        },
+    ))
+}
+fn parse_shouting_monkey(input: &str) -> IResult<'_, Monkey> {
+    let (input, (name, _, number)) = tuple((parse_name, tag(": "), int))(input)?;
+    Ok((input, Monkey::ShoutingMonkey { name, number }))
+}
+
+fn parse_monkey(input: &str) -> IResult<'_, Monkey> {
+    context(
+        "monkey job (\"name: number\" or \"name: a + b\")",
+        alt((parse_calculating_monkey, parse_shouting_monkey)),
+    )(input)
+}
+
+fn evaluate(monkeys: &mut Vec<Monkey>) -> Result<HashMap<Name, i64>> {
+    let mut something_evaluated = true;
+    let mut evaluated = HashMap::new();
+    while something_evaluated {
+        let monkeys_to_evaluate = monkeys
+            .extract_if(.., |m| match m {
+                Monkey::ShoutingMonkey { name: _, number: _ } => true,
+                Monkey::CalculatingMonkey {
+                    name: _,
+                    left,
+                    right,
+                    operator: _,
+                } => evaluated.contains_key(&*left) && evaluated.contains_key(&*right),
+                Monkey::PausedMonkey { name: _ } => false,
+            })
+            .collect_vec();
+        something_evaluated = !monkeys_to_evaluate.is_empty();
+        for monkey in monkeys_to_evaluate {
+            match monkey {
+                Monkey::ShoutingMonkey { name, number } => {
+                    evaluated.insert(name, number);
+                }
+                Monkey::CalculatingMonkey {
+                    name,
+                    left,
+                    right,
+                    operator,
+                } => {
+                    let left_ = evaluated.get(&left).unwrap();
+                    let right_ = evaluated.get(&right).unwrap();
+                    let number = match operator {
+                        Operator::Plus => left_ + right_,
+                        Operator::Minus => left_ - right_,
+                        Operator::Times => left_ * right_,
+                        Operator::DivideBy => left_.checked_div(*right_).ok_or_else(|| {
+                            AocError::Solve(format!(
+                                "Monkey {} can't divide {} by {}",
+                                String::from_utf8_lossy(&name),
+                                left_,
+                                right_
+                            ))
+                        })?,
+                    };
+                    evaluated.insert(name, number);
+                }
+                Monkey::PausedMonkey { name: _ } => {}
+            }
+        }
+    }
+    Ok(evaluated)
+}
+
+fn evaluate_for_name(
+    humn_value: i64,
+    humn_index: usize,
+    name: Name,
+    initial_monkeys: &[Monkey],
+) -> Result<i64> {
+    let mut monkeys = initial_monkeys.to_vec();
+    monkeys[humn_index] = Monkey::ShoutingMonkey {
+        name: HUMN,
+        number: humn_value,
+    };
+
+    let cache = evaluate(&mut monkeys)?;
+    cache
+        .get(&name)
+        .copied()
+        .ok_or_else(|| no_such_monkey(name))
+}
+
+fn is_between(a: i64, b: i64, x: i64) -> bool {
+    (a <= x && x < b) || (a > x && x >= b)
+}
+
+fn find_monkey_index(name_: Name, monkeys: &[Monkey]) -> Result<usize> {
+    monkeys
+        .iter()
+        .find_position(|m| match m {
+            Monkey::CalculatingMonkey {
+                name,
+                left: _,
+                right: _,
+                operator: _,
+            } => *name == name_,
+            Monkey::ShoutingMonkey { name, number: _ } => *name == name_,
+            Monkey::PausedMonkey { name } => *name == name_,
+        })
+        .map(|(index, _)| index)
+        .ok_or_else(|| no_such_monkey(name_))
+}
+
+impl Solution for MonkeyMath {
+    type Input = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let monkeys = parse::lines(input, parse_monkey)?;
+        trace!("{:?}", monkeys);
+        Ok(monkeys)
+    }
+
+    fn part_one(&self, monkeys: &Self::Input) -> Result<Answer> {
+        let mut monkeys_part_1 = monkeys.clone();
+        let evaluated_part_1 = evaluate(&mut monkeys_part_1)?;
+        let root = evaluated_part_1
+            .get(&ROOT)
+            .ok_or_else(|| no_such_monkey(ROOT))?;
+        Ok((*root).into())
+    }
+
+    fn part_two(&self, monkeys: &Self::Input) -> Result<Answer> {
+        let mut monkeys_part_2 = monkeys.clone();
+        let humn_index = find_monkey_index(HUMN, &monkeys_part_2)?;
+        let _root_index = find_monkey_index(ROOT, &monkeys_part_2)?;
+        let mut root_monkey_left: Name = BLANK;
+        let mut root_monkey_right: Name = BLANK;
+        for m in monkeys_part_2.iter() {
+            match m {
+                Monkey::CalculatingMonkey {
+                    name,
+                    left,
+                    right,
+                    operator: _,
+                } if *name == ROOT => {
+                    root_monkey_left = *left;
+                    root_monkey_right = *right;
+                }
+                _ => {}
+            }
+        }
+        monkeys_part_2[humn_index] = Monkey::PausedMonkey { name: HUMN };
+        //let cache = evaluate(&mut monkeys_part_2, HashMap::new());
+
+        // Both, in test and real input, the "humn" is in the left side of the equation.
+        // Our target value is on the right side.
+        let target_value = evaluate_for_name(0, humn_index, root_monkey_right, &monkeys_part_2)?;
+
+        let humn_index = find_monkey_index(HUMN, &monkeys_part_2)?;
+
+        // Bisection ahead --->>>>>>
+
+        // Initial search space (determined by trial and error)
+        let mut a = self.search_min;
+        let mut b = self.search_max;
+
+        loop {
+            budget::check()?;
+            let c = (a + b) / 2;
+            let for_a = evaluate_for_name(a, humn_index, root_monkey_left, &monkeys_part_2)?;
+            let for_b = evaluate_for_name(b, humn_index, root_monkey_left, &monkeys_part_2)?;
+            let for_c = evaluate_for_name(c, humn_index, root_monkey_left, &monkeys_part_2)?;
+
+            if for_a == target_value {
+                return Ok(a.into());
+            } else if for_b == target_value {
+                return Ok(b.into());
+            } else if b - a < 2 {
+                return Err(AocError::Solve(
+                    "No number to yell in the search space".to_owned(),
+                ));
+            } else if is_between(for_a, for_c, target_value) {
+                b = c;
+            } else if is_between(for_c, for_b, target_value) {
+                a = c;
+            } else {
+                return Err(AocError::Solve("Search space too narrow!".to_owned()));
+            }
+
+            debug!("a={}, b={}", a, b);
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("search_min", self.search_min.to_string()),
+            ("search_max", self.search_max.to_string()),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "search_min" => self.search_min = params::value(name, value)?,
+            "search_max" => self.search_max = params::value(name, value)?,
+            _ => return Err(params::unknown(name, &self.params())),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    type Jobs<'a> = HashMap<Name, &'a Monkey>;
+
+    fn jobs(monkeys: &[Monkey]) -> Jobs<'_> {
+        let name = |monkey: &Monkey| match monkey {
+            Monkey::CalculatingMonkey { name, .. }
+            | Monkey::ShoutingMonkey { name, .. }
+            | Monkey::PausedMonkey { name } => *name,
+        };
+        monkeys.iter().map(|m| (name(m), m)).collect()
+    }
+
+    fn reference_yell(jobs: &Jobs, name: Name) -> i64 {
+        match jobs[&name] {
+            Monkey::ShoutingMonkey { number, .. } => *number,
+            Monkey::CalculatingMonkey {
+                left,
+                right,
+                operator,
+                ..
+            } => {
+                let (left, right) = (reference_yell(jobs, *left), reference_yell(jobs, *right));
+                match operator {
+                    Operator::Plus => left + right,
+                    Operator::Minus => left - right,
+                    Operator::Times => left * right,
+                    Operator::DivideBy => left / right,
+                }
+            }
+            Monkey::PausedMonkey { .. } => unreachable!(),
+        }
+    }
+
+    fn needs_humn(jobs: &Jobs, name: Name) -> bool {
+        match jobs[&name] {
+            Monkey::CalculatingMonkey { left, right, .. } => {
+                needs_humn(jobs, *left) || needs_humn(jobs, *right)
+            }
+            _ => name == HUMN,
+        }
+    }
+
+    /// The number humn yells for `name` to yell `target`, undoing the
+    /// operations on the way from `name` down to humn.
+    fn reference_humn(jobs: &Jobs, name: Name, target: i64) -> i64 {
+        let Monkey::CalculatingMonkey {
+            left,
+            right,
+            operator,
+            ..
+        } = jobs[&name]
+        else {
+            return target;
+        };
+        if needs_humn(jobs, *left) {
+            let right = reference_yell(jobs, *right);
+            let target = match operator {
+                Operator::Plus => target - right,
+                Operator::Minus => target + right,
+                Operator::Times => target / right,
+                Operator::DivideBy => target * right,
+            };
+            reference_humn(jobs, *left, target)
+        } else {
+            let left = reference_yell(jobs, *left);
+            let target = match operator {
+                Operator::Plus => target - left,
+                Operator::Minus => left - target,
+                Operator::Times => target / left,
+                Operator::DivideBy => left / target,
+            };
+            reference_humn(jobs, *right, target)
+        }
+    }
+
+    #[test]
+    fn test_divide_by_zero() {
+        let day = MonkeyMath::DEFAULT;
+        let monkeys = day.parse("root: aaaa / bbbb\naaaa: 5\nbbbb: 0\n").unwrap();
+        match day.part_one(&monkeys) {
+            Err(AocError::Solve(message)) => {
+                assert_eq!(message, "Monkey root can't divide 5 by 0")
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_against_reference() {
+        let day = MonkeyMath::DEFAULT;
+        generate::check_generated(21, 0..50, &[1, 10, 60], |generated| {
+            let monkeys = day.parse(&generated.input).unwrap();
+            let jobs = jobs(&monkeys);
+            assert_eq!(
+                day.part_one(&monkeys).unwrap(),
+                reference_yell(&jobs, ROOT).into()
+            );
+
+            let Monkey::CalculatingMonkey { left, right, .. } = jobs[&ROOT] else {
+                panic!("root yells a number");
+            };
+            let (humn_side, other) = if needs_humn(&jobs, *left) {
+                (*left, *right)
+            } else {
+                (*right, *left)
+            };
+            let humn = reference_humn(&jobs, humn_side, reference_yell(&jobs, other));
+            assert_eq!(day.part_two(&monkeys).unwrap(), humn.into());
+
+            // Both sides of root are equal with that number
+            let mut monkeys = monkeys.clone();
+            for monkey in monkeys.iter_mut() {
+                if let Monkey::ShoutingMonkey { name: HUMN, number } = monkey {
+                    *number = humn;
+                }
+            }
+            let jobs = self::jobs(&monkeys);
+            assert_eq!(reference_yell(&jobs, *left), reference_yell(&jobs, *right));
+        });
+    }
+}
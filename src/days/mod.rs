@@ -0,0 +1,151 @@
+pub mod day_01_calorie_counting;
+pub mod day_02_rock_paper_scissors;
+pub mod day_03_rucksack_reorganization;
+pub mod day_04_camp_cleanup;
+pub mod day_05_supply_stacks;
+pub mod day_06_tuning_trouble;
+pub mod day_07_no_space_left_on_device;
+pub mod day_08_treetop_tree_house;
+pub mod day_09_rope_bridge;
+pub mod day_10_cathode_ray_tube;
+pub mod day_11_monkey_in_the_middle;
+pub mod day_12_hill_climbing_algorithm;
+pub mod day_13_distress_signal;
+pub mod day_14_regolith_reservoir;
+pub mod day_15_beacon_exclusion_zone;
+pub mod day_17_pyroclastic_flow;
+pub mod day_18_boiling_boulders;
+pub mod day_20_grove_positioning_system;
+pub mod day_21_monkey_math;
+
+use crate::journal::JOURNAL_DIR;
+use crate::{Part, Solver};
+
+/// A registered solution together with the name of its source and input files.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub solver: &'static dyn Solver,
+}
+
+impl Day {
+    /// Example: day_01_calorie_counting
+    pub fn name(&self) -> String {
+        format!("day_{:02}_{}", self.number, self.title)
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("inputs/{}.txt", self.name())
+    }
+
+    pub fn test_input_path(&self) -> String {
+        format!("test_inputs/{}.txt", self.name())
+    }
+
+    /// Example: journal/day_01_calorie_counting_part1.tsv
+    pub fn journal_path(&self, part: Part) -> String {
+        format!("{}/{}_part{}.tsv", JOURNAL_DIR, self.name(), part)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        title: "calorie_counting",
+        solver: &day_01_calorie_counting::CalorieCounting,
+    },
+    Day {
+        number: 2,
+        title: "rock_paper_scissors",
+        solver: &day_02_rock_paper_scissors::RockPaperScissors,
+    },
+    Day {
+        number: 3,
+        title: "rucksack_reorganization",
+        solver: &day_03_rucksack_reorganization::RucksackReorganization,
+    },
+    Day {
+        number: 4,
+        title: "camp_cleanup",
+        solver: &day_04_camp_cleanup::CampCleanup,
+    },
+    Day {
+        number: 5,
+        title: "supply_stacks",
+        solver: &day_05_supply_stacks::SupplyStacks,
+    },
+    Day {
+        number: 6,
+        title: "tuning_trouble",
+        solver: &day_06_tuning_trouble::TuningTrouble,
+    },
+    Day {
+        number: 7,
+        title: "no_space_left_on_device",
+        solver: &day_07_no_space_left_on_device::NoSpaceLeftOnDevice::DEFAULT,
+    },
+    Day {
+        number: 8,
+        title: "treetop_tree_house",
+        solver: &day_08_treetop_tree_house::TreetopTreeHouse,
+    },
+    Day {
+        number: 9,
+        title: "rope_bridge",
+        solver: &day_09_rope_bridge::RopeBridge,
+    },
+    Day {
+        number: 10,
+        title: "cathode_ray_tube",
+        solver: &day_10_cathode_ray_tube::CathodeRayTube,
+    },
+    Day {
+        number: 11,
+        title: "monkey_in_the_middle",
+        solver: &day_11_monkey_in_the_middle::MonkeyInTheMiddle::DEFAULT,
+    },
+    Day {
+        number: 12,
+        title: "hill_climbing_algorithm",
+        solver: &day_12_hill_climbing_algorithm::HillClimbingAlgorithm::DEFAULT,
+    },
+    Day {
+        number: 13,
+        title: "distress_signal",
+        solver: &day_13_distress_signal::DistressSignal,
+    },
+    Day {
+        number: 14,
+        title: "regolith_reservoir",
+        solver: &day_14_regolith_reservoir::RegolithReservoir::DEFAULT,
+    },
+    Day {
+        number: 15,
+        title: "beacon_exclusion_zone",
+        solver: &day_15_beacon_exclusion_zone::BeaconExclusionZone::DEFAULT,
+    },
+    Day {
+        number: 17,
+        title: "pyroclastic_flow",
+        solver: &day_17_pyroclastic_flow::PyroclasticFlow::DEFAULT,
+    },
+    Day {
+        number: 18,
+        title: "boiling_boulders",
+        solver: &day_18_boiling_boulders::BoilingBoulders,
+    },
+    Day {
+        number: 20,
+        title: "grove_positioning_system",
+        solver: &day_20_grove_positioning_system::GrovePositioningSystem::DEFAULT,
+    },
+    Day {
+        number: 21,
+        title: "monkey_math",
+        solver: &day_21_monkey_math::MonkeyMath::DEFAULT,
+    },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
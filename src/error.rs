@@ -0,0 +1,194 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use crate::budget::Exceeded;
+
+/// Malformed puzzle input, located by line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, counted in characters
+    pub column: usize,
+    /// The offending line, without the line ending
+    pub line_text: String,
+    /// What the parser was looking for, e.g. "number"
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error at byte `offset` of `input`.
+    pub fn at_offset(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            file: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            line_text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error at the start of `at`, which has to be a slice of `input`.
+    pub fn at(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(input.len());
+        ParseError::at_offset(input, offset, expected)
+    }
+
+    /// Error right after the end of `at`, which has to be a slice of `input`.
+    pub fn after(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        ParseError::at(input, &at[at.len()..], expected)
+    }
+
+    pub fn from_nom(
+        input: &str,
+        err: nom::Err<nom::error::Error<&str>>,
+        expected: impl Into<String>,
+    ) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::at(input, e.input, expected),
+            nom::Err::Incomplete(_) => ParseError::at_offset(input, input.len(), expected),
+        }
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// Compiler-style diagnostic with a caret under the offending column:
+    ///
+    /// ```text
+    /// error: expected number
+    ///  --> inputs/day_05_supply_stacks.txt:3:6
+    ///   |
+    /// 3 | move x from 2 to 1
+    ///   |      ^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: expected {}", self.expected)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug)]
+pub enum AocError {
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    Parse(ParseError),
+    /// The input is well-formed, but the puzzle cannot be solved with it.
+    Solve(String),
+    /// Unknown puzzle constant or a value of the wrong type
+    Param(String),
+    /// The solver ran out of time or memory, or was cancelled
+    Exceeded(Exceeded),
+}
+
+impl AocError {
+    /// Names the file a parse error was found in.
+    pub fn in_file(self, file: impl Into<String>) -> Self {
+        match self {
+            AocError::Parse(e) => AocError::Parse(e.in_file(file)),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "Cannot read file {}: {}", path, source),
+            AocError::Parse(e) => write!(f, "{}", e),
+            AocError::Solve(message) | AocError::Param(message) => {
+                write!(f, "error: {}", message)
+            }
+            AocError::Exceeded(exceeded) => write!(f, "error: {}", exceeded),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            AocError::Parse(e) => Some(e),
+            AocError::Solve(_) | AocError::Param(_) | AocError::Exceeded(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        AocError::Parse(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn parse_number<T: FromStr>(input: &str, token: &str) -> std::result::Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(input, token, "number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "1000\n2000\n\nabc\n";
+        let e = ParseError::at(input, &input[12..], "number");
+        assert_eq!((e.line, e.column), (4, 2));
+        assert_eq!(e.line_text, "abc");
+
+        let e = ParseError::at_offset(input, input.len(), "number");
+        assert_eq!((e.line, e.column), (5, 1));
+        assert_eq!(e.line_text, "");
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "move 1 from 2 to 1\nmove x from 2 to 1";
+        let e = parse_number::<usize>(input, &input[24..25])
+            .unwrap_err()
+            .in_file("test.txt");
+        assert_eq!(
+            e.to_string(),
+            "error: expected number\n --> test.txt:2:6\n  |\n2 | move x from 2 to 1\n  |      ^"
+        );
+    }
+}
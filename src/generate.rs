@@ -0,0 +1,911 @@
+//! Seeded generators of puzzle inputs, for testing the solvers with inputs
+//! bigger than the examples without sharing the real ones.
+//!
+//! Every registered day has a generator whose output its parser accepts and
+//! whose puzzle has an answer. The same seed and size always generate the
+//! same input. The size is roughly the number of lines; each generator says
+//! what it means exactly.
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::interval::IntervalSet;
+use crate::params::Overrides;
+
+/// Puzzle input together with the puzzle constants it needs, e.g. the row of
+/// day 15
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub params: Overrides,
+}
+
+impl From<String> for Generated {
+    fn from(input: String) -> Self {
+        Generated {
+            input,
+            params: vec![],
+        }
+    }
+}
+
+/// Generates an input for `day`, `None` for days without a generator.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<Generated> {
+    let generator: fn(&mut Rng, usize) -> Generated = match day {
+        1 => calorie_counting,
+        2 => rock_paper_scissors,
+        3 => rucksack_reorganization,
+        4 => camp_cleanup,
+        5 => supply_stacks,
+        6 => tuning_trouble,
+        7 => no_space_left_on_device,
+        8 => treetop_tree_house,
+        9 => rope_bridge,
+        10 => cathode_ray_tube,
+        11 => monkey_in_the_middle,
+        12 => hill_climbing_algorithm,
+        13 => distress_signal,
+        14 => regolith_reservoir,
+        15 => beacon_exclusion_zone,
+        17 => pyroclastic_flow,
+        18 => boiling_boulders,
+        20 => grove_positioning_system,
+        21 => monkey_math,
+        _ => return None,
+    };
+    Some(generator(&mut Rng::new(seed), size))
+}
+
+/// Runs `check` on the inputs of `day` generated from every seed and size,
+/// for comparing a solver with a slow reference. A failing check panics
+/// with the seed, size and input that reproduce it.
+#[cfg(test)]
+pub(crate) fn check_generated(
+    day: u8,
+    seeds: std::ops::Range<u64>,
+    sizes: &[usize],
+    check: impl Fn(&Generated),
+) {
+    for seed in seeds {
+        for &size in sizes {
+            let generated = generate(day, seed, size).expect("No generator for the day");
+            let result =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| check(&generated)));
+            if let Err(payload) = result {
+                let message = payload
+                    .downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| payload.downcast_ref::<&str>().copied())
+                    .unwrap_or("panic");
+                panic!(
+                    "Day {}, seed {}, size {}: {}\nInput:\n{}",
+                    day, seed, size, message, generated.input
+                );
+            }
+        }
+    }
+}
+
+/// SplitMix64, small and good enough for test inputs
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "Empty range {}..={}", start, end);
+        let span = (end as i128 - start as i128 + 1) as u128;
+        (start as i128 + (self.next_u64() as u128 % span) as i128) as i64
+    }
+
+    /// A number below `n`, which must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.below(26) as u8) as char
+}
+
+/// `size` elves, at least 3, with 1 to 12 snacks each
+fn calorie_counting(rng: &mut Rng, size: usize) -> Generated {
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            (0..rng.range(1..=12))
+                .map(|_| format!("{}\n", rng.range(1000..=60000)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n").into()
+}
+
+/// `size` rounds
+fn rock_paper_scissors(rng: &mut Rng, size: usize) -> Generated {
+    let rounds = (0..size).map(|_| {
+        let opponent = rng.choose(&['A', 'B', 'C']);
+        format!("{} {}\n", opponent, rng.choose(&['X', 'Y', 'Z']))
+    });
+    rounds.collect::<String>().into()
+}
+
+/// `size` rucksacks, rounded up to whole groups of three. Both compartments
+/// of a rucksack have exactly one item type in common and the rucksacks of a
+/// group exactly one.
+fn rucksack_reorganization(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        // The badge, and 17 item types for each elf of the group alone
+        let badge = items[51];
+        for own in items[..51].chunks(17) {
+            let shared = own[0];
+            let (left, right) = own[1..].split_at(8);
+            let half = 3 + rng.below(10);
+            let mut first = vec![shared, badge];
+            first.extend((2..half).map(|_| *rng.choose(left)));
+            let mut second = vec![shared];
+            second.extend((1..half).map(|_| *rng.choose(right)));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+            if rng.chance(0.5) {
+                std::mem::swap(&mut first, &mut second);
+            }
+            input.extend(first);
+            input.extend(second);
+            input.push('\n');
+        }
+    }
+    input.into()
+}
+
+/// `size` pairs of assignments
+fn camp_cleanup(rng: &mut Rng, size: usize) -> Generated {
+    let mut sections = || {
+        let start = rng.range(1..=99);
+        format!("{}-{}", start, rng.range(start..=99))
+    };
+    let pairs = (0..size).map(|_| format!("{},{}\n", sections(), sections()));
+    pairs.collect::<String>().into()
+}
+
+/// `size` moves of crates between 3 to 9 stacks
+fn supply_stacks(rng: &mut Rng, size: usize) -> Generated {
+    let count = (size / 50).clamp(3, 9);
+    let mut stacks: Vec<Vec<char>> = (0..count)
+        .map(|_| {
+            (0..rng.range(0..=8))
+                .map(|_| letter(rng).to_ascii_uppercase())
+                .collect()
+        })
+        .collect();
+    if stacks.iter().all(|s| s.is_empty()) {
+        stacks[0].push('A');
+    }
+
+    let mut input = String::new();
+    let depth = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    for level in (0..depth).rev() {
+        let mut crates = stacks.iter().map(|s| match s.get(level) {
+            Some(c) => format!("[{}]", c),
+            None => "   ".to_owned(),
+        });
+        writeln!(input, "{}", crates.join(" ")).unwrap();
+    }
+    let mut numbers = (1..=count).map(|i| format!(" {} ", i));
+    writeln!(input, "{}\n", numbers.join(" ")).unwrap();
+
+    for _ in 0..size {
+        let nonempty: Vec<usize> = (0..count).filter(|&i| !stacks[i].is_empty()).collect();
+        let from = *rng.choose(&nonempty);
+        let to = (from + 1 + rng.below(count - 1)) % count;
+        let how_many = 1 + rng.below(stacks[from].len());
+        let split_index = stacks[from].len() - how_many;
+        let mut crates = stacks[from].split_off(split_index);
+        stacks[to].append(&mut crates);
+        writeln!(input, "move {} from {} to {}", how_many, from + 1, to + 1).unwrap();
+    }
+    input.into()
+}
+
+/// A datastream of `size` characters, at least 40, with a start-of-message
+/// marker in its second half
+fn tuning_trouble(rng: &mut Rng, size: usize) -> Generated {
+    let length = size.max(40);
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let marker = length / 2 + rng.below(length - length / 2 - 13);
+    // Too few different letters for a start-of-message marker
+    let mut stream: String = (0..marker).map(|_| *rng.choose(&letters[..13])).collect();
+    rng.shuffle(&mut letters);
+    stream.extend(&letters[..14]);
+    stream.extend((marker + 14..length).map(|_| *rng.choose(&letters)));
+    stream.push('\n');
+    stream.into()
+}
+
+struct Directory {
+    name: String,
+    files: Vec<(String, u64)>,
+    directories: Vec<usize>,
+}
+
+/// A terminal session exploring a tree of `size` files and directories, at
+/// least one of them a file
+fn no_space_left_on_device(rng: &mut Rng, size: usize) -> Generated {
+    let mut tree = vec![Directory {
+        name: "/".to_owned(),
+        files: vec![],
+        directories: vec![],
+    }];
+    for i in 0..size.max(1) {
+        let parent = rng.below(tree.len());
+        let is_directory = i > 0 && rng.chance(0.25);
+        let name = loop {
+            let mut name: String = (0..rng.range(1..=8)).map(|_| letter(rng)).collect();
+            if !is_directory && rng.chance(0.5) {
+                name = format!("{}.{}", name, rng.choose(&["txt", "dat", "log", "lst"]));
+            }
+            let siblings = &tree[parent];
+            let taken = siblings.files.iter().any(|(file, _)| *file == name)
+                || siblings.directories.iter().any(|&d| tree[d].name == name);
+            if !taken {
+                break name;
+            }
+        };
+        if is_directory {
+            tree.push(Directory {
+                name,
+                files: vec![],
+                directories: vec![],
+            });
+            let index = tree.len() - 1;
+            tree[parent].directories.push(index);
+        } else {
+            let file_size = rng.range(1000..=300000) as u64;
+            tree[parent].files.push((name, file_size));
+        }
+    }
+
+    let mut lines = vec!["$ cd /".to_owned()];
+    explore(&tree, 0, &mut vec![], rng, &mut lines);
+    lines
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>()
+        .into()
+}
+
+/// Lists the directory `index` and explores its subdirectories, with `path`
+/// the directories from the root to it
+fn explore(
+    tree: &[Directory],
+    index: usize,
+    path: &mut Vec<usize>,
+    rng: &mut Rng,
+    lines: &mut Vec<String>,
+) {
+    let directory = &tree[index];
+    lines.push("$ ls".to_owned());
+    let directories = directory
+        .directories
+        .iter()
+        .map(|&d| format!("dir {}", tree[d].name));
+    let files = directory
+        .files
+        .iter()
+        .map(|(name, size)| format!("{} {}", size, name));
+    let mut listing: Vec<String> = directories.chain(files).collect();
+    rng.shuffle(&mut listing);
+    lines.extend(listing);
+
+    for &child in &directory.directories {
+        lines.push(format!("$ cd {}", tree[child].name));
+        path.push(child);
+        explore(tree, child, path, rng, lines);
+        path.pop();
+        // Back up, now and then all the way from the root
+        if rng.chance(0.1) {
+            lines.push("$ cd /".to_owned());
+            lines.extend(path.iter().map(|&d| format!("$ cd {}", tree[d].name)));
+        } else {
+            lines.push("$ cd ..".to_owned());
+        }
+    }
+}
+
+/// A square forest `size` trees wide, at least 5
+fn treetop_tree_house(rng: &mut Rng, size: usize) -> Generated {
+    let side = size.max(5);
+    let rows = (0..side).map(|_| {
+        let row: String = (0..side)
+            .map(|_| (b'0' + rng.below(10) as u8) as char)
+            .collect();
+        row + "\n"
+    });
+    rows.collect::<String>().into()
+}
+
+/// `size` motions of the head
+fn rope_bridge(rng: &mut Rng, size: usize) -> Generated {
+    let motions = (0..size).map(|_| {
+        let direction = rng.choose(&['R', 'L', 'U', 'D']);
+        format!("{} {}\n", direction, rng.range(1..=19))
+    });
+    motions.collect::<String>().into()
+}
+
+/// A program of at least `size` instructions that runs for the 240 cycles of
+/// the CRT, with the sprite on the screen
+fn cathode_ray_tube(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut x, mut cycles, mut count) = (1, 0, 0);
+    while count < size || cycles < 240 {
+        let target = rng.range(0..=39);
+        if rng.chance(0.4) || target == x {
+            input += "noop\n";
+            cycles += 1;
+        } else {
+            writeln!(input, "addx {}", target - x).unwrap();
+            x = target;
+            cycles += 2;
+        }
+        count += 1;
+    }
+    input.into()
+}
+
+/// How a monkey changes the worry level
+enum Worry {
+    Square,
+    Times(u64),
+    Plus(u64),
+}
+
+struct MonkeyNotes {
+    items: Vec<u64>,
+    worry: Worry,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// `size` monkeys, between 2 and 9, testing divisibility by distinct primes
+/// so that the worry levels of part 2 stay small. Like in the puzzle, one
+/// monkey squares the worry level, up to two multiply it and the others add
+/// to it. Nobody throws to the one squaring and the factors are small, so
+/// the worry levels of part 1 fit into a u64 for 20 rounds.
+fn monkey_in_the_middle(rng: &mut Rng, size: usize) -> Generated {
+    let count = size.clamp(2, 9);
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+    let squarer = (count > 2).then(|| rng.below(count));
+    let mut multipliers = 2;
+    let monkeys: Vec<MonkeyNotes> = (0..count)
+        .map(|i| {
+            let worry = if Some(i) == squarer {
+                Worry::Square
+            } else if multipliers > 0 && rng.chance(0.4) {
+                multipliers -= 1;
+                Worry::Times(rng.range(2..=7) as u64)
+            } else {
+                Worry::Plus(rng.range(1..=8) as u64)
+            };
+            let mut others: Vec<usize> = (0..count)
+                .filter(|&j| j != i && Some(j) != squarer)
+                .collect();
+            rng.shuffle(&mut others);
+            MonkeyNotes {
+                items: (0..rng.range(1..=8))
+                    .map(|_| rng.range(50..=99) as u64)
+                    .collect(),
+                worry,
+                divisor: primes[i],
+                if_true: others[0],
+                if_false: others[others.len() - 1],
+            }
+        })
+        .collect();
+
+    let notes: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let operation = match monkey.worry {
+                Worry::Square => "old * old".to_owned(),
+                Worry::Times(n) => format!("old * {}", n),
+                Worry::Plus(n) => format!("old + {}", n),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i,
+                monkey.items.iter().join(", "),
+                operation,
+                monkey.divisor,
+                monkey.if_true,
+                monkey.if_false
+            )
+        })
+        .collect();
+    notes.join("\n").into()
+}
+
+/// A height map `size` columns wide, at least 40, with a path from S to E
+/// that never climbs more than one level per step
+fn hill_climbing_algorithm(rng: &mut Rng, size: usize) -> Generated {
+    let width = size.max(40);
+    let height = (width / 4).max(5);
+    let mut map = Grid::new(width, height, 'a');
+    for y in 0..height {
+        for x in 0..width {
+            map[(x, y)] = letter(rng);
+        }
+    }
+
+    // Up or down each column, then one step right, so it never crosses itself
+    let mut path = vec![];
+    let end_x = width - 1 - rng.below(width / 4);
+    let mut y = rng.below(height);
+    for x in 0..=end_x {
+        path.push((x, y));
+        let target = rng.below(height);
+        while y != target {
+            y = if target > y { y + 1 } else { y - 1 };
+            path.push((x, y));
+        }
+    }
+    for (i, &pos) in path.iter().enumerate() {
+        map[pos] = (b'a' + (i * 25 / (path.len() - 1)) as u8) as char;
+    }
+    map[path[0]] = 'S';
+    map[path[path.len() - 1]] = 'E';
+    format!("{}\n", map).into()
+}
+
+#[derive(Clone)]
+enum Packet {
+    Number(u64),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn random(rng: &mut Rng, depth: usize) -> Packet {
+        let items = (0..rng.range(0..=4))
+            .map(|_| {
+                if depth > 0 && rng.chance(0.3) {
+                    Packet::random(rng, depth - 1)
+                } else {
+                    Packet::Number(rng.range(0..=10) as u64)
+                }
+            })
+            .collect();
+        Packet::List(items)
+    }
+
+    /// Changes a number or drops the end of a list somewhere in the packet
+    fn mutate(&mut self, rng: &mut Rng) {
+        match self {
+            Packet::Number(n) => *n = rng.range(0..=10) as u64,
+            Packet::List(items) if items.is_empty() || rng.chance(0.2) => {
+                items.truncate(rng.below(items.len() + 1));
+            }
+            Packet::List(items) => {
+                let i = rng.below(items.len());
+                items[i].mutate(rng);
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Packet::Number(n) => write!(f, "{}", n),
+            Packet::List(items) => write!(f, "[{}]", items.iter().join(",")),
+        }
+    }
+}
+
+/// `size` pairs of packets, half of them differing in only one place. None
+/// is a divider packet.
+fn distress_signal(rng: &mut Rng, size: usize) -> Generated {
+    let mut pairs = vec![];
+    while pairs.len() < size.max(1) {
+        let left = Packet::random(rng, 4);
+        let right = if rng.chance(0.5) {
+            let mut right = left.clone();
+            right.mutate(rng);
+            right
+        } else {
+            Packet::random(rng, 4)
+        };
+        let pair = format!("{}\n{}\n", left, right);
+        if !pair
+            .lines()
+            .any(|packet| packet == "[[2]]" || packet == "[[6]]")
+        {
+            pairs.push(pair);
+        }
+    }
+    pairs.join("\n").into()
+}
+
+/// `size` paths of rock below the source of the sand, with 2 to 5 corners
+fn regolith_reservoir(rng: &mut Rng, size: usize) -> Generated {
+    let paths = (0..size.max(1)).map(|_| {
+        let (mut x, mut y) = (rng.range(440..=560), rng.range(13..=170));
+        let mut corners = vec![format!("{},{}", x, y)];
+        let horizontal = rng.chance(0.5);
+        for i in 0..rng.range(1..=4) {
+            let length = rng.range(1..=8) * if rng.chance(0.5) { 1 } else { -1 };
+            if (i % 2 == 0) == horizontal {
+                x = (x + length).clamp(440, 560);
+            } else {
+                y = (y + length).clamp(13, 170);
+            }
+            corners.push(format!("{},{}", x, y));
+        }
+        corners.join(" -> ") + "\n"
+    });
+    paths.collect::<String>().into()
+}
+
+type Point = (i64, i64);
+
+fn manhattan(a: Point, b: Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Sensors and their closest beacons covering a square of `20 * size`,
+/// except for the distress beacon. Part 1 looks at its middle row.
+fn beacon_exclusion_zone(rng: &mut Rng, size: usize) -> Generated {
+    let space = 20 * size.max(1) as i64;
+    let (sensors, _) = sensors_around_distress(rng, space);
+    let input = sensors
+        .iter()
+        .map(|(sensor, beacon)| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sensor.0, sensor.1, beacon.0, beacon.1
+            )
+        })
+        .collect();
+    Generated {
+        input,
+        params: vec![
+            ("interesting_row".to_owned(), (space / 2).to_string()),
+            ("search_space".to_owned(), space.to_string()),
+        ],
+    }
+}
+
+/// Sensors with their beacons that leave exactly one position between 0 and
+/// `space` uncovered, the distress beacon, which is returned as well. No
+/// beacon is closer to a sensor than its own.
+fn sensors_around_distress(rng: &mut Rng, space: i64) -> (Vec<(Point, Point)>, Point) {
+    let distress = (rng.range(0..=space), rng.range(0..=space));
+    // Four sensors diagonally around the distress beacon cover all of its
+    // neighbours
+    let k = rng.range(1..=space / 2);
+    let mut sensors: Vec<(Point, Point)> = [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+        .into_iter()
+        .map(|(dx, dy)| {
+            let sensor = (distress.0 + dx * k, distress.1 + dy * k);
+            (sensor, (sensor.0 + dx * (2 * k - 1), sensor.1))
+        })
+        .collect();
+
+    // Then more sensors for whatever they left uncovered, row by row
+    let mut y = 0;
+    while y <= space {
+        let coverage: IntervalSet<i64> = sensors
+            .iter()
+            .filter_map(|&(sensor, beacon)| {
+                let reach = manhattan(sensor, beacon) - (y - sensor.1).abs();
+                (reach >= 0).then(|| sensor.0 - reach..=sensor.0 + reach)
+            })
+            .chain((y == distress.1).then_some(distress.0..=distress.0))
+            .collect();
+        let Some(uncovered) = coverage.complement(0..=space).iter().next() else {
+            y += 1;
+            continue;
+        };
+        let target = (*uncovered.start(), y);
+        let spread = space / 10 + 1;
+        let mut candidates: Vec<Point> = (0..10)
+            .map(|_| {
+                let dx = rng.range(-spread..=spread);
+                (target.0 + dx, target.1 + rng.range(-spread..=spread))
+            })
+            .collect();
+        candidates.push(target);
+        let sensor = candidates
+            .into_iter()
+            .find_map(|sensor| place_sensor(rng, &sensors, distress, sensor, target))
+            .expect("A sensor fits on an uncovered position");
+        sensors.push(sensor);
+    }
+
+    rng.shuffle(&mut sensors);
+    (sensors, distress)
+}
+
+/// A sensor at `sensor` covering `target` with a beacon that no other
+/// sensor is closer to than to its own, if there is one.
+fn place_sensor(
+    rng: &mut Rng,
+    sensors: &[(Point, Point)],
+    distress: Point,
+    sensor: Point,
+    target: Point,
+) -> Option<(Point, Point)> {
+    let max_reach = sensors
+        .iter()
+        .map(|&(_, beacon)| manhattan(sensor, beacon))
+        .chain([manhattan(sensor, distress) - 1])
+        .min()?;
+    let min_reach = manhattan(sensor, target).max(1);
+    let fits = |beacon: Point| {
+        sensors
+            .iter()
+            .all(|&(other, its_beacon)| manhattan(other, beacon) >= manhattan(other, its_beacon))
+    };
+    let mut reach = max_reach;
+    while reach >= min_reach {
+        let corners = [(reach, 0), (0, reach), (-reach, 0), (0, -reach)];
+        let edge = (0..16).map(|_| {
+            let dx = rng.range(-reach..=reach);
+            let dy = (reach - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+            (dx, dy)
+        });
+        let beacon = corners
+            .into_iter()
+            .chain(edge)
+            .map(|(dx, dy)| (sensor.0 + dx, sensor.1 + dy))
+            .find(|&beacon| fits(beacon));
+        if let Some(beacon) = beacon {
+            return Some((sensor, beacon));
+        }
+        reach = min_reach + (reach - min_reach) / 2 - i64::from(reach == min_reach);
+    }
+    None
+}
+
+/// `size` jets
+fn pyroclastic_flow(rng: &mut Rng, size: usize) -> Generated {
+    let jets: String = (0..size.max(1))
+        .map(|_| if rng.chance(0.5) { '<' } else { '>' })
+        .collect();
+    (jets + "\n").into()
+}
+
+/// A droplet of `size` cubes grown from the middle, with pockets of air
+fn boiling_boulders(rng: &mut Rng, size: usize) -> Generated {
+    let count = size.clamp(1, 30 * 30 * 30 / 2);
+    let mut cubes = vec![(15, 15, 15)];
+    let mut taken = HashSet::from([(15, 15, 15)]);
+    while cubes.len() < count {
+        let (x, y, z) = *rng.choose(&cubes);
+        let next = *rng.choose(&[
+            (x + 1, y, z),
+            (x - 1, y, z),
+            (x, y + 1, z),
+            (x, y - 1, z),
+            (x, y, z + 1),
+            (x, y, z - 1),
+        ]);
+        let inside = [next.0, next.1, next.2].iter().all(|c| (0..30).contains(c));
+        if inside && taken.insert(next) {
+            cubes.push(next);
+        }
+    }
+    rng.shuffle(&mut cubes);
+    let lines = cubes.iter().map(|(x, y, z)| format!("{},{},{}\n", x, y, z));
+    lines.collect::<String>().into()
+}
+
+/// `size` numbers, at least 3, exactly one of them 0
+fn grove_positioning_system(rng: &mut Rng, size: usize) -> Generated {
+    let count = size.max(3);
+    let zero = rng.below(count);
+    let numbers = (0..count).map(|i| {
+        let mut n = 0;
+        while n == 0 && i != zero {
+            n = rng.range(-10000..=10000);
+        }
+        format!("{}\n", n)
+    });
+    numbers.collect::<String>().into()
+}
+
+/// Values of the monkeys of day 21 stay below this
+const MAX_YELL: i64 = 1_000_000_000_000;
+
+/// About `size` monkeys. The side of root's equation with humn only adds,
+/// subtracts and multiplies, so that exactly one number makes both sides
+/// equal, and it is within the default search space of part 2.
+fn monkey_math(rng: &mut Rng, size: usize) -> Generated {
+    let mut names = HashSet::from(["root".to_owned(), "humn".to_owned()]);
+    let mut jobs = vec![format!("humn: {}", rng.range(1..=1000))];
+
+    let steps = rng.range(3..=12) as usize;
+    let budget = size / 2 / steps;
+    let (mut name, mut value) = ("humn".to_owned(), rng.range(1..=MAX_YELL / 10));
+    let mut multiplications = 0;
+    for _ in 0..steps {
+        let (constant, operator, humn_first, next_value) = match rng.below(3) {
+            0 if value > 1 && rng.chance(0.5) => {
+                let c = rng.range(1..=(value - 1).min(1000));
+                (c, '-', true, value - c)
+            }
+            0 => {
+                let c = value + rng.range(1..=1000);
+                (c, '-', false, c - value)
+            }
+            1 if multiplications < 3 => {
+                multiplications += 1;
+                let c = rng.range(2..=4);
+                (c, '*', rng.chance(0.5), value * c)
+            }
+            _ => {
+                let c = rng.range(1..=1000);
+                (c, '+', rng.chance(0.5), value + c)
+            }
+        };
+        let other = yell(rng, constant, budget, &mut names, &mut jobs);
+        let next = monkey_name(rng, &mut names);
+        let (left, right) = if humn_first {
+            (&name, &other)
+        } else {
+            (&other, &name)
+        };
+        jobs.push(format!("{}: {} {} {}", next, left, operator, right));
+        (name, value) = (next, next_value);
+    }
+    let other = yell(rng, value, size / 2, &mut names, &mut jobs);
+    jobs.push(format!("root: {} + {}", name, other));
+
+    rng.shuffle(&mut jobs);
+    jobs.iter()
+        .map(|job| format!("{}\n", job))
+        .collect::<String>()
+        .into()
+}
+
+fn monkey_name(rng: &mut Rng, names: &mut HashSet<String>) -> String {
+    loop {
+        let name: String = (0..4).map(|_| letter(rng)).collect();
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// The name of a monkey that yells `value`, from a tree of about `budget`
+/// monkeys whose divisions all come out even
+fn yell(
+    rng: &mut Rng,
+    value: i64,
+    budget: usize,
+    names: &mut HashSet<String>,
+    jobs: &mut Vec<String>,
+) -> String {
+    let name = monkey_name(rng, names);
+    if budget < 3 {
+        jobs.push(format!("{}: {}", name, value));
+        return name;
+    }
+    let (left, operator, right) = loop {
+        match rng.below(4) {
+            0 if value > 1 => {
+                let a = rng.range(1..=value - 1);
+                break (a, '+', value - a);
+            }
+            1 if value < MAX_YELL / 2 => {
+                let b = rng.range(1..=value.clamp(1, 1000));
+                break (value + b, '-', b);
+            }
+            2 => {
+                let d = rng.range(2..=20);
+                if value % d == 0 {
+                    break (value / d, '*', d);
+                }
+            }
+            3 if value < MAX_YELL / 5 => {
+                let d = rng.range(2..=5);
+                break (value * d, '/', d);
+            }
+            _ => {}
+        }
+    };
+    let left_budget = rng.below(budget);
+    let left = yell(rng, left, left_budget, names, jobs);
+    let right = yell(rng, right, budget - 1 - left_budget, names, jobs);
+    jobs.push(format!("{}: {} {} {}", name, left, operator, right));
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Part, DAYS};
+
+    #[test]
+    fn test_generators() {
+        for day in DAYS {
+            for (seed, size) in [(0, 1), (1, 20), (2, 200)] {
+                // A day fresh from `aoc new` has no generator yet
+                let Some(generated) = generate(day.number, seed, size) else {
+                    continue;
+                };
+                assert_eq!(generate(day.number, seed, size).as_ref(), Some(&generated));
+
+                let solver = day.solver.with_params(&generated.params).unwrap();
+                let parsed = solver
+                    .parse_input(&generated.input)
+                    .unwrap_or_else(|e| panic!("Day {}, seed {}: {}", day.number, seed, e));
+                for part in Part::BOTH {
+                    // 10^12 rocks
+                    if (day.number, part) == (17, Part::Two) {
+                        continue;
+                    }
+                    if let Err(e) = solver.solve_parsed(parsed.as_ref(), part) {
+                        panic!("Day {}, seed {}, part {}: {}", day.number, seed, part, e);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sensors_around_distress() {
+        for seed in 0..20 {
+            let (sensors, distress) = sensors_around_distress(&mut Rng::new(seed), 20);
+            for x in 0..=20 {
+                for y in 0..=20 {
+                    let covered = sensors
+                        .iter()
+                        .any(|&(s, b)| manhattan(s, (x, y)) <= manhattan(s, b));
+                    assert_eq!(covered, (x, y) != distress, "seed {}", seed);
+                }
+            }
+            for &(sensor, beacon) in &sensors {
+                let reach = manhattan(sensor, beacon);
+                assert!(sensors.iter().all(|&(_, b)| manhattan(sensor, b) >= reach));
+            }
+        }
+    }
+}
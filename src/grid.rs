@@ -0,0 +1,363 @@
+//! Two-dimensional grid for the map puzzles (days 8, 12, 14, 17).
+//!
+//! Positions are `(x, y)` with `x` growing to the right and `y` growing down,
+//! the way the maps are drawn in the puzzle text.
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::ParseError;
+
+pub type Pos = (usize, usize);
+
+/// Right, down, left, up
+pub const DIRECTIONS_4: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// [`DIRECTIONS_4`] and the diagonals
+pub const DIRECTIONS_8: [(i64, i64); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Panics if the rows are not all of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Rows of different lengths"
+        );
+        Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// One row per line and one cell per character. `cell` returns `None`
+    /// for characters that are not allowed, which are reported as expecting
+    /// `expected`.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in input.lines() {
+            for (i, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(ParseError::at(input, &line[i..], expected)),
+                }
+            }
+            let line_width = line.chars().count();
+            match width {
+                None if line_width == 0 => return Err(ParseError::at(input, line, expected)),
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    let expected = format!("row of {} cells", width);
+                    return Err(ParseError::at(input, line, expected));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            None => Err(ParseError::at_offset(input, 0, expected)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `(x, y)` as a position, if it is inside the grid.
+    pub fn pos(&self, x: i64, y: i64) -> Option<Pos> {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// The position one step from `pos` in `direction`, if it is inside the
+    /// grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (i64, i64)) -> Option<Pos> {
+        self.pos(x as i64 + dx, y as i64 + dy)
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Up to 4 orthogonal neighbours of `pos` inside the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    /// Up to 8 orthogonal and diagonal neighbours of `pos` inside the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // chunks() panics on a chunk size of 0
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Panics if `x` is outside the grid, like [`Grid::row`].
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {} of a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Positions from `pos` (exclusive) in `direction` up to the edge of the
+    /// grid, e.g. the trees in the line of sight from a tree house.
+    pub fn ray(&self, pos: Pos, direction: (i64, i64)) -> impl Iterator<Item = Pos> + '_ {
+        let mut current = Some(pos);
+        std::iter::from_fn(move || {
+            current = self.step(current?, direction);
+            current
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The `width` by `height` cells from `(x, y)`, as far as they are
+    /// inside the grid.
+    pub fn crop(&self, (x, y): Pos, width: usize, height: usize) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let x_end = x.saturating_add(width).min(self.width);
+        let y_end = y.saturating_add(height).min(self.height);
+        let (x, y) = (x.min(x_end), y.min(y_end));
+        let cells = (y..y_end)
+            .flat_map(|row| self.row(row)[x..x_end].iter().cloned())
+            .collect();
+        Grid {
+            width: x_end - x,
+            height: y_end - y,
+            cells,
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotates by 90° clockwise: the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotates by 90° counterclockwise: the top row becomes the left column.
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {:?} outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Position {:?} outside of the grid", pos))
+    }
+}
+
+/// Draws the grid, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, Some, "cell").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = grid("ab\ncd\nef\n");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g[(1, 2)], 'f');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.to_string(), "ab\ncd\nef");
+
+        let e = Grid::parse("12\n3x\n", |c| c.to_digit(10), "digit").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 2, "digit"));
+
+        let e = Grid::parse("12\n345\n", |c| c.to_digit(10), "digit").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.expected, "row of 2 cells");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid("abc\ndef\nghi");
+        let values = |positions: Vec<Pos>| positions.into_iter().map(|p| g[p]).collect::<String>();
+        assert_eq!(values(g.neighbours4((0, 0)).collect()), "bd");
+        assert_eq!(values(g.neighbours4((1, 1)).collect()), "fhdb");
+        assert_eq!(values(g.neighbours8((1, 1)).collect()), "fihgdabc");
+        assert_eq!(values(g.neighbours8((2, 2)).collect()), "hef");
+    }
+
+    #[test]
+    fn test_lines() {
+        let g = grid("abc\ndef\nghi");
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.column(2).collect::<String>(), "cfi");
+        assert_eq!(
+            g.ray((2, 1), (-1, 0)).map(|p| g[p]).collect::<String>(),
+            "ed"
+        );
+        assert_eq!(
+            g.ray((0, 0), (1, 1)).map(|p| g[p]).collect::<String>(),
+            "ei"
+        );
+        assert_eq!(g.ray((0, 0), (0, -1)).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 of a grid 3 wide")]
+    fn test_column_outside() {
+        grid("abc\ndef\nghi").column(3).count();
+    }
+
+    #[test]
+    fn test_transform() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(g.rotate_clockwise().rotate_counterclockwise(), g);
+        assert_eq!(g.crop((1, 0), 2, 2).to_string(), "bc\nef");
+        assert_eq!(g.crop((2, 1), 5, 5).to_string(), "f");
+        assert_eq!(g.crop((3, 0), 1, 1).width(), 0);
+    }
+}
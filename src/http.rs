@@ -0,0 +1,357 @@
+//! Just enough HTTP/1.1 for the puzzle site client and the local servers:
+//! reading and writing requests and responses with a `Content-Length` or
+//! chunked body. Responses may also end with the connection.
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+/// Requests and responses bigger than this are refused
+const MAX_BODY: usize = 16 << 20;
+/// Longest start line, header or chunk size line
+const MAX_LINE: usize = 8 << 10;
+const MAX_HEADERS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// Path with the query string, e.g. `/2022/day/1/input`
+    pub path: String,
+    pub headers: Headers,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Headers,
+    pub body: String,
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+type Headers = Vec<(String, String)>;
+
+/// The value of the header `name`, which is case-insensitive
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// The start line and the headers, `None` if the connection closed first.
+fn read_head(reader: &mut impl BufRead) -> io::Result<Option<(String, Headers)>> {
+    let start = read_line(reader)?;
+    if start.is_empty() {
+        return Ok(None);
+    }
+    let mut headers = vec![];
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            return Err(invalid("Connection closed in the headers"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(invalid(format!("More than {} headers", MAX_HEADERS)));
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(format!("Invalid header: {}", line)))?;
+        headers.push((name.trim().to_owned(), value.trim().to_owned()));
+    }
+    Ok(Some((start.trim_end().to_owned(), headers)))
+}
+
+fn read_exact_body(reader: &mut impl BufRead, length: usize) -> io::Result<Vec<u8>> {
+    if length > MAX_BODY {
+        return Err(invalid(format!("Body too large: {} bytes", length)));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(body)
+}
+
+fn read_chunked_body(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let size = read_line(reader)?;
+        // Chunk extensions after ';' are ignored
+        let size = size.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| invalid(format!("Invalid chunk size: {}", size)))?;
+        if size == 0 {
+            // Trailers up to the empty line
+            while !matches!(read_line(reader)?.as_str(), "" | "\r\n" | "\n") {}
+            return Ok(body);
+        }
+        body.extend(read_exact_body(reader, size)?);
+        read_line(reader)?;
+        if body.len() > MAX_BODY {
+            return Err(invalid("Body too large"));
+        }
+    }
+}
+
+/// The next line with its line ending, empty at the end of the input
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    reader.take(MAX_LINE as u64 + 1).read_line(&mut line)?;
+    if line.len() > MAX_LINE {
+        return Err(invalid(format!("Line longer than {} bytes", MAX_LINE)));
+    }
+    Ok(line)
+}
+
+/// Whether the body comes in chunks, the only transfer encoding supported
+fn is_chunked(headers: &[(String, String)]) -> io::Result<bool> {
+    match find_header(headers, "Transfer-Encoding") {
+        None => Ok(false),
+        Some(encoding) if encoding.eq_ignore_ascii_case("chunked") => Ok(true),
+        Some(encoding) => Err(invalid(format!(
+            "Unsupported Transfer-Encoding: {}",
+            encoding
+        ))),
+    }
+}
+
+fn text(body: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(body).map_err(|_| invalid("Body is not UTF-8"))
+}
+
+fn content_length(headers: &[(String, String)]) -> io::Result<Option<usize>> {
+    find_header(headers, "Content-Length")
+        .map(|length| {
+            length
+                .parse()
+                .map_err(|_| invalid(format!("Invalid Content-Length: {}", length)))
+        })
+        .transpose()
+}
+
+fn write_message(
+    writer: &mut impl Write,
+    start: &str,
+    headers: &[(String, String)],
+    body: &str,
+) -> io::Result<()> {
+    let mut message = format!("{}\r\n", start);
+    for (name, value) in headers {
+        message += &format!("{}: {}\r\n", name, value);
+    }
+    if find_header(headers, "Content-Length").is_none() {
+        message += &format!("Content-Length: {}\r\n", body.len());
+    }
+    message += "\r\n";
+    message += body;
+    writer.write_all(message.as_bytes())?;
+    writer.flush()
+}
+
+impl Request {
+    pub fn new(method: &str, path: &str) -> Request {
+        Request {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            headers: vec![],
+            body: String::new(),
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Reads one request, `None` if the connection closed before it.
+    pub fn read(reader: &mut impl BufRead) -> io::Result<Option<Request>> {
+        let Some((start, headers)) = read_head(reader)? else {
+            return Ok(None);
+        };
+        let mut parts = start.split(' ');
+        let (Some(method), Some(path), Some(version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid(format!("Invalid request line: {}", start)));
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(invalid(format!("Unsupported version: {}", version)));
+        }
+        let body = if is_chunked(&headers)? {
+            text(read_chunked_body(reader)?)?
+        } else {
+            match content_length(&headers)? {
+                Some(length) => text(read_exact_body(reader, length)?)?,
+                None => String::new(),
+            }
+        };
+        Ok(Some(Request {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            headers,
+            body,
+        }))
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let start = format!("{} {} HTTP/1.1", self.method, self.path);
+        write_message(writer, &start, &self.headers, &self.body)
+    }
+}
+
+impl Response {
+    pub fn new(status: u16, body: impl Into<String>) -> Response {
+        Response {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Response {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn read(reader: &mut impl BufRead) -> io::Result<Response> {
+        let (start, headers) =
+            read_head(reader)?.ok_or_else(|| invalid("Connection closed without a response"))?;
+        let status = start
+            .strip_prefix("HTTP/1.")
+            .and_then(|rest| rest.get(2..5))
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| invalid(format!("Invalid status line: {}", start)))?;
+        let body = if is_chunked(&headers)? {
+            read_chunked_body(reader)?
+        } else if let Some(length) = content_length(&headers)? {
+            read_exact_body(reader, length)?
+        } else {
+            let mut body = vec![];
+            reader.take(MAX_BODY as u64).read_to_end(&mut body)?;
+            body
+        };
+        Ok(Response {
+            status,
+            headers,
+            body: text(body)?,
+        })
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let start = format!("HTTP/1.1 {} {}", self.status, reason(self.status));
+        write_message(writer, &start, &self.headers, &self.body)
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.status, reason(self.status))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        302 => "Found",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+/// `value` encoded for a form body (`application/x-www-form-urlencoded`)
+pub fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_owned(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request() {
+        let mut request = Request::new("POST", "/2022/day/1/answer");
+        request
+            .headers
+            .push(("Cookie".to_owned(), "session=abc".to_owned()));
+        request.body = "level=1&answer=24000".to_owned();
+        let mut bytes = vec![];
+        request.write_to(&mut bytes).unwrap();
+        let text = String::from_utf8(bytes.clone()).unwrap();
+        assert!(text.starts_with("POST /2022/day/1/answer HTTP/1.1\r\nCookie: session=abc\r\n"));
+
+        let read = Request::read(&mut &bytes[..]).unwrap().unwrap();
+        assert_eq!(read.header("cookie"), Some("session=abc"));
+        assert_eq!(read.header("Content-Length"), Some("20"));
+        assert_eq!(read.body, request.body);
+        assert_eq!(Request::read(&mut &b""[..]).unwrap(), None);
+        assert!(Request::read(&mut &b"GET /\r\n\r\n"[..]).is_err());
+
+        let chunked = "POST /solve/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+                       3\r\n1\n2\r\n2\r\n\n3\r\n0\r\n\r\n";
+        let read = Request::read(&mut chunked.as_bytes()).unwrap().unwrap();
+        assert_eq!(read.body, "1\n2\n3");
+        let gzip = "POST /solve/1 HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\r\n";
+        assert!(Request::read(&mut gzip.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_limits() {
+        let long_path = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE));
+        assert!(Request::read(&mut long_path.as_bytes()).is_err());
+        let headers = "X-A: b\r\n".repeat(MAX_HEADERS);
+        let request = format!("GET / HTTP/1.1\r\n{}\r\n", headers);
+        assert!(Request::read(&mut request.as_bytes()).is_ok());
+        let request = format!("GET / HTTP/1.1\r\n{}X-A: b\r\n\r\n", headers);
+        assert!(Request::read(&mut request.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_response() {
+        let response = Response::new(404, "Not found").with_header("Server", "mock");
+        let mut bytes = vec![];
+        response.write_to(&mut bytes).unwrap();
+        let read = Response::read(&mut &bytes[..]).unwrap();
+        assert_eq!((read.status, read.body.as_str()), (404, "Not found"));
+        assert_eq!(read.to_string(), "404 Not Found");
+
+        let chunked = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                       5\r\nHello\r\n7;x=y\r\n, world\r\n0\r\n\r\n";
+        let read = Response::read(&mut chunked.as_bytes()).unwrap();
+        assert_eq!(read.body, "Hello, world");
+
+        let until_closed = "HTTP/1.0 200 OK\r\n\r\n1 2 3\n";
+        assert_eq!(
+            Response::read(&mut until_closed.as_bytes()).unwrap().body,
+            "1 2 3\n"
+        );
+    }
+
+    #[test]
+    fn test_form_encode() {
+        assert_eq!(form_encode("ZAEH 42"), "ZAEH+42");
+        assert_eq!(form_encode("a&b=c"), "a%26b%3Dc");
+    }
+}
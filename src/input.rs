@@ -0,0 +1,183 @@
+//! Loading puzzle inputs as the parsers expect them.
+//!
+//! Inputs saved on Windows or copied from a browser may have `\r\n` line
+//! endings, a byte order mark, spaces at the ends of lines or no line break
+//! at the end. [`normalize`] removes these differences, so that the parsers
+//! only see Unix text. [`Solver::parse_input`](crate::Solver::parse_input)
+//! normalizes every input before it is parsed, and [`Normalizer`] does the
+//! same for inputs that are streamed. Anything else that is left over is the
+//! parsers' to report.
+//!
+//! The path `-` stands for stdin.
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Read};
+
+use crate::{AocError, Result};
+
+/// The path of stdin
+pub const STDIN: &str = "-";
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+fn is_normal(input: &str) -> bool {
+    !input.starts_with(BYTE_ORDER_MARK)
+        && !input.contains('\r')
+        && !input.ends_with("\n\n")
+        && (input.is_empty() || input.ends_with('\n'))
+        && input.lines().all(|line| !line.ends_with([' ', '\t']))
+}
+
+/// `input` without a byte order mark, with `\n` line endings, without
+/// spaces or tabs at the ends of lines and with a single line break at the
+/// end, unless it is empty. Leading whitespace is kept.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    if is_normal(input) {
+        return Cow::Borrowed(input);
+    }
+    let text = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut normalized = String::with_capacity(text.len() + 1);
+    for line in text.lines() {
+        normalized.push_str(line.trim_end_matches([' ', '\t']));
+        normalized.push('\n');
+    }
+    let end = normalized.trim_end_matches('\n').len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+/// [`normalize`] for an input that comes in pieces, which may end anywhere.
+/// Spaces and line breaks are held back until it is clear whether they are
+/// at the end of a line or of the input.
+#[derive(Debug, Default)]
+pub struct Normalizer {
+    started: bool,
+    /// The last character was a `\r`, which may be followed by a `\n`
+    after_cr: bool,
+    /// Spaces and tabs since the last character that was written
+    spaces: String,
+    /// Line breaks since the last character that was written
+    line_breaks: usize,
+    written: bool,
+}
+
+impl Normalizer {
+    /// Appends the normalized `text` to `out`, as far as it is known.
+    pub fn push(&mut self, text: &str, out: &mut String) {
+        for c in text.chars() {
+            if !self.started {
+                self.started = true;
+                if c == BYTE_ORDER_MARK {
+                    continue;
+                }
+            }
+            if self.after_cr {
+                self.after_cr = false;
+                if c == '\n' {
+                    continue;
+                }
+            }
+            match c {
+                '\r' | '\n' => {
+                    self.after_cr = c == '\r';
+                    self.spaces.clear();
+                    self.line_breaks += 1;
+                }
+                ' ' | '\t' => self.spaces.push(c),
+                c => {
+                    out.extend((0..self.line_breaks).map(|_| '\n'));
+                    out.push_str(&self.spaces);
+                    out.push(c);
+                    self.line_breaks = 0;
+                    self.spaces.clear();
+                    self.written = true;
+                }
+            }
+        }
+    }
+
+    /// Appends the end of the normalized input to `out`.
+    pub fn finish(self, out: &mut String) {
+        if self.written {
+            out.push('\n');
+        }
+    }
+}
+
+/// Whether stdin is a pipe, as in `cat input.txt | aoc run 1`. A file
+/// redirected to stdin is not taken for the input, as scripts and CI jobs
+/// often run with some file or `/dev/null` there.
+#[cfg(unix)]
+pub fn stdin_is_piped() -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    fs::metadata("/dev/stdin").is_ok_and(|stdin| stdin.file_type().is_fifo())
+}
+
+#[cfg(not(unix))]
+pub fn stdin_is_piped() -> bool {
+    false
+}
+
+/// Reads and normalizes the input at `path`, or stdin for [`STDIN`].
+pub fn load(path: &str) -> Result<String> {
+    let io_error = |source| AocError::Io {
+        path: path.to_owned(),
+        source,
+    };
+    let contents = match path {
+        STDIN => {
+            let mut contents = String::new();
+            io::stdin()
+                .lock()
+                .read_to_string(&mut contents)
+                .map_err(io_error)?;
+            contents
+        }
+        path => fs::read_to_string(path).map_err(io_error)?,
+    };
+    Ok(normalize(&contents).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let normal = "    [D]\n1 2\n";
+        assert!(matches!(normalize(normal), Cow::Borrowed(_)));
+        assert!(matches!(normalize(""), Cow::Borrowed(_)));
+
+        assert_eq!(normalize("\u{feff}    [D]    \r\n1 2\t\r\n"), normal);
+        assert_eq!(normalize("    [D]\r1 2"), normal);
+        assert_eq!(normalize("    [D]\n1 2\n\n \n"), normal);
+        assert_eq!(normalize("a\n\n\nb\n"), "a\n\n\nb\n");
+        assert_eq!(normalize("\n\r\n"), "");
+    }
+
+    #[test]
+    fn test_normalizer() {
+        let inputs = [
+            "\u{feff}    [D]    \r\n1 2\t\r\n",
+            "    [D]\r1 2",
+            "\n\n a \n\n\t\n",
+            "a\r\r\nb\r",
+            " \u{feff}",
+            "",
+        ];
+        for input in inputs {
+            // One character at a time
+            let mut normalizer = Normalizer::default();
+            let mut out = String::new();
+            for c in input.chars() {
+                normalizer.push(c.encode_utf8(&mut [0; 4]), &mut out);
+            }
+            normalizer.finish(&mut out);
+            assert_eq!(out, normalize(input), "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Sets of integers stored as sorted, disjoint ranges, for puzzles about
+//! section assignments and sensor coverage (days 4 and 15).
+//!
+//! All ranges are inclusive at both ends, like the ranges in the puzzle text.
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Integer types an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + fmt::Debug {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+    /// Number of values in `lo..=hi`, saturating at `u64::MAX`.
+    fn count(lo: Self, hi: Self) -> u64;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(lo: Self, hi: Self) -> u64 {
+                    let count = hi as i128 - lo as i128 + 1;
+                    count.clamp(0, u64::MAX as i128) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, i64, u32, u64, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T = i64> {
+    /// Sorted and neither overlapping nor adjacent, so that every set has a
+    /// single representation.
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Integer> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (lo, hi) = range.into_inner();
+        let ranges = if lo <= hi { vec![(lo, hi)] } else { vec![] };
+        IntervalSet { ranges }
+    }
+}
+
+/// Sorts and merges the ranges in O(n log n).
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let ranges = iter.into_iter().map(RangeInclusive::into_inner).collect();
+        IntervalSet::normalized(ranges)
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: vec![] }
+    }
+
+    fn normalized(mut ranges: Vec<(T, T)>) -> IntervalSet<T> {
+        ranges.retain(|(lo, hi)| lo <= hi);
+        ranges.sort_unstable();
+        let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                // `last_hi` is the maximum when there is no successor
+                Some((_, last_hi)) if last_hi.succ().is_none_or(|next| lo <= next) => {
+                    *last_hi = (*last_hi).max(hi);
+                }
+                _ => merged.push((lo, hi)),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    /// The ranges of the set in increasing order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(lo, hi)| lo..=hi)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set, saturating at `u64::MAX`.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().fold(0, |len: u64, &(lo, hi)| {
+            len.saturating_add(T::count(lo, hi))
+        })
+    }
+
+    /// Adds the values of `range`, merging it with the ranges it overlaps or
+    /// touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lo, mut hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        // Ranges before `first` end before `lo - 1`, those from `last` on
+        // start after `hi + 1`.
+        let first = self
+            .ranges
+            .partition_point(|&(_, b)| b.succ().is_some_and(|next| next < lo));
+        let last = self
+            .ranges
+            .partition_point(|&(a, _)| a.pred().is_none_or(|prev| prev <= hi));
+        if first < last {
+            lo = lo.min(self.ranges[first].0);
+            hi = hi.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(lo, hi)]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, hi)| hi < value);
+        self.ranges.get(i).is_some_and(|&(lo, _)| lo <= value)
+    }
+
+    /// Whether every value of `range` is in the set. True for an empty
+    /// `range`.
+    pub fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
+        let (&lo, &hi) = (range.start(), range.end());
+        if lo > hi {
+            return true;
+        }
+        let i = self.ranges.partition_point(|&(_, b)| b < lo);
+        self.ranges.get(i).is_some_and(|&(a, b)| a <= lo && hi <= b)
+    }
+
+    /// Whether every value of the set is in `other`.
+    pub fn is_subset(&self, other: &IntervalSet<T>) -> bool {
+        self.iter().all(|range| other.contains_range(&range))
+    }
+
+    /// Whether any value of `range` is in the set.
+    pub fn overlaps(&self, range: &RangeInclusive<T>) -> bool {
+        let (&lo, &hi) = (range.start(), range.end());
+        if lo > hi {
+            return false;
+        }
+        let i = self.ranges.partition_point(|&(_, b)| b < lo);
+        self.ranges.get(i).is_some_and(|&(a, _)| a <= hi)
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let ranges = self.ranges.iter().chain(&other.ranges).copied().collect();
+        IntervalSet::normalized(ranges)
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_lo, a_hi)), Some(&(b_lo, b_hi))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (lo, hi) = (a_lo.max(b_lo), a_hi.min(b_hi));
+            if lo <= hi {
+                ranges.push((lo, hi));
+            }
+            // The range ending first cannot overlap anything else
+            if a_hi < b_hi {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The values of the set that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        match (self.ranges.first(), self.ranges.last()) {
+            (Some(&(lo, _)), Some(&(_, hi))) => self.intersection(&other.complement(lo..=hi)),
+            _ => IntervalSet::new(),
+        }
+    }
+
+    /// The values of `bound` that are not in the set.
+    pub fn complement(&self, bound: RangeInclusive<T>) -> IntervalSet<T> {
+        let (lo, hi) = bound.into_inner();
+        let mut ranges = vec![];
+        let mut start = Some(lo);
+        let first = self.ranges.partition_point(|&(_, b)| b < lo);
+        for &(a, b) in self.ranges[first..].iter().take_while(|&&(a, _)| a <= hi) {
+            let Some(from) = start else { break };
+            if from < a {
+                ranges.push((from, a.pred().expect("a > from")));
+            }
+            start = b.succ();
+        }
+        if let Some(from) = start.filter(|&from| from <= hi) {
+            ranges.push((from, hi));
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The ranges missing between the lowest and the highest value of the
+    /// set, in increasing order.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.windows(2).map(|pair| {
+            // Ranges are never adjacent, so both ends exist and lo <= hi
+            let lo = pair[0].1.succ().expect("gap after the maximum");
+            let hi = pair[1].0.pred().expect("gap before the minimum");
+            lo..=hi
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &IntervalSet) -> Vec<RangeInclusive<i64>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_from_ranges() {
+        assert!(set(&[]).is_empty());
+        assert!(set(&[RangeInclusive::new(5, 4)]).is_empty());
+        assert_eq!(ranges(&set(&[0..=13, 15..=20])), vec![0..=13, 15..=20]);
+        // Adjacent and overlapping ranges are merged
+        assert_eq!(ranges(&set(&[0..=14, 15..=20])), vec![0..=20]);
+        assert_eq!(ranges(&set(&[0..=15, 15..=20])), vec![0..=20]);
+        assert_eq!(
+            ranges(&set(&[0..=15, 15..=20, -5..=-2])),
+            vec![-5..=-2, 0..=20]
+        );
+        assert_eq!(
+            ranges(&set(&[0..=15, 15..=20, -5..=-2, -2..=-1])),
+            vec![-5..=20]
+        );
+        assert_eq!(ranges(&set(&[0..=3, 1..=2])), vec![0..=3]);
+        assert_eq!(
+            ranges(&set(&[i64::MAX..=i64::MAX, 0..=i64::MAX])),
+            vec![0..=i64::MAX]
+        );
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[0..=2, 6..=8, 12..=14]);
+        s.insert(4..=4);
+        assert_eq!(ranges(&s), vec![0..=2, 4..=4, 6..=8, 12..=14]);
+        s.insert(3..=5);
+        assert_eq!(ranges(&s), vec![0..=8, 12..=14]);
+        s.insert(9..=11);
+        assert_eq!(ranges(&s), vec![0..=14]);
+        s.insert(-3..=-2);
+        s.insert(RangeInclusive::new(20, 19));
+        assert_eq!(ranges(&s), vec![-3..=-2, 0..=14]);
+        s.insert(i64::MIN..=-1);
+        assert_eq!(ranges(&s), vec![i64::MIN..=14]);
+    }
+
+    #[test]
+    fn test_len_and_contains() {
+        let s = set(&[-5..=-2, 0..=20]);
+        assert_eq!(s.len(), 25);
+        assert!(s.contains(-5) && s.contains(0) && s.contains(20));
+        assert!(!s.contains(-1) && !s.contains(21) && !s.contains(-6));
+        assert!(s.contains_range(&(2..=8)));
+        assert!(!s.contains_range(&(-3..=3)));
+        assert!(s.overlaps(&(-3..=3)));
+        assert!(!s.overlaps(&(-1..=-1)));
+        assert!(!s.overlaps(&(21..=30)));
+        assert_eq!(set(&[i64::MIN..=i64::MAX]).len(), u64::MAX);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25]);
+        assert_eq!(ranges(&a.union(&b)), vec![0..=30]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![5..=10, 20..=25]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..=4, 26..=30]);
+        assert_eq!(ranges(&b.difference(&a)), vec![11..=19]);
+        assert!(a.difference(&a).is_empty());
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!(IntervalSet::new().difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_complement_and_gaps() {
+        let s = set(&[0..=4, 6..=10]);
+        assert_eq!(ranges(&s.complement(0..=20)), vec![5..=5, 11..=20]);
+        assert_eq!(ranges(&s.complement(-3..=2)), vec![-3..=-1]);
+        assert!(s.complement(6..=8).is_empty());
+        assert_eq!(ranges(&IntervalSet::new().complement(1..=3)), vec![1..=3]);
+        assert_eq!(
+            ranges(&set(&[0..=i64::MAX]).complement(i64::MIN..=i64::MAX)),
+            vec![i64::MIN..=-1]
+        );
+        assert_eq!(
+            set(&[0..=1, 3..=3, 7..=9]).gaps().collect::<Vec<_>>(),
+            vec![2..=2, 4..=6]
+        );
+        assert_eq!(set(&[0..=1]).gaps().count(), 0);
+    }
+}
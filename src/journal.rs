@@ -0,0 +1,376 @@
+//! Answers submitted for the real inputs and what the puzzle site said about
+//! them, one file per day and part under `journal/`.
+//!
+//! Each line holds the time in seconds since the Unix epoch, the outcome
+//! (`correct`, `too_high`, `too_low` or `wrong`) and the answer, separated
+//! by tabs.
+//!
+//! Tabs, line breaks and backslashes in an answer are written as `\t`, `\n`
+//! and `\\`. The wrong numbers that were too high or too low leave a range
+//! the right answer must be in, which the runner checks new answers against.
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{parse_number, Answer, AocError, ParseError, Result};
+
+pub const JOURNAL_DIR: &str = "journal";
+
+/// What the puzzle site said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way
+    Wrong,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            _ => Err(format!(
+                "Invalid outcome: {} (expected correct, too_high, too_low or wrong)",
+                s
+            )),
+        }
+    }
+}
+
+/// One line of a journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub outcome: Outcome,
+    pub answer: Answer,
+}
+
+impl Entry {
+    pub fn new(answer: Answer, outcome: Outcome) -> Entry {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Entry {
+            timestamp,
+            outcome,
+            answer,
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let answer = match &self.answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => escape(s),
+        };
+        write!(f, "{}\t{}\t{}", self.timestamp, self.outcome, answer)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(escaped @ ('\\' | 't' | 'n'))) => {
+                chars.next();
+                unescaped.push(match escaped {
+                    't' => '\t',
+                    'n' => '\n',
+                    _ => '\\',
+                });
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+fn parse_entry(input: &str, line: &str) -> std::result::Result<Entry, ParseError> {
+    let fields: Vec<&str> = line.splitn(3, '\t').collect();
+    if fields.len() != 3 {
+        return Err(ParseError::at(
+            input,
+            line,
+            "timestamp, outcome and answer separated by tabs",
+        ));
+    }
+    // Numbers are told apart from text the same way as in answers.toml
+    let answer = match fields[2].parse() {
+        Ok(n) => Answer::Number(n),
+        Err(_) => Answer::Text(unescape(fields[2])),
+    };
+    Ok(Entry {
+        timestamp: parse_number(input, fields[0])?,
+        outcome: fields[1]
+            .parse()
+            .map_err(|_| ParseError::at(input, fields[1], "correct, too_high, too_low or wrong"))?,
+        answer,
+    })
+}
+
+/// Why an answer cannot be the right one, going by the journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// The same answer was submitted before
+    Submitted(Outcome),
+    /// At or above an answer that was too high
+    TooHigh { high: i64 },
+    /// At or below an answer that was too low
+    TooLow { low: i64 },
+    /// A different answer was correct
+    NotCorrect(Answer),
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Conflict::Submitted(Outcome::Correct) => write!(f, "it is correct"),
+            Conflict::Submitted(Outcome::Wrong) => write!(f, "it was submitted before and wrong"),
+            Conflict::Submitted(outcome) => write!(
+                f,
+                "it was submitted before and {}",
+                outcome.to_string().replace('_', " ")
+            ),
+            Conflict::TooHigh { high } => write!(f, "{} was too high already", high),
+            Conflict::TooLow { low } => write!(f, "{} was too low already", low),
+            Conflict::NotCorrect(answer) => write!(f, "the correct answer is {}", answer),
+        }
+    }
+}
+
+/// Answers submitted for one part, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    pub entries: Vec<Entry>,
+}
+
+impl Journal {
+    pub fn parse(input: &str) -> Result<Journal> {
+        let mut entries = vec![];
+        for line in input.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            entries.push(parse_entry(input, line)?);
+        }
+        Ok(Journal { entries })
+    }
+
+    /// A missing file is an empty journal.
+    pub fn load(path: &str) -> Result<Journal> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Journal::default()),
+            Err(source) => {
+                return Err(AocError::Io {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        };
+        Journal::parse(&contents).map_err(|e| e.in_file(path))
+    }
+
+    /// Appends `entry` to the file at `path`, creating its directory if needed.
+    pub fn append(path: &str, entry: &Entry) -> Result<()> {
+        let io_error = |source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        };
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(io_error)?;
+        writeln!(file, "{}", entry).map_err(io_error)
+    }
+
+    pub fn correct(&self) -> Option<&Answer> {
+        self.entries
+            .iter()
+            .find(|e| e.outcome == Outcome::Correct)
+            .map(|e| &e.answer)
+    }
+
+    /// The highest answer that was too low and the lowest one that was too
+    /// high. The right answer is strictly between them.
+    pub fn bounds(&self) -> (Option<i64>, Option<i64>) {
+        let numbers = |outcome| {
+            self.entries.iter().filter_map(move |e| match e.answer {
+                Answer::Number(n) if e.outcome == outcome => Some(n),
+                _ => None,
+            })
+        };
+        (
+            numbers(Outcome::TooLow).max(),
+            numbers(Outcome::TooHigh).min(),
+        )
+    }
+
+    /// Why `answer` cannot be right, or `None` if it may be.
+    pub fn check(&self, answer: &Answer) -> Option<Conflict> {
+        // Pictures are submitted as the letters they show
+        if matches!(answer, Answer::Text(text) if text.contains('\n')) {
+            return None;
+        }
+        if let Some(correct) = self.correct() {
+            return (correct != answer).then(|| Conflict::NotCorrect(correct.clone()));
+        }
+        if let Some(entry) = self.entries.iter().find(|e| &e.answer == answer) {
+            return Some(Conflict::Submitted(entry.outcome));
+        }
+        let Answer::Number(n) = *answer else {
+            return None;
+        };
+        match self.bounds() {
+            (Some(low), _) if n <= low => Some(Conflict::TooLow { low }),
+            (_, Some(high)) if n >= high => Some(Conflict::TooHigh { high }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Journal {
+    /// The submitted answers and what is left of the range
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.entries.is_empty() {
+            return write!(f, "  Nothing submitted");
+        }
+        for entry in &self.entries {
+            writeln!(f, "  {:<8}  {}", entry.outcome, entry.answer)?;
+        }
+        if let Some(correct) = self.correct() {
+            return write!(f, "  Solved: {}", correct);
+        }
+        match self.bounds() {
+            (Some(low), Some(high)) if high - low <= 1 => {
+                write!(f, "  No answer left between {} and {}", low, high)
+            }
+            (Some(low), Some(high)) => write!(f, "  Answer between {} and {}", low, high),
+            (Some(low), None) => write!(f, "  Answer above {}", low),
+            (None, Some(high)) => write!(f, "  Answer below {}", high),
+            (None, None) => write!(f, "  No bounds known"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journal(entries: &[(Outcome, Answer)]) -> Journal {
+        Journal {
+            entries: entries
+                .iter()
+                .map(|(outcome, answer)| Entry {
+                    timestamp: 1671000000,
+                    outcome: *outcome,
+                    answer: answer.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let entries = journal(&[
+            (Outcome::TooHigh, Answer::Number(7305)),
+            (Outcome::Wrong, Answer::from("A\tB\\n\nC")),
+        ])
+        .entries;
+        let text: String = entries.iter().map(|e| format!("{}\n", e)).collect();
+        assert_eq!(
+            text,
+            "1671000000\ttoo_high\t7305\n1671000000\twrong\tA\\tB\\\\n\\nC\n"
+        );
+        assert_eq!(
+            Journal::parse(&format!("# comment\n{}", text))
+                .unwrap()
+                .entries,
+            entries
+        );
+
+        let e = Journal::parse("1671000000\ttoo_big\t7\n").unwrap_err();
+        match e {
+            AocError::Parse(e) => assert_eq!((e.line, e.column), (1, 12)),
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut journal = journal(&[
+            (Outcome::TooHigh, Answer::Number(7305)),
+            (Outcome::TooLow, Answer::Number(6120)),
+            (Outcome::TooHigh, Answer::Number(7000)),
+            (Outcome::Wrong, Answer::Number(6500)),
+        ]);
+        assert_eq!(journal.bounds(), (Some(6120), Some(7000)));
+        assert_eq!(
+            journal.to_string().lines().last(),
+            Some("  Answer between 6120 and 7000")
+        );
+        assert_eq!(
+            journal.check(&Answer::Number(7000)),
+            Some(Conflict::Submitted(Outcome::TooHigh))
+        );
+        assert_eq!(
+            journal.check(&Answer::Number(7200)),
+            Some(Conflict::TooHigh { high: 7000 })
+        );
+        assert_eq!(
+            journal.check(&Answer::Number(6000)),
+            Some(Conflict::TooLow { low: 6120 })
+        );
+        assert_eq!(
+            journal.check(&Answer::Number(6500)),
+            Some(Conflict::Submitted(Outcome::Wrong))
+        );
+        assert_eq!(journal.check(&Answer::Number(6900)), None);
+        assert_eq!(journal.check(&Answer::from("EHZFZHCZ")), None);
+        assert_eq!(journal.check(&Answer::from("#..#\n####")), None);
+
+        journal
+            .entries
+            .push(Entry::new(Answer::Number(6900), Outcome::Correct));
+        assert_eq!(journal.check(&Answer::Number(6900)), None);
+        assert_eq!(
+            journal.check(&Answer::Number(6901)),
+            Some(Conflict::NotCorrect(Answer::Number(6900)))
+        );
+        assert_eq!(journal.to_string().lines().last(), Some("  Solved: 6900"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Just enough JSON writing for machine-readable output, and reading of the
+//! flat objects it writes.
+use std::fmt;
+
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, take};
+use nom::character::complete::{char, digit1};
+use nom::combinator::{all_consuming, map, map_opt, opt, recognize, value};
+use nom::multi::{fold_many0, separated_list0};
+use nom::sequence::{delimited, pair, preceded, separated_pair, tuple};
+use nom::IResult;
+
+use crate::Answer;
+
+/// `s` as a quoted JSON string.
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Numeric answers are JSON numbers, the rest are strings.
+pub fn answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => string(s),
+    }
+}
+
+/// A JSON object written on a single line, fields in insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct Object {
+    fields: Vec<(String, String)>,
+}
+
+impl Object {
+    pub fn new() -> Object {
+        Object::default()
+    }
+
+    /// Adds a field whose value is already JSON.
+    pub fn raw(mut self, key: &str, value: impl Into<String>) -> Object {
+        self.fields.push((key.to_owned(), value.into()));
+        self
+    }
+
+    pub fn string(self, key: &str, value: &str) -> Object {
+        self.raw(key, string(value))
+    }
+
+    pub fn number(self, key: &str, value: impl fmt::Display) -> Object {
+        self.raw(key, value.to_string())
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}:{}", string(key), value)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// A value of an object read by [`parse_object`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// As written, so that big integers keep their digits
+    Number(String),
+    String(String),
+}
+
+impl Value {
+    /// The inverse of [`answer`]
+    pub fn to_answer(&self) -> Option<Answer> {
+        match self {
+            Value::Number(n) => n.parse().ok().map(Answer::Number),
+            Value::String(s) => Some(Answer::Text(s.clone())),
+        }
+    }
+}
+
+fn escape(input: &str) -> IResult<&str, String> {
+    preceded(
+        char('\\'),
+        alt((
+            value("\"".to_owned(), char('"')),
+            value("\\".to_owned(), char('\\')),
+            value("/".to_owned(), char('/')),
+            value("\n".to_owned(), char('n')),
+            value("\r".to_owned(), char('r')),
+            value("\t".to_owned(), char('t')),
+            map_opt(preceded(char('u'), take(4usize)), |hex: &str| {
+                let c = char::from_u32(u32::from_str_radix(hex, 16).ok()?)?;
+                Some(c.to_string())
+            }),
+        )),
+    )(input)
+}
+
+fn string_literal(input: &str) -> IResult<&str, String> {
+    delimited(
+        char('"'),
+        fold_many0(
+            alt((map(is_not("\"\\"), str::to_owned), escape)),
+            String::new,
+            |mut s, part| {
+                s.push_str(&part);
+                s
+            },
+        ),
+        char('"'),
+    )(input)
+}
+
+fn number(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((
+        opt(char('-')),
+        digit1,
+        opt(pair(char('.'), digit1)),
+    )))(input)
+}
+
+/// Reads an object of numbers and strings like those [`Object`] writes,
+/// without nesting or white space. `None` if it is anything else.
+pub fn parse_object(input: &str) -> Option<Vec<(String, Value)>> {
+    let field = separated_pair(
+        string_literal,
+        char(':'),
+        alt((
+            map(number, |n| Value::Number(n.to_owned())),
+            map(string_literal, Value::String),
+        )),
+    );
+    let object = delimited(char('{'), separated_list0(char(','), field), char('}'));
+    all_consuming(object)(input.trim_end())
+        .ok()
+        .map(|(_, fields)| fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object() {
+        let object = Object::new()
+            .number("day", 10)
+            .raw("answer", answer(&Answer::from("#.\n.#")))
+            .string("input", "a \"quoted\"\\path");
+        assert_eq!(
+            object.to_string(),
+            r##"{"day":10,"answer":"#.\n.#","input":"a \"quoted\"\\path"}"##
+        );
+        assert_eq!(answer(&Answer::Number(-720)), "-720");
+    }
+
+    #[test]
+    fn test_parse_object() {
+        let object = Object::new()
+            .number("day", 10)
+            .raw("answer", answer(&Answer::from("#.\n.#\u{1}")))
+            .number("elapsed_ms", "-0.125")
+            .string("input", "a \"quoted\"\\path");
+        let fields = parse_object(&object.to_string()).unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                ("day".to_owned(), Value::Number("10".to_owned())),
+                ("answer".to_owned(), Value::String("#.\n.#\u{1}".to_owned())),
+                ("elapsed_ms".to_owned(), Value::Number("-0.125".to_owned())),
+                (
+                    "input".to_owned(),
+                    Value::String("a \"quoted\"\\path".to_owned())
+                ),
+            ]
+        );
+        assert_eq!(fields[0].1.to_answer(), Some(Answer::Number(10)));
+        assert_eq!(parse_object("{}\n"), Some(vec![]));
+        assert_eq!(parse_object("{\"a\":[1]}"), None);
+        assert_eq!(parse_object("{\"a\":1} x"), None);
+    }
+}
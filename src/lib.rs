@@ -0,0 +1,34 @@
+//! Solutions to Advent of Code 2022
+//! https://adventofcode.com/2022
+pub mod animate;
+pub mod bench;
+pub mod budget;
+pub mod calendar;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod compare;
+pub mod days;
+mod error;
+pub mod generate;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod interval;
+pub mod journal;
+pub mod json;
+pub mod log;
+pub mod manifest;
+pub mod params;
+pub mod parse;
+pub mod render;
+pub mod scaffold;
+pub mod search;
+pub mod serve;
+mod solution;
+pub mod stream;
+mod toml;
+pub mod watch;
+
+pub use days::{Day, DAYS};
+pub use error::{parse_number, AocError, ParseError, Result};
+pub use solution::{Answer, Part, Solution, Solver};
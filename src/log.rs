@@ -0,0 +1,267 @@
+//! Log messages with levels, written to stderr so that stdout carries only
+//! the answers.
+//!
+//! The target of a message is the module it comes from, e.g.
+//! `day_15_beacon_exclusion_zone` or `serve`. A filter has a default level
+//! and levels for targets, which also match the targets that continue them
+//! after a `_`:
+//!
+//! ```text
+//! AOC_LOG=info,day_15=debug,day_10=trace
+//! ```
+//!
+//! Only warnings and errors are written by default. The runner's `-v` and
+//! `-q` flags raise or lower the default level.
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// The environment variable with the filter
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// From the most to the least important. A filter at a level lets the
+/// messages of that level and the more important ones through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 6] = [
+        Level::Off,
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    /// `steps` levels more verbose, or less if negative
+    pub fn shifted(self, steps: i32) -> Level {
+        let index = (self as i32 + steps).clamp(0, Level::ALL.len() as i32 - 1);
+        Level::ALL[index as usize]
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Off => "OFF",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid log level: {} (expected off, error, warn, info, debug or trace)",
+                    s
+                )
+            })
+    }
+}
+
+/// Which messages are written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub level: Level,
+    /// Levels of targets like `day_15` (matching `day_15_beacon_exclusion_zone`),
+    /// the last match wins
+    pub targets: Vec<(String, Level)>,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter {
+            level: Level::Warn,
+            targets: vec![],
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    /// A comma-separated list of a level and `target=level` pairs
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => filter
+                    .targets
+                    .push((target.trim().to_owned(), level.trim().parse()?)),
+                None => filter.level = directive.parse()?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl Filter {
+    pub fn level_of(&self, target: &str) -> Level {
+        self.targets
+            .iter()
+            .rev()
+            .find(|(name, _)| {
+                target
+                    .strip_prefix(name.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+            })
+            .map_or(self.level, |&(_, level)| level)
+    }
+
+    /// The most verbose level of any target
+    fn max_level(&self) -> Level {
+        self.targets
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.level, Level::max)
+    }
+}
+
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+/// [`Filter::max_level`] of the filter, so that most messages are dropped
+/// without taking the lock. Warnings by default.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+/// Replaces the filter of the whole process.
+pub fn set_filter(filter: Filter) {
+    MAX_LEVEL.store(filter.max_level() as u8, Ordering::Relaxed);
+    *FILTER.write().unwrap_or_else(|e| e.into_inner()) = Some(filter);
+}
+
+/// The filter from `AOC_LOG`, with the default level moved by `verbosity`
+/// (the number of `-v` minus the number of `-q`). A malformed `AOC_LOG` is
+/// an error.
+pub fn init(verbosity: i32) -> Result<(), String> {
+    let mut filter: Filter = match std::env::var(LOG_ENV) {
+        Ok(filter) => filter
+            .parse()
+            .map_err(|e| format!("{} in {}", e, LOG_ENV))?,
+        Err(_) => Filter::default(),
+    };
+    filter.level = filter.level.shifted(verbosity);
+    set_filter(filter);
+    Ok(())
+}
+
+/// The last segment of a module path, e.g. `day_15_beacon_exclusion_zone`
+pub fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    let filter = FILTER.read().unwrap_or_else(|e| e.into_inner());
+    let level_of_target = match &*filter {
+        Some(filter) => filter.level_of(target(module_path)),
+        None => Filter::default().level,
+    };
+    level <= level_of_target
+}
+
+/// Writes a message that passed the filter. Use the macros instead, which
+/// check the filter before formatting it.
+pub fn write(level: Level, module_path: &str, message: fmt::Arguments) {
+    let _ = writeln!(
+        io::stderr().lock(),
+        "{:<5} {}: {}",
+        level,
+        target(module_path),
+        message
+    );
+}
+
+/// Logs at the given level from the module it is called in:
+/// `log!(Level::Debug, "{} sensors", sensors.len())`
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level, module_path!()) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "info, day_1=trace,day_15=debug,day_10=off,day_10_c=trace"
+            .parse()
+            .unwrap();
+        assert_eq!(filter.level, Level::Info);
+        assert_eq!(filter.level_of("serve"), Level::Info);
+        assert_eq!(
+            filter.level_of("day_15_beacon_exclusion_zone"),
+            Level::Debug
+        );
+        assert_eq!(filter.level_of("day_11_monkey_in_the_middle"), Level::Info);
+        assert_eq!(filter.level_of("day_10_cathode_ray_tube"), Level::Off);
+        assert_eq!(filter.max_level(), Level::Trace);
+
+        assert_eq!("".parse(), Ok(Filter::default()));
+        assert!("day_15=loud".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(Level::Warn.shifted(2), Level::Debug);
+        assert_eq!(Level::Warn.shifted(5), Level::Trace);
+        assert_eq!(Level::Warn.shifted(-3), Level::Off);
+        assert_eq!("Debug".parse(), Ok(Level::Debug));
+        assert_eq!(format!("{:<5}|", Level::Warn), "WARN |");
+        assert_eq!(
+            target("advent_of_code_2022::days::day_15_beacon_exclusion_zone"),
+            "day_15_beacon_exclusion_zone"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Expected answers, kept in `answers.toml` next to the inputs.
+//!
+//! The manifest has one table per input file:
+//!
+//! ```toml
+//! ["test_inputs/day_01_calorie_counting.txt"]
+//! day = 1
+//! part1 = 24000
+//! part2 = 45000
+//! ```
+//!
+//! Answers are integers or strings; multi-line answers (pictures) use
+//! `"""` strings. A part without a known answer is left out.
+use std::fs;
+
+use crate::toml::{self, Value};
+use crate::{Answer, AocError, ParseError, Part, Result};
+
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+/// Expected answers for one input file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    /// Path of the input, relative to the repository root
+    pub input: String,
+    pub day: u8,
+    pub part_one: Option<Answer>,
+    pub part_two: Option<Answer>,
+}
+
+impl Expected {
+    pub fn answer(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part_one.as_ref(),
+            Part::Two => self.part_two.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    pub entries: Vec<Expected>,
+}
+
+impl Manifest {
+    pub fn parse(input: &str) -> Result<Manifest> {
+        let mut entries = vec![];
+        for table in toml::parse(input)? {
+            let mut expected = Expected {
+                input: table.name.to_owned(),
+                day: 0,
+                part_one: None,
+                part_two: None,
+            };
+            for (key, value) in table.entries {
+                let answer = match value {
+                    Value::Integer(n) => Answer::Number(n),
+                    Value::String(s) => Answer::Text(s),
+                };
+                match (key, answer) {
+                    ("day", Answer::Number(day)) if (1..=25).contains(&day) => {
+                        expected.day = day as u8
+                    }
+                    ("day", _) => {
+                        return Err(ParseError::at(input, key, "day between 1 and 25").into())
+                    }
+                    ("part1", answer) => expected.part_one = Some(answer),
+                    ("part2", answer) => expected.part_two = Some(answer),
+                    _ => return Err(ParseError::at(input, key, "day, part1 or part2").into()),
+                }
+            }
+            if expected.day == 0 {
+                return Err(ParseError::after(input, table.header, "day = <day>").into());
+            }
+            entries.push(expected);
+        }
+        Ok(Manifest { entries })
+    }
+
+    pub fn load(path: &str) -> Result<Manifest> {
+        let contents = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        Manifest::parse(&contents).map_err(|e| e.in_file(path))
+    }
+
+    /// Expected answers for the input at `path`.
+    pub fn find(&self, path: &str) -> Option<&Expected> {
+        let path = path.trim_start_matches("./");
+        self.entries.iter().find(|e| e.input == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest::parse(
+            "# Comment\n\
+             [\"test_inputs/day_05_supply_stacks.txt\"]\n\
+             day = 5\n\
+             part1 = \"CMZ\"\n\
+             \n\
+             [\"test_inputs/day_10_cathode_ray_tube_3.txt\"]\n\
+             day = 10\n\
+             part1 = -720\n\
+             part2 = \"\"\"\n##..\n..##\"\"\"\n",
+        )
+        .unwrap();
+
+        let day_5 = manifest
+            .find("./test_inputs/day_05_supply_stacks.txt")
+            .unwrap();
+        assert_eq!(day_5.day, 5);
+        assert_eq!(day_5.answer(Part::One), Some(&Answer::from("CMZ")));
+        assert_eq!(day_5.answer(Part::Two), None);
+
+        let day_10 = manifest
+            .find("test_inputs/day_10_cathode_ray_tube_3.txt")
+            .unwrap();
+        assert_eq!(day_10.part_one, Some(Answer::Number(-720)));
+        assert_eq!(day_10.part_two, Some(Answer::from("##..\n..##")));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = Manifest::parse("[\"a.txt\"]\nday = 1\npart3 = 2\n").unwrap_err();
+        match e {
+            AocError::Parse(e) => assert_eq!((e.line, e.column), (3, 1)),
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Overrides of the puzzle constants, see [`Solution::set_param`].
+//!
+//! Some constants differ between the example and the real input, so the
+//! overrides in `params.toml` apply to a single input file:
+//!
+//! ```toml
+//! ["test_inputs/day_15_beacon_exclusion_zone.txt"]
+//! interesting_row = 10
+//! search_space = 20
+//! ```
+//!
+//! The days with several [strategies](crate::Solution::strategies) take the
+//! name of one as the parameter [`STRATEGY`], e.g. `--param strategy=cycles`.
+//!
+//! [`Solution::set_param`]: crate::Solution::set_param
+use std::fs;
+use std::str::FromStr;
+
+use crate::toml;
+use crate::{AocError, Result};
+
+pub const PARAMS_PATH: &str = "params.toml";
+
+/// The parameter that picks the strategy of a day
+pub const STRATEGY: &str = "strategy";
+
+/// Parameter names and values, applied in order
+pub type Overrides = Vec<(String, String)>;
+
+#[derive(Debug, Clone, Default)]
+pub struct ParamsConfig {
+    /// Overrides per input file
+    pub inputs: Vec<(String, Overrides)>,
+}
+
+impl ParamsConfig {
+    pub fn parse(input: &str) -> Result<ParamsConfig> {
+        let inputs = toml::parse(input)?
+            .into_iter()
+            .map(|table| {
+                let overrides = table
+                    .entries
+                    .into_iter()
+                    .map(|(name, value)| (name.to_owned(), value.to_string()))
+                    .collect();
+                (table.name.to_owned(), overrides)
+            })
+            .collect();
+        Ok(ParamsConfig { inputs })
+    }
+
+    pub fn load(path: &str) -> Result<ParamsConfig> {
+        let contents = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        ParamsConfig::parse(&contents).map_err(|e| e.in_file(path))
+    }
+
+    /// Overrides for the input at `path`, empty if there are none.
+    pub fn for_input(&self, path: &str) -> Overrides {
+        let path = path.trim_start_matches("./");
+        self.inputs
+            .iter()
+            .filter(|(input, _)| input == path)
+            .flat_map(|(_, overrides)| overrides.iter().cloned())
+            .collect()
+    }
+}
+
+/// Parses a `name=value` command line override.
+pub fn parse_override(arg: &str) -> std::result::Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!("Invalid parameter: {} (expected name=value)", arg)),
+    }
+}
+
+/// Parses the value of the parameter `name`.
+pub fn value<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| AocError::Param(format!("Invalid value for {}: {}", name, value)))
+}
+
+/// Error for a parameter that is not one of `available`.
+pub fn unknown(name: &str, available: &[(&str, String)]) -> AocError {
+    if available.is_empty() {
+        return AocError::Param(format!(
+            "Unknown parameter: {} (this day has no parameters)",
+            name
+        ));
+    }
+    let names: Vec<&str> = available.iter().map(|(name, _)| *name).collect();
+    AocError::Param(format!(
+        "Unknown parameter: {} (expected one of {})",
+        name,
+        names.join(", ")
+    ))
+}
+
+/// The names of `strategies`, the default first
+pub fn strategy_names<T>(strategies: &[(&'static str, T)]) -> Vec<&'static str> {
+    strategies.iter().map(|(name, _)| *name).collect()
+}
+
+/// The name of `strategy` in `strategies`
+pub fn strategy_name<T: PartialEq>(strategy: T, strategies: &[(&'static str, T)]) -> String {
+    strategies
+        .iter()
+        .find(|(_, s)| *s == strategy)
+        .map_or("", |(name, _)| name)
+        .to_owned()
+}
+
+/// Parses the value of the [`STRATEGY`] parameter, one of the names of
+/// `strategies`.
+pub fn strategy<T: Copy>(value: &str, strategies: &[(&'static str, T)]) -> Result<T> {
+    match strategies.iter().find(|(name, _)| *name == value) {
+        Some(&(_, strategy)) => Ok(strategy),
+        None => Err(AocError::Param(format!(
+            "Unknown strategy: {} (expected one of {})",
+            value,
+            strategy_names(strategies).join(", ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_input() {
+        let config = ParamsConfig::parse(
+            "[\"test_inputs/day_15_beacon_exclusion_zone.txt\"]\n\
+             interesting_row = 10\n\
+             search_space = 20\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.for_input("./test_inputs/day_15_beacon_exclusion_zone.txt"),
+            vec![
+                ("interesting_row".to_owned(), "10".to_owned()),
+                ("search_space".to_owned(), "20".to_owned())
+            ]
+        );
+        assert!(config
+            .for_input("inputs/day_15_beacon_exclusion_zone.txt")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("rounds=20"),
+            Ok(("rounds".to_owned(), "20".to_owned()))
+        );
+        assert!(parse_override("rounds").is_err());
+        assert!(parse_override("=20").is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! nom combinators shared by the input parsers.
+//!
+//! The parsers label what they are looking for with [`context`], and
+//! [`line`] and [`lines`] turn the innermost label of a failure into a
+//! [`ParseError`]:
+//!
+//! ```
+//! use advent_of_code_2022::parse::{self, coords};
+//! use nom::bytes::complete::tag;
+//! use nom::sequence::preceded;
+//!
+//! let input = "Sensor at x=2, y=18\nSensor at x=9, y=?\n";
+//! let e = parse::lines(input, preceded(tag("Sensor at "), coords)).unwrap_err();
+//! assert_eq!((e.line, e.column), (2, 18));
+//! assert_eq!(e.expected, "number");
+//! ```
+use std::str::FromStr;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, multispace0, one_of, space0};
+use nom::combinator::{all_consuming, cut, map_res, opt, recognize};
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
+use nom::Parser;
+
+pub use nom::error::context;
+
+use crate::ParseError;
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Unsigned integer, e.g. `42`
+pub fn uint<T: FromStr>(input: &str) -> IResult<'_, T> {
+    context("number", map_res(digit1, str::parse))(input)
+}
+
+/// Integer with an optional sign, e.g. `-7`
+pub fn int<T: FromStr>(input: &str) -> IResult<'_, T> {
+    context(
+        "number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+/// `x=…, y=…`
+pub fn coords(input: &str) -> IResult<'_, (i64, i64)> {
+    context(
+        "coordinates (x=.., y=..)",
+        separated_pair(
+            preceded(tag("x="), int),
+            tag(", "),
+            preceded(tag("y="), int),
+        ),
+    )(input)
+}
+
+/// One or more `item`s separated by `separator`. An item has to follow
+/// every separator, so a bad item is reported rather than ending the list
+/// before it.
+pub fn list<'a, O, S>(
+    mut separator: impl Parser<&'a str, S, VerboseError<&'a str>>,
+    mut item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = separator.parse(input) {
+            let (rest, next) = cut(|i| item.parse(i))(rest)?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// One or more `item`s separated by commas, with or without spaces after
+/// them.
+pub fn comma_list<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    list(pair(char(','), space0), item)
+}
+
+/// `value` after `label`, e.g. `Starting items: 79, 98`. Leading spaces and
+/// the spaces after the label are skipped.
+pub fn labelled<'a, O>(
+    label: &'static str,
+    value: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<'a, O> {
+    context(label, preceded(tuple((space0, tag(label), space0)), value))
+}
+
+/// The error of a failed line parser, located in `input`.
+///
+/// The innermost context label is what was expected. Without one, the error
+/// is described by what nom was looking for.
+pub fn error(input: &str, err: nom::Err<VerboseError<&str>>) -> ParseError {
+    located(input, err, "end of line")
+}
+
+/// `end` describes what `all_consuming` expects after the parsed value.
+fn located(input: &str, err: nom::Err<VerboseError<&str>>, end: &str) -> ParseError {
+    let errors = match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e.errors,
+        nom::Err::Incomplete(_) => return ParseError::at_offset(input, input.len(), "more input"),
+    };
+    let label = errors.iter().find_map(|(at, kind)| match kind {
+        VerboseErrorKind::Context(label) => Some((*at, label.to_string())),
+        _ => None,
+    });
+    match (label, errors.first()) {
+        (Some((at, label)), _) => ParseError::at(input, at, label),
+        (None, Some((at, kind))) => {
+            let expected = match kind {
+                VerboseErrorKind::Char(c) => format!("'{}'", c),
+                VerboseErrorKind::Nom(ErrorKind::Eof) => end.to_owned(),
+                VerboseErrorKind::Nom(ErrorKind::Digit) => "number".to_owned(),
+                _ => "valid input".to_owned(),
+            };
+            ParseError::at(input, at, expected)
+        }
+        (None, None) => ParseError::at_offset(input, 0, "valid input"),
+    }
+}
+
+/// Parses the whole of `line`, a slice of `input`.
+pub fn line<'a, O>(
+    input: &str,
+    line: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> Result<O, ParseError> {
+    all_consuming(parser)(line)
+        .map(|(_, value)| value)
+        .map_err(|e| error(input, e))
+}
+
+/// Parses every line of `input`, stopping at the first one that fails.
+pub fn lines<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> Result<Vec<O>, ParseError> {
+    input
+        .lines()
+        .map(|l| line(input, l, |i| parser.parse(i)))
+        .collect()
+}
+
+/// Parses the whole `input`, allowing trailing whitespace.
+pub fn whole<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> Result<O, ParseError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, value)| value)
+        .map_err(|e| located(input, e, "end of input"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(uint::<u8>("42 rest"), Ok((" rest", 42)));
+        assert_eq!(int::<i64>("-7,"), Ok((",", -7)));
+        assert_eq!(int::<i64>("+7"), Ok(("", 7)));
+        assert!(uint::<u8>("-7").is_err());
+        // Too big for the type
+        assert!(uint::<u8>("300").is_err());
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(coords("x=-2, y=15:"), Ok((":", (-2, 15))));
+        assert_eq!(comma_list(uint::<u32>)("1,2, 3"), Ok(("", vec![1, 2, 3])));
+        assert_eq!(
+            list(tag(" -> "), uint::<u32>)("4 -> 9;"),
+            Ok((";", vec![4, 9]))
+        );
+        assert_eq!(
+            labelled("Starting items:", comma_list(uint::<u64>))("  Starting items: 79, 98"),
+            Ok(("", vec![79, 98]))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "1,2\n3,x\n";
+        let e = lines(input, comma_list(uint::<u32>)).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "number"));
+
+        let e = lines("1,2 \n", comma_list(uint::<u32>)).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (1, 4, "end of line")
+        );
+
+        let input = "Items: 1\nItems 2\n";
+        let e = lines(input, labelled("Items:", uint::<u32>)).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 1, "Items:"));
+
+        assert_eq!(whole("1, 2\n\n", comma_list(uint::<u32>)), Ok(vec![1, 2]));
+        let e = whole("1, 2\nx", comma_list(uint::<u32>)).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 1, "end of input")
+        );
+    }
+}
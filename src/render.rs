@@ -0,0 +1,348 @@
+//! Pictures of puzzle states, written as PBM, PPM or PNG images.
+//!
+//! A [`Picture`] holds an intensity between 0 and 1 per cell, and a
+//! [`Palette`] turns the intensities into colours. The PNG writer stores
+//! the pixels uncompressed, which keeps it free of dependencies.
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::{AocError, Result};
+
+pub type Rgb = [u8; 3];
+
+/// Intensities between 0 and 1, see [`crate::Solution::render`].
+pub type Picture = Grid<f64>;
+
+/// Named palettes for `--palette`
+pub const PALETTES: [(&str, &[Rgb]); 4] = [
+    ("grey", &[[0, 0, 0], [255, 255, 255]]),
+    (
+        "heat",
+        &[[0, 0, 0], [192, 32, 0], [255, 208, 0], [255, 255, 255]],
+    ),
+    (
+        "terrain",
+        &[
+            [16, 48, 128],
+            [48, 144, 64],
+            [176, 144, 80],
+            [255, 255, 255],
+        ],
+    ),
+    ("paper", &[[255, 255, 255], [0, 0, 0]]),
+];
+
+/// Colours that intensities from 0 to 1 are interpolated between, evenly
+/// spaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    stops: Vec<Rgb>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            stops: PALETTES[0].1.to_vec(),
+        }
+    }
+}
+
+impl Palette {
+    /// At least one colour is required.
+    pub fn new(stops: Vec<Rgb>) -> Palette {
+        assert!(!stops.is_empty(), "Palette without colours");
+        Palette { stops }
+    }
+
+    /// The colour of `intensity`, clamped to 0..=1.
+    pub fn colour(&self, intensity: f64) -> Rgb {
+        let last = self.stops.len() - 1;
+        if last == 0 {
+            return self.stops[0];
+        }
+        let position = intensity.clamp(0.0, 1.0) * last as f64;
+        let i = (position.floor() as usize).min(last - 1);
+        let t = position - i as f64;
+        let (from, to) = (self.stops[i], self.stops[i + 1]);
+        [0, 1, 2].map(|c| (from[c] as f64 + (to[c] as f64 - from[c] as f64) * t).round() as u8)
+    }
+}
+
+fn parse_colour(s: &str) -> Option<Rgb> {
+    let hex = s.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// A palette name or colours like `#000000,#ff8000`
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if let Some((_, stops)) = PALETTES.iter().find(|(name, _)| *name == s) {
+            return Ok(Palette::new(stops.to_vec()));
+        }
+        let names: Vec<&str> = PALETTES.iter().map(|(name, _)| *name).collect();
+        let invalid = || {
+            format!(
+                "Invalid palette: {} (expected one of {} or colours like #000000,#ff8000)",
+                s,
+                names.join(", ")
+            )
+        };
+        let stops = s
+            .split(',')
+            .map(|colour| parse_colour(colour.trim()).ok_or_else(invalid))
+            .collect::<std::result::Result<Vec<Rgb>, String>>()?;
+        Ok(Palette::new(stops))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Pbm,
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    /// The format of a file name with the extension `.pbm`, `.ppm` or `.png`
+    pub fn from_path(path: &str) -> Option<ImageFormat> {
+        let (_, extension) = path.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "pbm" => Some(ImageFormat::Pbm),
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageFormat::Pbm => write!(f, "PBM"),
+            ImageFormat::Ppm => write!(f, "PPM"),
+            ImageFormat::Png => write!(f, "PNG"),
+        }
+    }
+}
+
+/// RGB pixels, row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Draws every cell of `picture` as a `scale` by `scale` square.
+    pub fn new(picture: &Picture, palette: &Palette, scale: usize) -> Image {
+        let scale = scale.max(1);
+        let mut pixels = Vec::with_capacity(picture.width() * picture.height() * scale * scale);
+        for row in picture.rows() {
+            let colours: Vec<Rgb> = row
+                .iter()
+                .flat_map(|&intensity| [palette.colour(intensity)].repeat(scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&colours);
+            }
+        }
+        Image {
+            width: picture.width() * scale,
+            height: picture.height() * scale,
+            pixels,
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    /// Black and white, the dark pixels black
+    pub fn write_pbm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P4\n{} {}\n", self.width, self.height)?;
+        for row in self.rows() {
+            let mut bits = vec![0_u8; self.width.div_ceil(8)];
+            for (x, &[r, g, b]) in row.iter().enumerate() {
+                let luminance = (299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000;
+                if luminance < 128 {
+                    bits[x / 8] |= 0b10000000 >> (x % 8);
+                }
+            }
+            out.write_all(&bits)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())
+    }
+
+    pub fn write_png(&self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = vec![];
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, deflate, no filters, not interlaced
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        write_chunk(out, b"IHDR", &header)?;
+
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.rows() {
+            scanlines.push(0); // No filter
+            scanlines.extend_from_slice(&row.concat());
+        }
+        write_chunk(out, b"IDAT", &zlib_stored(&scanlines))?;
+        write_chunk(out, b"IEND", &[])
+    }
+
+    pub fn write(&self, out: &mut impl Write, format: ImageFormat) -> io::Result<()> {
+        match format {
+            ImageFormat::Pbm => self.write_pbm(out),
+            ImageFormat::Ppm => self.write_ppm(out),
+            ImageFormat::Png => self.write_png(out),
+        }
+    }
+
+    pub fn save(&self, path: &str, format: ImageFormat) -> Result<()> {
+        let io_error = |source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        };
+        let mut out = BufWriter::new(File::create(path).map_err(io_error)?);
+        self.write(&mut out, format).map_err(io_error)?;
+        out.flush().map_err(io_error)
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(&[kind.as_slice(), data].concat());
+    out.write_all(&crc.to_be_bytes())
+}
+
+/// zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        // A single empty final block
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        out.push(is_final as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffff_u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        Grid::from_rows(vec![vec![0.0, 1.0], vec![0.5, 0.25]])
+    }
+
+    #[test]
+    fn test_palette() {
+        let grey = Palette::default();
+        assert_eq!(grey.colour(0.0), [0, 0, 0]);
+        assert_eq!(grey.colour(0.5), [128, 128, 128]);
+        assert_eq!(grey.colour(2.0), [255, 255, 255]);
+
+        let heat: Palette = "heat".parse().unwrap();
+        assert_eq!(heat.colour(1.0 / 3.0), [192, 32, 0]);
+        let custom: Palette = "#ff0000, #0000ff".parse().unwrap();
+        assert_eq!(custom.colour(0.5), [128, 0, 128]);
+        assert_eq!(Palette::new(vec![[1, 2, 3]]).colour(0.7), [1, 2, 3]);
+        assert!("#12345".parse::<Palette>().is_err());
+        assert!("rainbow".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn test_ppm_and_pbm() {
+        let image = Image::new(&picture(), &Palette::default(), 1);
+        let mut ppm = vec![];
+        image.write_ppm(&mut ppm).unwrap();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 255, 255, 255, 128, 128, 128, 64, 64, 64]);
+        assert_eq!(ppm, expected);
+
+        let image = Image::new(&picture(), &Palette::default(), 5);
+        assert_eq!((image.width, image.height), (10, 10));
+        let mut pbm = vec![];
+        image.write_pbm(&mut pbm).unwrap();
+        // Rows of 10 pixels take 2 bytes, the dark ones are set
+        assert_eq!(&pbm[..9], b"P4\n10 10\n");
+        assert_eq!(&pbm[9..11], &[0b11111000, 0b00000000]);
+        assert_eq!(&pbm[19..21], &[0b00000111, 0b11000000]);
+        assert_eq!(pbm.len(), 9 + 2 * 10);
+    }
+
+    #[test]
+    fn test_png() {
+        let image = Image::new(&picture(), &Palette::default(), 1);
+        let mut png = vec![];
+        image.write_png(&mut png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 2, 0, 0, 0, 2]);
+        // The IEND chunk is always the same
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        let stored = zlib_stored(b"abc");
+        assert_eq!(&stored[..7], &[0x78, 0x01, 1, 3, 0, 0xfc, 0xff]);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(ImageFormat::from_path("out.PNG"), Some(ImageFormat::Png));
+        assert_eq!(
+            ImageFormat::from_path("a.b/out.ppm"),
+            Some(ImageFormat::Ppm)
+        );
+        assert_eq!(ImageFormat::from_path("out"), None);
+    }
+}
@@ -0,0 +1,394 @@
+//! Scaffolding for a new day: the solver module with a parser skeleton and
+//! tests, its registration in `src/days/mod.rs`, empty input files and
+//! placeholder entries in the answers manifest.
+//!
+//! [`plan`] works out every change up front and refuses to touch a day that
+//! already has a module, so nothing is written unless the whole day can be
+//! added. Inputs downloaded before keep their contents and manifest entries.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::ANSWERS_PATH;
+use crate::{AocError, Result};
+
+const DAYS_MODULE: &str = "src/days/mod.rs";
+
+/// A file to write, relative to the repository root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub path: PathBuf,
+    pub contents: String,
+    /// Whether the file is new rather than updated
+    pub created: bool,
+}
+
+/// The changes that add day `number` called `title`, e.g. `monkey_math`,
+/// to the repository at `root`.
+pub fn plan(root: &Path, number: u8, title: &str) -> std::result::Result<Vec<Change>, String> {
+    if !(1..=25).contains(&number) {
+        return Err(format!("Invalid day: {} (expected 1 to 25)", number));
+    }
+    let valid_title = title.starts_with(|c: char| c.is_ascii_lowercase())
+        && title
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid_title {
+        return Err(format!(
+            "Invalid title: {} (expected lowercase words joined by _, e.g. monkey_math)",
+            title
+        ));
+    }
+
+    let name = format!("day_{:02}_{}", number, title);
+    let read = |path: &str| {
+        fs::read_to_string(root.join(path)).map_err(|e| format!("Cannot read {}: {}", path, e))
+    };
+    let days_module = read(DAYS_MODULE)?;
+    let manifest = read(ANSWERS_PATH)?;
+    if registered_days(&days_module).contains(&number) {
+        return Err(format!(
+            "Day {} is already registered in {}",
+            number, DAYS_MODULE
+        ));
+    }
+
+    let module_path = format!("src/days/{}.rs", name);
+    let inputs = [
+        format!("test_inputs/{}.txt", name),
+        format!("inputs/{}.txt", name),
+    ];
+    if root.join(&module_path).exists() {
+        return Err(format!("{} already exists", module_path));
+    }
+
+    let mut changes = vec![Change {
+        path: root.join(&module_path),
+        contents: solver_module(number, &struct_name(title)),
+        created: true,
+    }];
+    changes.push(Change {
+        path: root.join(DAYS_MODULE),
+        contents: register(&days_module, number, title),
+        created: false,
+    });
+    changes.extend(
+        inputs
+            .iter()
+            .filter(|input| !root.join(input).exists())
+            .map(|input| Change {
+                path: root.join(input),
+                contents: String::new(),
+                created: true,
+            }),
+    );
+    let unlisted: Vec<String> = inputs
+        .into_iter()
+        .filter(|input| !manifest.contains(&format!("[\"{}\"]", input)))
+        .collect();
+    if !unlisted.is_empty() {
+        changes.push(Change {
+            path: root.join(ANSWERS_PATH),
+            contents: add_placeholders(&manifest, number, &unlisted),
+            created: false,
+        });
+    }
+    Ok(changes)
+}
+
+/// Writes the planned files, creating their directories as needed.
+pub fn apply(changes: &[Change]) -> Result<()> {
+    for change in changes {
+        let io_error = |source| AocError::Io {
+            path: change.path.display().to_string(),
+            source,
+        };
+        if let Some(dir) = change.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&change.path, &change.contents).map_err(io_error)?;
+    }
+    Ok(())
+}
+
+/// Example: monkey_math → MonkeyMath
+fn struct_name(title: &str) -> String {
+    title
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn solver_module(number: u8, struct_name: &str) -> String {
+    format!(
+        r#"//! Solution to an Advent of Code problem, day {number:02}, 2022
+//! https://adventofcode.com/2022/day/{number:02}
+use nom::character::complete::not_line_ending;
+
+use crate::parse::{{self, context, IResult}};
+use crate::{{Answer, Result, Solution}};
+
+#[derive(Clone)]
+pub struct {struct_name};
+
+fn parse_line(input: &str) -> IResult<'_, String> {{
+    let (input, line) = context("line", not_line_ending)(input)?;
+    Ok((input, line.to_owned()))
+}}
+
+impl Solution for {struct_name} {{
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {{
+        Ok(parse::lines(input, parse_line)?)
+    }}
+
+    fn part_one(&self, lines: &Self::Input) -> Result<Answer> {{
+        Ok(lines.len().into())
+    }}
+
+    fn part_two(&self, lines: &Self::Input) -> Result<Answer> {{
+        Ok(lines.len().into())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {{
+        assert_eq!(parse_line("example"), Ok(("", "example".to_owned())));
+    }}
+}}
+"#
+    )
+}
+
+/// The numbers of the days in the `DAYS` table of `src/days/mod.rs`
+fn registered_days(days_module: &str) -> Vec<u8> {
+    days_module
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("number: "))
+        .filter_map(|number| number.trim_end_matches(',').parse().ok())
+        .collect()
+}
+
+/// `days_module` with the module declaration and the `DAYS` entry of the new
+/// day, both in order of the day numbers.
+fn register(days_module: &str, number: u8, title: &str) -> String {
+    let name = format!("day_{:02}_{}", number, title);
+    let declaration = format!("pub mod {};\n", name);
+    let entry = format!(
+        "    Day {{\n        number: {},\n        title: \"{}\",\n        solver: &{}::{},\n    }},\n",
+        number,
+        title,
+        name,
+        struct_name(title)
+    );
+
+    let mut lines: Vec<String> = days_module
+        .split_inclusive('\n')
+        .map(str::to_owned)
+        .collect();
+    // Module declarations are sorted by name, which starts with the number
+    let last_module = lines.iter().rposition(|l| l.starts_with("pub mod day_"));
+    let position = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod day_") && *l > declaration)
+        .or(last_module.map(|i| i + 1))
+        .unwrap_or(0);
+    lines.insert(position, declaration);
+
+    let later_entry = lines.iter().enumerate().position(|(i, line)| {
+        let next = lines.get(i + 1).map(|l| l.trim());
+        line.trim() == "Day {"
+            && next
+                .and_then(|l| l.strip_prefix("number: "))
+                .and_then(|n| n.trim_end_matches(',').parse::<u8>().ok())
+                .is_some_and(|n| n > number)
+    });
+    let end_of_days = lines
+        .iter()
+        .position(|l| l.starts_with("pub const DAYS"))
+        .and_then(|start| {
+            lines[start..]
+                .iter()
+                .position(|l| l.trim() == "];")
+                .map(|i| start + i)
+        });
+    if let Some(position) = later_entry.or(end_of_days) {
+        lines.insert(position, entry);
+    }
+    lines.concat()
+}
+
+/// `manifest` with an entry without answers for each of `inputs`, before
+/// the entries of later days.
+fn add_placeholders(manifest: &str, number: u8, inputs: &[String]) -> String {
+    let placeholders: String = inputs
+        .iter()
+        .map(|input| format!("[\"{}\"]\nday = {}\n\n", input, number))
+        .collect();
+
+    // The day of each table is on the line after its header
+    let lines: Vec<&str> = manifest.split_inclusive('\n').collect();
+    let later_table = (0..lines.len()).find(|&i| {
+        lines[i].starts_with("[\"")
+            && lines
+                .get(i + 1)
+                .and_then(|l| l.trim().strip_prefix("day = "))
+                .and_then(|n| n.parse::<u8>().ok())
+                .is_some_and(|n| n > number)
+    });
+    match later_table {
+        Some(i) => lines[..i].concat() + &placeholders + &lines[i..].concat(),
+        None => {
+            let mut manifest = manifest.trim_end().to_owned();
+            manifest.push_str("\n\n");
+            manifest + placeholders.trim_end() + "\n"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = "pub mod day_01_calorie_counting;
+pub mod day_21_monkey_math;
+
+use crate::Solver;
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        title: \"calorie_counting\",
+        solver: &day_01_calorie_counting::CalorieCounting,
+    },
+    Day {
+        number: 21,
+        title: \"monkey_math\",
+        solver: &day_21_monkey_math::MonkeyMath::DEFAULT,
+    },
+];
+";
+
+    #[test]
+    fn test_register() {
+        let registered = register(MODULE, 16, "proboscidea_volcanium");
+        assert!(registered.starts_with(
+            "pub mod day_01_calorie_counting;
+pub mod day_16_proboscidea_volcanium;
+pub mod day_21_monkey_math;
+"
+        ));
+        assert!(registered.contains(
+            "    },
+    Day {
+        number: 16,
+        title: \"proboscidea_volcanium\",
+        solver: &day_16_proboscidea_volcanium::ProboscideaVolcanium,
+    },
+    Day {
+        number: 21,"
+        ));
+        assert_eq!(registered_days(&registered), vec![1, 16, 21]);
+
+        let registered = register(MODULE, 25, "full_of_hot_air");
+        assert_eq!(registered_days(&registered), vec![1, 21, 25]);
+        assert!(
+            registered.contains("pub mod day_21_monkey_math;\npub mod day_25_full_of_hot_air;\n")
+        );
+        assert!(registered.ends_with("FullOfHotAir,\n    },\n];\n"));
+    }
+
+    #[test]
+    fn test_add_placeholders() {
+        let manifest = "# Expected answers
+
+[\"inputs/day_01_calorie_counting.txt\"]
+day = 1
+part1 = 70509
+
+[\"inputs/day_21_monkey_math.txt\"]
+day = 21
+";
+        let inputs = ["test_inputs/day_16_x.txt".to_owned()];
+        assert_eq!(
+            add_placeholders(manifest, 16, &inputs),
+            "# Expected answers
+
+[\"inputs/day_01_calorie_counting.txt\"]
+day = 1
+part1 = 70509
+
+[\"test_inputs/day_16_x.txt\"]
+day = 16
+
+[\"inputs/day_21_monkey_math.txt\"]
+day = 21
+"
+        );
+        assert!(add_placeholders(manifest, 22, &inputs)
+            .ends_with("day = 21\n\n[\"test_inputs/day_16_x.txt\"]\nday = 22\n"));
+    }
+
+    #[test]
+    fn test_plan() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        fs::write(root.join(DAYS_MODULE), MODULE).unwrap();
+        fs::write(root.join(ANSWERS_PATH), "").unwrap();
+        fs::write(root.join("inputs/day_16_proboscidea_volcanium.txt"), "AA").unwrap();
+
+        let paths = |changes: &[Change]| -> Vec<String> {
+            changes
+                .iter()
+                .map(|c| c.path.strip_prefix(&root).unwrap().display().to_string())
+                .collect()
+        };
+        let changes = plan(&root, 22, "monkey_map").unwrap();
+        assert_eq!(
+            paths(&changes),
+            [
+                "src/days/day_22_monkey_map.rs",
+                "src/days/mod.rs",
+                "test_inputs/day_22_monkey_map.txt",
+                "inputs/day_22_monkey_map.txt",
+                "answers.toml"
+            ]
+        );
+        assert!(changes[0].contents.contains("pub struct MonkeyMap;"));
+
+        // The downloaded input is kept
+        let changes = plan(&root, 16, "proboscidea_volcanium").unwrap();
+        assert_eq!(
+            paths(&changes),
+            [
+                "src/days/day_16_proboscidea_volcanium.rs",
+                "src/days/mod.rs",
+                "test_inputs/day_16_proboscidea_volcanium.txt",
+                "answers.toml"
+            ]
+        );
+
+        apply(&changes).unwrap();
+        assert_eq!(
+            plan(&root, 16, "proboscidea_volcanium"),
+            Err("Day 16 is already registered in src/days/mod.rs".to_owned())
+        );
+        let input = root.join("inputs/day_16_proboscidea_volcanium.txt");
+        assert_eq!(fs::read_to_string(input).unwrap(), "AA");
+        assert!(plan(&root, 21, "monkey_math").is_err());
+        assert!(plan(&root, 26, "later").is_err());
+        assert!(plan(&root, 22, "MonkeyMap").is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,223 @@
+//! Shortest paths over graphs given by a neighbour function: breadth-first
+//! search for unit costs, Dijkstra and A* for weighted edges.
+//!
+//! Every search returns the distances of the nodes it reached together with
+//! their predecessors, so the paths themselves can be rebuilt.
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A graph given by the neighbours of each node.
+///
+/// Closures `Fn(N) -> impl IntoIterator<Item = N>` are graphs whose edges all
+/// cost 1.
+pub trait Graph<N> {
+    /// Nodes one edge away from `node`.
+    fn neighbours(&self, node: N) -> impl Iterator<Item = N>;
+
+    /// Cost of the edge from `from` to its neighbour `to`. Breadth-first
+    /// search ignores it.
+    fn cost(&self, _from: N, _to: N) -> u64 {
+        1
+    }
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbours(&self, node: N) -> impl Iterator<Item = N> {
+        self(node).into_iter()
+    }
+}
+
+/// Result of a search
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+    /// Distance of every reached node from the closest start
+    pub distances: HashMap<N, u64>,
+    /// The node before each reached node on a shortest path. Starts have
+    /// none.
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash> Search<N> {
+    fn new() -> Search<N> {
+        Search {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// `None` if `node` was not reached.
+    pub fn distance(&self, node: N) -> Option<u64> {
+        self.distances.get(&node).copied()
+    }
+
+    /// A shortest path from a start to `node`, both included.
+    pub fn path(&self, node: N) -> Option<Vec<N>> {
+        self.distances.get(&node)?;
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search from `start`.
+pub fn bfs<N: Copy + Eq + Hash>(graph: &impl Graph<N>, start: N) -> Search<N> {
+    bfs_multi(graph, [start])
+}
+
+/// Breadth-first search from all of `starts` at once: the distance of a node
+/// is the distance from the closest start.
+pub fn bfs_multi<N: Copy + Eq + Hash>(
+    graph: &impl Graph<N>,
+    starts: impl IntoIterator<Item = N>,
+) -> Search<N> {
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.distances.insert(start, 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in graph.neighbours(node) {
+            if let Entry::Vacant(entry) = search.distances.entry(next) {
+                entry.insert(distance + 1);
+                search.predecessors.insert(next, node);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from `start`, using [`Graph::cost`].
+pub fn dijkstra<N: Copy + Ord + Hash>(graph: &impl Graph<N>, start: N) -> Search<N> {
+    best_first(graph, start, None, |_| 0)
+}
+
+/// A* search from `start` to `goal`. `heuristic` estimates the cost from a
+/// node to `goal` and must never overestimate it, or the distance found may
+/// not be the shortest. The search stops once `goal` is reached, so only
+/// the distances of the nodes on the way are final.
+pub fn astar<N: Copy + Ord + Hash>(
+    graph: &impl Graph<N>,
+    start: N,
+    goal: N,
+    heuristic: impl Fn(N) -> u64,
+) -> Search<N> {
+    best_first(graph, start, Some(goal), heuristic)
+}
+
+fn best_first<N: Copy + Ord + Hash>(
+    graph: &impl Graph<N>,
+    start: N,
+    goal: Option<N>,
+    heuristic: impl Fn(N) -> u64,
+) -> Search<N> {
+    let mut search = Search::new();
+    search.distances.insert(start, 0);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+
+    while let Some(Reverse((_, distance, node))) = queue.pop() {
+        if Some(node) == goal {
+            break;
+        }
+        // Stale entry, the node was reached by a shorter path since
+        if search.distances[&node] < distance {
+            continue;
+        }
+        for next in graph.neighbours(node) {
+            let next_distance = distance + graph.cost(node, next);
+            if search.distance(next).is_none_or(|d| next_distance < d) {
+                search.distances.insert(next, next_distance);
+                search.predecessors.insert(next, node);
+                queue.push(Reverse((
+                    next_distance + heuristic(next),
+                    next_distance,
+                    next,
+                )));
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 → 1 → 2 → 3 costs 3 + 3 + 3, the shortcut 0 → 3 costs 10.
+    struct Weighted;
+
+    impl Graph<u32> for Weighted {
+        fn neighbours(&self, node: u32) -> impl Iterator<Item = u32> {
+            let shortcut = (node == 0).then_some(3);
+            (node < 3).then_some(node + 1).into_iter().chain(shortcut)
+        }
+
+        fn cost(&self, from: u32, to: u32) -> u64 {
+            if to - from == 3 {
+                10
+            } else {
+                3
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        // Numbers up to 20, stepping by +1 or *2
+        let graph = |n: u32| [n + 1, n * 2].into_iter().filter(|&m| m <= 20);
+        let search = bfs(&graph, 1);
+        assert_eq!(search.distance(1), Some(0));
+        assert_eq!(search.distance(20), Some(5));
+        assert_eq!(search.path(20).unwrap().len(), 6);
+        assert_eq!(search.path(10), Some(vec![1, 2, 4, 5, 10]));
+        assert_eq!(search.distance(0), None);
+        assert_eq!(search.path(0), None);
+
+        let search = bfs_multi(&graph, [1, 9]);
+        assert_eq!(search.distance(9), Some(0));
+        assert_eq!(search.distance(20), Some(2));
+        assert_eq!(search.path(20), Some(vec![9, 10, 20]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(&Weighted, 0);
+        assert_eq!(search.distance(3), Some(9));
+        assert_eq!(search.path(3), Some(vec![0, 1, 2, 3]));
+        // Breadth-first search counts edges
+        assert_eq!(bfs(&Weighted, 0).distance(3), Some(1));
+    }
+
+    #[test]
+    fn test_astar() {
+        // 10x10 grid with a wall at x = 5 open only at y = 9
+        let graph = |(x, y): (i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+                .filter(|&(x, y)| x != 5 || y == 9)
+        };
+        let goal = (9, 0);
+        let manhattan = |(x, y): (i32, i32)| (goal.0 - x).unsigned_abs() as u64 + y as u64;
+        let search = astar(&graph, (0, 0), goal, manhattan);
+        assert_eq!(search.distance(goal), Some(27));
+        let path = search.path(goal).unwrap();
+        assert_eq!((path[0], path.len()), ((0, 0), 28));
+        assert!(path.contains(&(5, 9)));
+        assert_eq!(dijkstra(&graph, (0, 0)).distance(goal), Some(27));
+    }
+}
@@ -0,0 +1,367 @@
+//! The solvers behind a small HTTP API, for tools written in other languages
+//! (`aoc serve`).
+//!
+//! - `POST /solve/{day}/{part}` solves the puzzle input in the request body.
+//!   Puzzle constants go in the query string, e.g.
+//!   `/solve/15/1?interesting_row=10`. The response is like
+//!   `{"day":15,"part":1,"answer":26,"parse_ms":0.011,"elapsed_ms":0.004}`,
+//!   where `elapsed_ms` excludes parsing as in `aoc run --format json`.
+//! - `GET /health` answers `{"status":"ok","days":[1,2,...],"busy":0,"workers":8}`.
+//!
+//! Errors are `{"error":"..."}` with the status 400 for malformed requests,
+//! inputs and constants, 404 for unknown days and paths, 408 when the time
+//! limit ran out, 422 when the input has no solution, 500 when the solver
+//! panicked and 503 when all workers are busy.
+//!
+//! Each connection is handled on a thread of its own and each solve runs on
+//! a worker thread, at most `workers` of them at once. A solve that ran out
+//! of time is cancelled through its [`Budget`], and gives its worker back at
+//! its next [check](budget::check).
+use std::io::{self, BufReader};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::budget::{self, Budget, Token};
+use crate::http::{Request, Response};
+use crate::params::{self, Overrides};
+use crate::{days, info, json, Answer, AocError, Day, Part, DAYS};
+
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServeOptions {
+    /// Longest time a request waits for its answer
+    pub time_limit: Duration,
+    /// Most solves running at once
+    pub workers: usize,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        ServeOptions {
+            time_limit: Duration::from_secs(10),
+            workers: thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+struct Shared {
+    options: ServeOptions,
+    /// Solves running, including those past the time limit
+    busy: AtomicUsize,
+}
+
+/// A worker taken from [`Shared::busy`], given back when dropped.
+struct Worker(Arc<Shared>);
+
+impl Worker {
+    fn take(shared: &Arc<Shared>) -> Option<Worker> {
+        shared
+            .busy
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |busy| {
+                (busy < shared.options.workers).then_some(busy + 1)
+            })
+            .ok()
+            .map(|_| Worker(Arc::clone(shared)))
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.0.busy.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs, options: ServeOptions) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            shared: Arc::new(Shared {
+                options,
+                busy: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves until the process ends.
+    pub fn run(self) {
+        for stream in self.listener.incoming() {
+            // A connection that failed before it was accepted concerns
+            // nobody else
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let shared = Arc::clone(&self.shared);
+            thread::spawn(move || serve_connection(&shared, stream));
+        }
+    }
+}
+
+/// Answers a single request. The connection is closed after it.
+fn serve_connection(shared: &Arc<Shared>, stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let start = Instant::now();
+    let response = match Request::read(&mut BufReader::new(&stream)) {
+        Ok(Some(request)) => {
+            let response = handle(shared, &request);
+            info!(
+                "{} {} {} in {:.1?}",
+                request.method,
+                request.path,
+                response,
+                start.elapsed()
+            );
+            response
+        }
+        Ok(None) => return,
+        Err(e) => {
+            info!("Invalid request: {}", e);
+            error(400, &format!("Invalid request: {}", e))
+        }
+    };
+    let _ = response
+        .with_header("Connection", "close")
+        .write_to(&mut &stream);
+}
+
+fn json_response(status: u16, body: impl ToString) -> Response {
+    Response::new(status, body.to_string()).with_header("Content-Type", "application/json")
+}
+
+fn error(status: u16, message: &str) -> Response {
+    json_response(status, json::Object::new().string("error", message))
+}
+
+fn handle(shared: &Arc<Shared>, request: &Request) -> Response {
+    let (path, query) = request.path.split_once('?').unwrap_or((&request.path, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), &segments[..]) {
+        ("GET", ["health"]) => health(shared),
+        ("POST", ["solve", day, part]) => {
+            let Some(day) = day.parse().ok().and_then(days::find) else {
+                return error(404, &format!("Cannot find solution for day {}", day));
+            };
+            let part: Part = match part.parse() {
+                Ok(part) => part,
+                Err(e) => return error(404, &e),
+            };
+            let overrides = match query_overrides(query) {
+                Ok(overrides) => overrides,
+                Err(e) => return error(400, &e),
+            };
+            solve(shared, day, part, overrides, request.body.clone())
+        }
+        (_, ["health"]) => error(405, "Expected GET").with_header("Allow", "GET"),
+        (_, ["solve", _, _]) => error(405, "Expected POST").with_header("Allow", "POST"),
+        _ => error(404, &format!("Unknown path: {}", path)),
+    }
+}
+
+fn health(shared: &Shared) -> Response {
+    let days: Vec<String> = DAYS.iter().map(|day| day.number.to_string()).collect();
+    json_response(
+        200,
+        json::Object::new()
+            .string("status", "ok")
+            .raw("days", format!("[{}]", days.join(",")))
+            .number("busy", shared.busy.load(Ordering::SeqCst))
+            .number("workers", shared.options.workers),
+    )
+}
+
+/// `a=1&b=2` as overrides of the puzzle constants
+fn query_overrides(query: &str) -> Result<Overrides, String> {
+    query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(params::parse_override)
+        .collect()
+}
+
+struct Solved {
+    answer: Answer,
+    parse_time: Duration,
+    solve_time: Duration,
+}
+
+fn solve_timed(day: &Day, part: Part, overrides: &Overrides, input: &str) -> crate::Result<Solved> {
+    let solver = day.solver.with_params(overrides)?;
+    let start = Instant::now();
+    let parsed = solver
+        .parse_input(input)
+        .map_err(|e| e.in_file("request body"))?;
+    let parse_time = start.elapsed();
+    let start = Instant::now();
+    let answer = solver.solve_parsed(parsed.as_ref(), part)?;
+    Ok(Solved {
+        answer,
+        parse_time,
+        solve_time: start.elapsed(),
+    })
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+fn solve(
+    shared: &Arc<Shared>,
+    day: &'static Day,
+    part: Part,
+    overrides: Overrides,
+    input: String,
+) -> Response {
+    let Some(worker) = Worker::take(shared) else {
+        return error(
+            503,
+            &format!("All {} workers are busy", shared.options.workers),
+        );
+    };
+    let time_limit = shared.options.time_limit;
+    let budget = Budget {
+        time: Some(time_limit),
+        ..Budget::default()
+    };
+    let token = Token::default();
+    let (sender, receiver) = mpsc::channel();
+    let cancel = token.clone();
+    thread::spawn(move || {
+        let solved = budget::run(&budget, &token, || {
+            solve_timed(day, part, &overrides, &input)
+        });
+        // Free before answering, so that the client's next request finds it
+        drop(worker);
+        // The receiver is gone if the time ran out
+        let _ = sender.send(solved);
+    });
+
+    match receiver.recv_timeout(time_limit) {
+        Ok(Ok(solved)) => json_response(
+            200,
+            json::Object::new()
+                .number("day", day.number)
+                .number("part", part)
+                .raw("answer", json::answer(&solved.answer))
+                .number("parse_ms", millis(solved.parse_time))
+                .number("elapsed_ms", millis(solved.solve_time)),
+        ),
+        Ok(Err(e)) => {
+            let status = match e {
+                AocError::Solve(_) => 422,
+                AocError::Exceeded(_) => 408,
+                AocError::Parse(_) | AocError::Param(_) => 400,
+                AocError::Io { .. } => 500,
+            };
+            error(status, &e.to_string())
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            error(
+                408,
+                &format!("No answer within the time limit of {:?}", time_limit),
+            )
+        }
+        Err(RecvTimeoutError::Disconnected) => error(500, "The solver panicked"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(options: ServeOptions) -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0", options).unwrap();
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    fn send(addr: SocketAddr, method: &str, path: &str, body: &str) -> Response {
+        let stream = TcpStream::connect(addr).unwrap();
+        let mut request = Request::new(method, path);
+        request.body = body.to_owned();
+        request.write_to(&mut &stream).unwrap();
+        Response::read(&mut BufReader::new(&stream)).unwrap()
+    }
+
+    #[test]
+    fn test_solve() {
+        let addr = start(ServeOptions::default());
+        let input = "1000\n2000\n\n4000\n";
+        let response = send(addr, "POST", "/solve/1/1", input);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("Content-Type"), Some("application/json"));
+        assert!(
+            response
+                .body
+                .starts_with(r#"{"day":1,"part":1,"answer":4000,"parse_ms":"#),
+            "{}",
+            response.body
+        );
+
+        // Day 17 with one rock: it lands on the floor
+        let response = send(addr, "POST", "/solve/17/1?rocks_part_one=1", ">>><<>\n");
+        assert!(
+            response.body.contains(r#""answer":1,"#),
+            "{}",
+            response.body
+        );
+
+        let health = send(addr, "GET", "/health", "");
+        assert!(health.body.starts_with(r#"{"status":"ok","days":[1,2,3,"#));
+    }
+
+    #[test]
+    fn test_errors() {
+        let addr = start(ServeOptions::default());
+        let status = |method, path, body| send(addr, method, path, body).status;
+        assert_eq!(status("POST", "/solve/1/1", "1000\nabc\n"), 400);
+        assert_eq!(status("POST", "/solve/17/1?rocks=2", ">\n"), 400);
+        assert_eq!(status("POST", "/solve/26/1", ""), 404);
+        assert_eq!(status("POST", "/solve/1/3", ""), 404);
+        assert_eq!(status("GET", "/solve/1/1", ""), 405);
+        assert_eq!(status("GET", "/", ""), 404);
+
+        let response = send(addr, "POST", "/solve/1/1", "1000\nabc\n");
+        assert!(
+            response.body.contains("request body:2:1"),
+            "{}",
+            response.body
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let addr = start(ServeOptions {
+            time_limit: Duration::from_millis(1),
+            workers: 1,
+        });
+        let slow = "/solve/17/1?rocks_part_one=10000000&strategy=brute_force";
+        assert_eq!(send(addr, "POST", slow, ">>><<><>><<<>\n").status, 408);
+        // The solve that ran out of time was cancelled and frees the only
+        // worker at its next check
+        let start = Instant::now();
+        while !send(addr, "GET", "/health", "")
+            .body
+            .contains(r#""busy":0"#)
+        {
+            assert!(start.elapsed() < Duration::from_secs(5), "still busy");
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(send(addr, "POST", "/solve/1/1", "1\n").status, 200);
+    }
+}
@@ -0,0 +1,245 @@
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::animate::Simulation;
+use crate::render::Picture;
+use crate::stream::Stream;
+use crate::{input, params, Result};
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part: {} (expected 1 or 2)", s)),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle. Most puzzles have a numeric answer,
+/// some ask for a message (day 5) or a picture (day 10).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Answer {
+    /// The answer on a single line, pictures shortened to `[6 lines]`
+    pub fn short(&self) -> String {
+        let text = self.to_string();
+        match text.lines().count() {
+            0 | 1 => text,
+            lines => format!("[{} lines]", lines),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(i64::try_from(n).expect("Answer doesn't fit into i64"))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(i64::try_from(n).expect("Answer doesn't fit into i64"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+/// A solution to a single day.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part_one(&self, input: &Self::Input) -> Result<Answer>;
+    fn part_two(&self, input: &Self::Input) -> Result<Answer>;
+
+    /// Names and values of the puzzle constants that can be overridden with
+    /// [`Solution::set_param`]. Most days have none.
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    /// Overrides a puzzle constant, for example the row that day 15 looks
+    /// at, which is different for the example input.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<()> {
+        Err(params::unknown(name, &self.params()))
+    }
+
+    /// Names of the algorithms the day can solve the puzzle with, the default
+    /// first, for `aoc compare`. One is picked with the parameter
+    /// [`params::STRATEGY`]. Most days have a single one and none to pick.
+    fn strategies(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Whether `part` ends in reasonable time with these puzzle constants and
+    /// strategy, so that the answer tests can skip those that don't, like the
+    /// brute force of day 17 part 2.
+    fn finishes(&self, _part: Part) -> bool {
+        true
+    }
+
+    /// Named pictures of the puzzle state for `aoc render`, the first one
+    /// being the default. Most days have none.
+    fn render(&self, _input: &Self::Input) -> Result<Vec<(&'static str, Picture)>> {
+        Ok(vec![])
+    }
+
+    /// A step-by-step simulation of `part` for `aoc run --animate`. Most
+    /// days have none.
+    fn simulate<'a>(
+        &'a self,
+        _input: &'a Self::Input,
+        _part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        None
+    }
+
+    /// The solver of `part` taking the input as it is read, in memory that
+    /// doesn't grow with the input (see [`crate::stream`]). Most days need
+    /// the whole input and have none.
+    fn stream(&self, _part: Part) -> Option<Box<dyn Stream + '_>> {
+        None
+    }
+}
+
+/// Type-erased [`Solution`], so that solutions with different input types can
+/// live side by side in the registry.
+pub trait Solver: Send + Sync {
+    /// Parses the input after [normalizing](input::normalize) it, so error
+    /// positions are those of the normalized text.
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn solve_parsed(&self, parsed: &dyn Any, part: Part) -> Result<Answer>;
+    fn params(&self) -> Vec<(&'static str, String)>;
+    fn strategies(&self) -> Vec<&'static str>;
+    fn finishes(&self, part: Part) -> bool;
+    /// A copy of the solver with the given puzzle constants overridden
+    fn with_params(&self, overrides: &[(String, String)]) -> Result<Box<dyn Solver>>;
+    fn render_parsed(&self, parsed: &dyn Any) -> Result<Vec<(&'static str, Picture)>>;
+    fn simulate_parsed<'a>(
+        &'a self,
+        parsed: &'a dyn Any,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>>;
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>>;
+
+    fn solve(&self, input: &str, part: Part) -> Result<Answer> {
+        self.solve_parsed(self.parse_input(input)?.as_ref(), part)
+    }
+}
+
+impl<S: Solution + Clone + Send + Sync + 'static> Solver for S {
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(self.parse(&input::normalize(input))?))
+    }
+
+    fn solve_parsed(&self, parsed: &dyn Any, part: Part) -> Result<Answer> {
+        let input = downcast::<S>(parsed);
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        Solution::params(self)
+    }
+
+    fn strategies(&self) -> Vec<&'static str> {
+        Solution::strategies(self)
+    }
+
+    fn finishes(&self, part: Part) -> bool {
+        Solution::finishes(self, part)
+    }
+
+    fn with_params(&self, overrides: &[(String, String)]) -> Result<Box<dyn Solver>> {
+        let mut solver = self.clone();
+        for (name, value) in overrides {
+            solver.set_param(name, value)?;
+        }
+        Ok(Box::new(solver))
+    }
+
+    fn render_parsed(&self, parsed: &dyn Any) -> Result<Vec<(&'static str, Picture)>> {
+        self.render(downcast::<S>(parsed))
+    }
+
+    fn simulate_parsed<'a>(
+        &'a self,
+        parsed: &'a dyn Any,
+        part: Part,
+    ) -> Option<Box<dyn Simulation + 'a>> {
+        self.simulate(downcast::<S>(parsed), part)
+    }
+
+    fn stream(&self, part: Part) -> Option<Box<dyn Stream + '_>> {
+        Solution::stream(self, part)
+    }
+}
+
+fn downcast<S: Solution>(parsed: &dyn Any) -> &S::Input {
+    parsed
+        .downcast_ref::<S::Input>()
+        .expect("Parsed input of a different solution")
+}
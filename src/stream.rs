@@ -0,0 +1,290 @@
+//! Solving inputs as they are read, for stdin and for generated inputs too
+//! big to hold in memory.
+//!
+//! Days whose answers only need a little state per line, like the sums of
+//! calories of day 1, implement [`Solution::stream`](crate::Solution::stream).
+//! Their streams take the normalized input in pieces of at most
+//! [`CHUNK_SIZE`] bytes, so that the memory used doesn't grow with the input.
+//! Most of them take whole lines through [`lines`], of at most
+//! [`MAX_LINE_LENGTH`] bytes.
+use std::io::{self, Read};
+use std::str;
+
+use crate::input::Normalizer;
+use crate::{budget, Answer, AocError, ParseError, Part, Result, Solver};
+
+/// Bytes read at once
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Longest line taken by [`lines`], which would otherwise hold an input
+/// without line breaks in memory
+pub const MAX_LINE_LENGTH: usize = 16 * CHUNK_SIZE;
+
+/// The solver of one part, fed the input as it is read.
+pub trait Stream {
+    /// Takes the next piece of the input, which may end in the middle of a
+    /// line.
+    fn push(&mut self, text: &str) -> Result<()>;
+
+    /// The answer, once the whole input was pushed.
+    fn finish(self: Box<Self>) -> Result<Answer>;
+}
+
+/// A [`Stream`] that takes one line at a time.
+pub trait LineStream {
+    /// Takes the next line, without its line break. Parse errors are located
+    /// in `line`, their line numbers are corrected by [`lines`].
+    fn line(&mut self, line: &str) -> Result<()>;
+
+    fn finish(self) -> Result<Answer>;
+}
+
+struct Lines<S> {
+    stream: S,
+    /// The start of a line that was not pushed to its end yet
+    partial: String,
+    /// Lines taken so far
+    count: usize,
+}
+
+impl<S: LineStream> Lines<S> {
+    fn line(&mut self, line: &str) -> Result<()> {
+        self.count += 1;
+        self.stream.line(line).map_err(|e| match e {
+            AocError::Parse(mut e) => {
+                e.line = self.count;
+                AocError::Parse(e)
+            }
+            e => e,
+        })
+    }
+}
+
+impl<S: LineStream> Stream for Lines<S> {
+    fn push(&mut self, mut text: &str) -> Result<()> {
+        while let Some((end, rest)) = text.split_once('\n') {
+            if self.partial.is_empty() {
+                self.line(end)?;
+            } else {
+                let mut line = std::mem::take(&mut self.partial);
+                line.push_str(end);
+                self.line(&line)?;
+            }
+            text = rest;
+        }
+        self.partial.push_str(text);
+        if self.partial.len() > MAX_LINE_LENGTH {
+            let mut e = ParseError::at_offset(&self.partial, MAX_LINE_LENGTH, "line break");
+            e.line = self.count + 1;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Answer> {
+        // Normalized inputs end with a line break, so there is no partial
+        // line left unless the input was pushed directly
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            self.line(&line)?;
+        }
+        self.stream.finish()
+    }
+}
+
+/// The [`Stream`] of a solver that takes whole lines
+pub fn lines<'a, S: LineStream + 'a>(stream: S) -> Box<dyn Stream + 'a> {
+    Box::new(Lines {
+        stream,
+        partial: String::new(),
+        count: 0,
+    })
+}
+
+/// Normalizes everything `reader` has and pushes it to `streams` in pieces.
+/// `name` is the path of the input for errors.
+pub fn feed(mut reader: impl Read, name: &str, streams: &mut [Box<dyn Stream + '_>]) -> Result<()> {
+    let io_error = |source| AocError::Io {
+        path: name.to_owned(),
+        source,
+    };
+    let mut normalizer = Normalizer::default();
+    let mut buffer = vec![0; CHUNK_SIZE];
+    // Bytes at the end of the buffer that may start a character
+    let mut carried = 0;
+    let mut text = String::new();
+    loop {
+        budget::check()?;
+        let read = match reader.read(&mut buffer[carried..]) {
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(io_error(e)),
+        };
+        let end = carried + read;
+        let valid = match str::from_utf8(&buffer[..end]) {
+            Ok(valid) => valid,
+            // A character cut at the end of the buffer
+            Err(e) if e.error_len().is_none() && read > 0 => {
+                str::from_utf8(&buffer[..e.valid_up_to()]).unwrap()
+            }
+            Err(_) => {
+                let message = "stream did not contain valid UTF-8";
+                return Err(io_error(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    message,
+                )));
+            }
+        };
+        text.clear();
+        normalizer.push(valid, &mut text);
+        let valid_len = valid.len();
+        if read == 0 {
+            break;
+        }
+        for stream in streams.iter_mut() {
+            stream.push(&text)?;
+        }
+        buffer.copy_within(valid_len..end, 0);
+        carried = end - valid_len;
+    }
+    text.clear();
+    normalizer.finish(&mut text);
+    for stream in streams.iter_mut() {
+        stream.push(&text)?;
+    }
+    Ok(())
+}
+
+/// Solves `parts` of the input in `reader` in a single pass, or returns
+/// `None` without reading anything if `solver` cannot stream one of them.
+pub fn solve(
+    solver: &dyn Solver,
+    parts: &[Part],
+    reader: impl Read,
+    name: &str,
+) -> Option<Result<Vec<(Part, Answer)>>> {
+    let mut streams = parts
+        .iter()
+        .map(|&part| solver.stream(part))
+        .collect::<Option<Vec<_>>>()?;
+    let solve = || {
+        feed(reader, name, &mut streams).map_err(|e| e.in_file(name))?;
+        parts
+            .iter()
+            .zip(streams)
+            .map(|(&part, stream)| Ok((part, stream.finish().map_err(|e| e.in_file(name))?)))
+            .collect()
+    };
+    Some(solve())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use std::fs;
+
+    /// Gives out at most 3 bytes per read, to cut lines and characters
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(3).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    /// Every line that was pushed
+    struct Collect(Vec<String>);
+
+    impl LineStream for Collect {
+        fn line(&mut self, line: &str) -> Result<()> {
+            if line == "bad" {
+                return Err(ParseError::at(line, &line[1..], "good").into());
+            }
+            self.0.push(line.to_owned());
+            Ok(())
+        }
+
+        fn finish(self) -> Result<Answer> {
+            Ok(self.0.join("|").into())
+        }
+    }
+
+    #[test]
+    fn test_feed() {
+        let mut streams = [lines(Collect(vec![]))];
+        let input = "\u{feff}ä€ \r\n\r\n𝄞b\t\r\n\r\n";
+        feed(Trickle(input.as_bytes()), "x", &mut streams).unwrap();
+        let [stream] = streams;
+        assert_eq!(stream.finish().unwrap(), Answer::from("ä€||𝄞b"));
+
+        let mut streams = [lines(Collect(vec![]))];
+        match feed(Trickle(b"a\nbad\n"), "x", &mut streams).unwrap_err() {
+            AocError::Parse(e) => assert_eq!((e.line, e.column), (2, 2)),
+            e => panic!("Unexpected error: {}", e),
+        }
+        let e = feed(Trickle(b"a\n\xff\n"), "x", &mut streams).unwrap_err();
+        assert!(e.to_string().starts_with("Cannot read file x:"), "{}", e);
+    }
+
+    /// An input without line breaks is not read to its end
+    #[test]
+    fn test_long_line() {
+        let mut streams = [lines(Collect(vec![]))];
+        let input = b"a\n".chain(io::repeat(b'1').take(4 * MAX_LINE_LENGTH as u64));
+        match feed(input, "x", &mut streams).unwrap_err() {
+            AocError::Parse(e) => {
+                assert_eq!((e.line, e.column), (2, MAX_LINE_LENGTH + 1));
+                assert_eq!(e.expected, "line break");
+            }
+            e => panic!("Unexpected error: {}", e),
+        }
+
+        let mut streams = [lines(Collect(vec![]))];
+        let input = "1".repeat(MAX_LINE_LENGTH) + "\n";
+        feed(input.as_bytes(), "x", &mut streams).unwrap();
+    }
+
+    /// The streams give the same answers as the solvers that parse the
+    /// whole input
+    #[test]
+    fn test_streamed_answers() {
+        // The larger example, which runs for the 240 cycles of the screen
+        let day_10 = fs::read_to_string("test_inputs/day_10_cathode_ray_tube.txt").unwrap();
+        let inputs = [
+            (
+                1,
+                "1000\n2000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+            ),
+            (2, "A Y\nB X\nC Z\n"),
+            (
+                3,
+                "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjFGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n\
+                 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n",
+            ),
+            (4, "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n"),
+            (6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"),
+            (9, "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n"),
+            (10, &day_10),
+        ];
+        for (day, input) in inputs {
+            let solver = days::find(day).unwrap().solver;
+            let streamed = solve(solver, &Part::BOTH, Trickle(input.as_bytes()), "x")
+                .expect("Day cannot be streamed")
+                .unwrap();
+            for (part, answer) in streamed {
+                assert_eq!(answer, solver.solve(input, part).unwrap(), "Day {}", day);
+            }
+        }
+        assert!(solve(
+            days::find(5).unwrap().solver,
+            &[Part::One],
+            io::empty(),
+            "x"
+        )
+        .is_none());
+    }
+}
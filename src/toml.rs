@@ -0,0 +1,164 @@
+//! The subset of TOML used by `answers.toml` and `params.toml`: tables named
+//! by a quoted string (an input file), holding integers and strings.
+//!
+//! ```toml
+//! # Comment
+//! ["test_inputs/day_10_cathode_ray_tube.txt"]
+//! day = 10
+//! part1 = 13140
+//! part2 = """
+//! ##..##..
+//! ###...##"""
+//! ```
+use std::fmt;
+
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, tag, take_until, take_while, take_while1};
+use nom::character::complete::{i64, multispace1, not_line_ending, space0};
+use nom::combinator::{map, value};
+use nom::multi::many0_count;
+use nom::sequence::{delimited, preceded, tuple};
+use nom::IResult;
+
+use crate::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Integer(i64),
+    String(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Keys and headers are slices of the parsed text, so that errors found
+/// while interpreting the values can point at them.
+#[derive(Debug)]
+pub struct Table<'a> {
+    pub name: &'a str,
+    /// The whole `["name"]` header
+    pub header: &'a str,
+    pub entries: Vec<(&'a str, Value)>,
+}
+
+fn blank(input: &str) -> IResult<&str, usize> {
+    many0_count(alt((multispace1, preceded(tag("#"), not_line_ending))))(input)
+}
+
+fn header(input: &str) -> IResult<&str, &str> {
+    delimited(tag("[\""), is_not("\"\n"), tag("\"]"))(input)
+}
+
+fn key(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_')(input)
+}
+
+fn multi_line_string(input: &str) -> IResult<&str, Value> {
+    map(
+        delimited(tag("\"\"\""), take_until("\"\"\""), tag("\"\"\"")),
+        // A newline right after the opening quotes is not part of the string
+        |s: &str| Value::String(s.strip_prefix('\n').unwrap_or(s).to_owned()),
+    )(input)
+}
+
+fn string(input: &str) -> IResult<&str, Value> {
+    map(
+        delimited(tag("\""), take_while(|c| c != '"' && c != '\n'), tag("\"")),
+        |s: &str| Value::String(s.to_owned()),
+    )(input)
+}
+
+fn key_value(input: &str) -> IResult<&str, (&str, Value)> {
+    map(
+        tuple((
+            key,
+            space0,
+            value((), tag("=")),
+            space0,
+            alt((multi_line_string, string, map(i64, Value::Integer))),
+        )),
+        |(k, _, _, _, v)| (k, v),
+    )(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Table<'_>>, ParseError> {
+    let mut tables: Vec<Table> = vec![];
+    let mut rest = input;
+    loop {
+        (rest, _) = blank(rest).map_err(|e| ParseError::from_nom(input, e, "comment"))?;
+        if rest.is_empty() {
+            break;
+        }
+
+        if let Ok((after, name)) = header(rest) {
+            tables.push(Table {
+                name,
+                header: &rest[..rest.len() - after.len()],
+                entries: vec![],
+            });
+            rest = after;
+            continue;
+        }
+
+        let (after, entry) = key_value(rest).map_err(|e| {
+            ParseError::from_nom(input, e, "table header [\"name\"] or key = value")
+        })?;
+        tables
+            .last_mut()
+            .ok_or_else(|| ParseError::at(input, rest, "table header [\"name\"]"))?
+            .entries
+            .push(entry);
+        rest = after;
+    }
+    Ok(tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "# Comment\n\
+                     [\"a.txt\"]\n\
+                     day = 5\n\
+                     part1 = \"CMZ\"\n\
+                     \n\
+                     [\"b.txt\"]\n\
+                     part1 = -720\n\
+                     part2 = \"\"\"\n##..\n..##\"\"\"\n";
+        let tables = parse(input).unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "a.txt");
+        assert_eq!(tables[0].header, "[\"a.txt\"]");
+        assert_eq!(
+            tables[0].entries,
+            vec![
+                ("day", Value::Integer(5)),
+                ("part1", Value::String("CMZ".to_owned()))
+            ]
+        );
+        assert_eq!(
+            tables[1].entries,
+            vec![
+                ("part1", Value::Integer(-720)),
+                ("part2", Value::String("##..\n..##".to_owned()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("day = 1\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+
+        let e = parse("[\"a.txt\"]\nday = \n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 7));
+    }
+}
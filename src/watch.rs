@@ -0,0 +1,472 @@
+//! Re-running a day whenever its code, its inputs or the expected answers
+//! change (`aoc watch`).
+//!
+//! The watcher polls the modification times of `src/`, `Cargo.toml`, the
+//! answers and params files and the inputs of the day. A change to the code
+//! rebuilds the runner with cargo first. Each part of each input is solved by
+//! a fresh `aoc run`, so that it runs the new code and so that an endless
+//! loop can be stopped: a change while it runs starts everything over.
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::json;
+use crate::manifest::{Expected, Manifest, ANSWERS_PATH};
+use crate::params::PARAMS_PATH;
+use crate::{Answer, Day, Part};
+
+/// How often the files are checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Files whose change needs a new build of the runner
+const CODE_PATHS: [&str; 2] = ["src", "Cargo.toml"];
+
+/// Modification time and size of the watched files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl Snapshot {
+    /// The files at `paths`, and in them for directories. Missing paths are
+    /// left out.
+    pub fn take(paths: &[PathBuf]) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        for path in paths {
+            snapshot.add(path);
+        }
+        snapshot
+    }
+
+    fn add(&mut self, path: &Path) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                self.add(&entry.path());
+            }
+        } else {
+            let modified = metadata.modified().ok();
+            self.files
+                .insert(path.to_owned(), (modified, metadata.len()));
+        }
+    }
+
+    /// The files added, removed or modified since `earlier`
+    pub fn changes(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changes: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, state)| earlier.files.get(*path) != Some(state))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changes.extend(
+            earlier
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .cloned(),
+        );
+        changes
+    }
+}
+
+/// The inputs of `day`, examples first, including extra examples like
+/// `test_inputs/day_10_cathode_ray_tube_3.txt`.
+pub fn inputs(day: &Day) -> Vec<String> {
+    let mut inputs = vec![];
+    for dir in ["test_inputs", "inputs"] {
+        let mut files: Vec<String> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with(&day.name()))
+            .map(|name| format!("{}/{}", dir, name))
+            .collect();
+        files.sort();
+        inputs.extend(files);
+    }
+    inputs
+}
+
+fn watched(day: &Day) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = CODE_PATHS.iter().map(PathBuf::from).collect();
+    paths.push(ANSWERS_PATH.into());
+    paths.push(PARAMS_PATH.into());
+    paths.extend(inputs(day).into_iter().map(PathBuf::from));
+    paths
+}
+
+/// What a run of one part of one input gave
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunResult {
+    Answer(Answer),
+    /// The error output of the run
+    Failed(String),
+}
+
+/// One line for the runs of `input`: PASS when every expected answer
+/// matched, FAIL when one did not or a run failed, and ---- when nothing is
+/// expected. Answers that changed since the `previous` run show what they
+/// were. The error output of failed runs follows, indented.
+pub fn summary(
+    input: &str,
+    results: &[(Part, RunResult)],
+    expected: Option<&Expected>,
+    previous: &HashMap<(String, Part), RunResult>,
+) -> String {
+    let mut pass = false;
+    let mut fail = false;
+    let mut parts = vec![];
+    let mut errors: Vec<&str> = vec![];
+    for (part, result) in results {
+        let mut text = format!("part {}: ", part);
+        match result {
+            RunResult::Answer(answer) => {
+                text += &answer.short();
+                match expected.and_then(|e| e.answer(*part)) {
+                    Some(expected) if expected == answer => pass = true,
+                    Some(expected) => {
+                        fail = true;
+                        text += &format!(" (expected {})", expected.short());
+                    }
+                    None => {}
+                }
+            }
+            RunResult::Failed(error) => {
+                fail = true;
+                text += "error";
+                // A malformed input fails both parts the same way
+                if !errors.contains(&error.as_str()) {
+                    errors.push(error);
+                }
+            }
+        }
+        match previous.get(&(input.to_owned(), *part)) {
+            Some(RunResult::Answer(was)) if result != &RunResult::Answer(was.clone()) => {
+                text += &format!(" (was {})", was.short());
+            }
+            _ => {}
+        }
+        parts.push(text);
+    }
+    let status = match (fail, pass) {
+        (true, _) => "FAIL",
+        (false, true) => "PASS",
+        (false, false) => "----",
+    };
+    let mut summary = format!("  {}  {}  {}", status, input, parts.join("  "));
+    for line in errors.iter().flat_map(|error| error.lines()) {
+        summary += &format!("\n        {}", line);
+    }
+    summary
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut output = vec![];
+        let _ = pipe.read_to_end(&mut output);
+        output
+    })
+}
+
+/// Polls the watched files of a day against the snapshot of the last run.
+struct Watch<'a> {
+    day: &'a Day,
+    snapshot: Snapshot,
+}
+
+impl Watch<'_> {
+    fn take(&self) -> Snapshot {
+        Snapshot::take(&watched(self.day))
+    }
+
+    fn changed(&self) -> bool {
+        self.take() != self.snapshot
+    }
+
+    /// Runs `command` to its end. `None` if a watched file changed first,
+    /// which stops it.
+    fn run(&self, command: &mut Command) -> io::Result<Option<Output>> {
+        let mut child: Child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = read_in_background(child.stdout.take().expect("stdout is piped"));
+        let stderr = read_in_background(child.stderr.take().expect("stderr is piped"));
+        let mut last_poll = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(Output {
+                    status,
+                    stdout: stdout.join().unwrap_or_default(),
+                    stderr: stderr.join().unwrap_or_default(),
+                }));
+            }
+            if last_poll.elapsed() >= POLL_INTERVAL {
+                if self.changed() {
+                    let _ = child.kill();
+                    child.wait()?;
+                    return Ok(None);
+                }
+                last_poll = Instant::now();
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// `cargo build` of the running `aoc`, with the same profile and features
+fn build_command() -> Command {
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()));
+    command.args(["build", "--bin", "aoc", "--message-format", "short"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    if cfg!(feature = "client") {
+        command.args(["--features", "client"]);
+    }
+    command
+}
+
+/// The answer printed by `aoc run --format json`, or its errors
+fn run_result(output: Output) -> RunResult {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return RunResult::Failed(stderr.trim_end().to_owned());
+    }
+    let answer = stdout
+        .lines()
+        .last()
+        .and_then(json::parse_object)
+        .and_then(|fields| {
+            let (_, answer) = fields.into_iter().find(|(key, _)| key == "answer")?;
+            answer.to_answer()
+        });
+    match answer {
+        Some(answer) => RunResult::Answer(answer),
+        None => RunResult::Failed(format!("Unexpected output: {}", stdout.trim_end())),
+    }
+}
+
+/// The parts with an expected answer, or both if none is known yet. Parts
+/// without one might not finish at all (day 17).
+fn parts_to_run(expected: Option<&Expected>) -> Vec<Part> {
+    let parts: Vec<Part> = Part::BOTH
+        .into_iter()
+        .filter(|&part| expected.is_some_and(|e| e.answer(part).is_some()))
+        .collect();
+    if parts.is_empty() {
+        Part::BOTH.to_vec()
+    } else {
+        parts
+    }
+}
+
+fn describe(changes: &[PathBuf]) -> String {
+    let mut names: Vec<String> = changes
+        .iter()
+        .take(3)
+        .map(|path| path.display().to_string())
+        .collect();
+    if changes.len() > 3 {
+        names.push(format!("{} more", changes.len() - 3));
+    }
+    names.join(", ")
+}
+
+/// Builds the runner if `needs_build` and runs the parts of every input.
+/// `false` if a watched file changed first.
+fn run_day(
+    watch: &Watch,
+    exe: &Path,
+    needs_build: &mut bool,
+    previous: &mut HashMap<(String, Part), RunResult>,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    let day = watch.day;
+    if *needs_build {
+        writeln!(out, "  Building...")?;
+        let Some(output) = watch.run(&mut build_command())? else {
+            return Ok(false);
+        };
+        if !output.status.success() {
+            writeln!(out, "  BUILD FAILED")?;
+            for line in String::from_utf8_lossy(&output.stderr).lines() {
+                if !line.trim_start().starts_with("Compiling") {
+                    writeln!(out, "        {}", line)?;
+                }
+            }
+            return Ok(true);
+        }
+        *needs_build = false;
+    }
+
+    let manifest = match Manifest::load(ANSWERS_PATH) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            writeln!(out, "  {}", e)?;
+            Manifest::default()
+        }
+    };
+    for input in inputs(day) {
+        if fs::metadata(&input).is_ok_and(|file| file.len() == 0) {
+            writeln!(out, "  ----  {}  empty", input)?;
+            continue;
+        }
+        let expected = manifest.find(&input);
+        let mut results = vec![];
+        for part in parts_to_run(expected) {
+            let mut command = Command::new(exe);
+            command.args(["run", &day.number.to_string(), "--input", &input]);
+            command.args(["--part", &part.to_string(), "--format", "json"]);
+            let Some(output) = watch.run(&mut command)? else {
+                return Ok(false);
+            };
+            results.push((part, run_result(output)));
+        }
+        writeln!(out, "{}", summary(&input, &results, expected, previous))?;
+        for (part, result) in results {
+            previous.insert((input.clone(), part), result);
+        }
+    }
+    Ok(true)
+}
+
+/// Watches `day` until the process is stopped, writing a summary to `out`
+/// after each run.
+pub fn watch(day: &Day, out: &mut impl Write) -> io::Result<()> {
+    let exe = env::current_exe()?;
+    let mut watch = Watch {
+        day,
+        snapshot: Snapshot::default(),
+    };
+    watch.snapshot = watch.take();
+    writeln!(
+        out,
+        "Watching day {:02}: src/, {}, {} and {} inputs (Ctrl-C stops)",
+        day.number,
+        ANSWERS_PATH,
+        PARAMS_PATH,
+        inputs(day).len()
+    )?;
+
+    // The running binary was just built
+    let mut needs_build = false;
+    let mut previous = HashMap::new();
+    loop {
+        let finished = run_day(&watch, &exe, &mut needs_build, &mut previous, out)?;
+        let changes = loop {
+            let snapshot = watch.take();
+            let changes = snapshot.changes(&watch.snapshot);
+            watch.snapshot = snapshot;
+            // An interrupted run starts over right away
+            if !changes.is_empty() || !finished {
+                break changes;
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+        needs_build |= changes
+            .iter()
+            .any(|path| CODE_PATHS.iter().any(|code| path.starts_with(code)));
+        writeln!(
+            out,
+            "Day {:02}, changed {}:",
+            day.number,
+            describe(&changes)
+        )?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = env::temp_dir().join(format!("aoc_watch_{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let (a, b) = (dir.join("src").join("a.rs"), dir.join("b.txt"));
+        fs::write(&a, "1").unwrap();
+        let paths = [dir.join("src"), b.clone()];
+        let before = Snapshot::take(&paths);
+
+        fs::write(&b, "2").unwrap();
+        fs::write(&a, "12").unwrap();
+        let after = Snapshot::take(&paths);
+        assert_eq!(after.changes(&before), vec![b.clone(), a.clone()]);
+        assert!(after.changes(&after).is_empty());
+
+        fs::remove_file(&b).unwrap();
+        assert_eq!(Snapshot::take(&paths).changes(&after), vec![b]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_summary() {
+        let expected = Expected {
+            input: "inputs/day_10_cathode_ray_tube.txt".to_owned(),
+            day: 10,
+            part_one: Some(Answer::Number(13140)),
+            part_two: None,
+        };
+        let input = expected.input.as_str();
+        let results = [
+            (Part::One, RunResult::Answer(Answer::Number(13140))),
+            (Part::Two, RunResult::Answer(Answer::from("##..\n..##"))),
+        ];
+        let mut previous = HashMap::new();
+        assert_eq!(
+            summary(input, &results, Some(&expected), &previous),
+            "  PASS  inputs/day_10_cathode_ray_tube.txt  part 1: 13140  part 2: [2 lines]"
+        );
+        assert_eq!(
+            summary(input, &results, None, &previous),
+            "  ----  inputs/day_10_cathode_ray_tube.txt  part 1: 13140  part 2: [2 lines]"
+        );
+
+        previous.insert((input.to_owned(), Part::One), results[0].1.clone());
+        let results = [
+            (Part::One, RunResult::Answer(Answer::Number(-1))),
+            (Part::Two, RunResult::Failed("error: x\n --> y".to_owned())),
+        ];
+        let both_failed = [
+            (Part::One, results[1].1.clone()),
+            (Part::Two, results[1].1.clone()),
+        ];
+        assert_eq!(
+            summary(input, &both_failed, None, &HashMap::new()),
+            "  FAIL  inputs/day_10_cathode_ray_tube.txt  part 1: error  part 2: error\n        \
+             error: x\n         --> y"
+        );
+        assert_eq!(
+            summary(input, &results, Some(&expected), &previous),
+            "  FAIL  inputs/day_10_cathode_ray_tube.txt  \
+             part 1: -1 (expected 13140) (was 13140)  part 2: error\n        \
+             error: x\n         --> y"
+        );
+    }
+
+    #[test]
+    fn test_parts_to_run() {
+        let mut expected = Expected {
+            input: "inputs/day_17_pyroclastic_flow.txt".to_owned(),
+            day: 17,
+            part_one: None,
+            part_two: None,
+        };
+        assert_eq!(parts_to_run(None), Part::BOTH);
+        assert_eq!(parts_to_run(Some(&expected)), Part::BOTH);
+        expected.part_one = Some(Answer::Number(3068));
+        assert_eq!(parts_to_run(Some(&expected)), [Part::One]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Checks every solver against the expected answers in answers.toml
+use std::fs::{self, File};
+
+use advent_of_code_2022::manifest::{Manifest, ANSWERS_PATH};
+use advent_of_code_2022::params::{ParamsConfig, PARAMS_PATH, STRATEGY};
+use advent_of_code_2022::stream;
+use advent_of_code_2022::{days, Part, DAYS};
+
+/// Solves every input in the manifest with every strategy of its day, after
+/// passing its contents through `edit`, and returns the differences from the
+/// expected answers.
+fn check_answers(edit: impl Fn(&str) -> String) -> Vec<String> {
+    let manifest = Manifest::load(ANSWERS_PATH).unwrap();
+    // Puzzle constants of the example inputs
+    let params = ParamsConfig::load(PARAMS_PATH).unwrap();
+
+    let mut failures = vec![];
+    for expected in &manifest.entries {
+        let day = days::find(expected.day)
+            .unwrap_or_else(|| panic!("No solution for day {}", expected.day));
+        let contents = fs::read_to_string(&expected.input)
+            .unwrap_or_else(|e| panic!("Cannot read file {}: {}", expected.input, e));
+        let contents = edit(&contents);
+        let solver = day
+            .solver
+            .with_params(&params.for_input(&expected.input))
+            .unwrap();
+        let parsed = match solver.parse_input(&contents) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                failures.push(format!("{}:\n{}", expected.input, e));
+                continue;
+            }
+        };
+
+        // The default strategy of the days that have a single one
+        let mut strategies: Vec<Option<&str>> = solver.strategies().into_iter().map(Some).collect();
+        if strategies.is_empty() {
+            strategies.push(None);
+        }
+        for strategy in strategies {
+            let (solver, name) = match strategy {
+                Some(name) => (
+                    solver
+                        .with_params(&[(STRATEGY.to_owned(), name.to_owned())])
+                        .unwrap(),
+                    format!(" ({})", name),
+                ),
+                None => (solver.with_params(&[]).unwrap(), String::new()),
+            };
+            for part in Part::BOTH {
+                let Some(expected_answer) = expected.answer(part) else {
+                    continue;
+                };
+                // Like the brute force of day 17 part 2
+                if !solver.finishes(part) {
+                    continue;
+                }
+                match solver.solve_parsed(parsed.as_ref(), part) {
+                    Ok(answer) if &answer == expected_answer => {}
+                    Ok(answer) => failures.push(format!(
+                        "{}, part {}{}: expected {}, got {}",
+                        expected.input, part, name, expected_answer, answer
+                    )),
+                    Err(e) => {
+                        failures.push(format!("{}, part {}{}:\n{}", expected.input, part, name, e))
+                    }
+                }
+            }
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn answers_match_manifest() {
+    let failures = check_answers(str::to_owned);
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+/// The inputs as saved by an editor on Windows: a byte order mark, `\r\n`
+/// line endings, spaces at the ends of lines and no line break at the end
+#[test]
+fn answers_survive_windows_editors() {
+    let failures = check_answers(|contents| {
+        let lines: Vec<String> = contents.lines().map(|line| format!("{}  ", line)).collect();
+        format!("\u{feff}{}", lines.join("\r\n"))
+    });
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+/// The days that can be streamed give the same answers when they are
+#[test]
+fn streamed_answers_match_manifest() {
+    let manifest = Manifest::load(ANSWERS_PATH).unwrap();
+    let params = ParamsConfig::load(PARAMS_PATH).unwrap();
+
+    let mut failures = vec![];
+    let mut streamed = 0;
+    for expected in &manifest.entries {
+        let day = days::find(expected.day).unwrap();
+        let solver = day
+            .solver
+            .with_params(&params.for_input(&expected.input))
+            .unwrap();
+        let parts: Vec<Part> = Part::BOTH
+            .into_iter()
+            .filter(|&part| expected.answer(part).is_some())
+            .collect();
+        let file = File::open(&expected.input).unwrap();
+        let Some(answers) = stream::solve(solver.as_ref(), &parts, file, &expected.input) else {
+            continue;
+        };
+        streamed += 1;
+        match answers {
+            Ok(answers) => {
+                for (part, answer) in answers {
+                    if Some(&answer) != expected.answer(part) {
+                        failures.push(format!(
+                            "{}, part {}: got {} streamed",
+                            expected.input, part, answer
+                        ));
+                    }
+                }
+            }
+            Err(e) => failures.push(format!("{}:\n{}", expected.input, e)),
+        }
+    }
+
+    assert!(streamed > 0);
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+/// Every input of a registered day, including extra examples like
+/// test_inputs/day_10_cathode_ray_tube_3.txt, needs an entry.
+#[test]
+fn every_input_is_in_manifest() {
+    let manifest = Manifest::load(ANSWERS_PATH).unwrap();
+
+    let mut missing = vec![];
+    for dir in ["test_inputs", "inputs"] {
+        for file in fs::read_dir(dir).unwrap() {
+            let file_name = file.unwrap().file_name().into_string().unwrap();
+            let path = format!("{}/{}", dir, file_name);
+            let registered = DAYS.iter().any(|day| file_name.starts_with(&day.name()));
+            if registered && manifest.find(&path).is_none() {
+                missing.push(path);
+            }
+        }
+    }
+    missing.sort();
+
+    assert!(
+        missing.is_empty(),
+        "Missing in {}: {:?}",
+        ANSWERS_PATH,
+        missing
+    );
+}